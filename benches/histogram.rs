@@ -0,0 +1,30 @@
+//! Scalar vs. unrolled byte histogram on a multi-megabyte sample
+//!
+//! Run with: cargo bench --bench histogram
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use entropy_forge::entropy::{ChaChaEntropy, EntropySource};
+use entropy_forge::quality::QualityMetrics;
+
+fn histogram(c: &mut Criterion) {
+    let mut random = vec![0u8; 8 * 1024 * 1024];
+    ChaChaEntropy::new([1; 32]).fill_bytes(&mut random);
+    let constant = vec![0u8; random.len()];
+
+    let mut group = c.benchmark_group("byte_histogram");
+    group.throughput(Throughput::Bytes(random.len() as u64));
+
+    for (name, data) in [("random", &random), ("constant", &constant)] {
+        group.bench_with_input(BenchmarkId::new("scalar", name), data, |b, data| {
+            b.iter(|| QualityMetrics::byte_histogram(black_box(data)))
+        });
+        group.bench_with_input(BenchmarkId::new("fast", name), data, |b, data| {
+            b.iter(|| QualityMetrics::byte_histogram_fast(black_box(data)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, histogram);
+criterion_main!(benches);
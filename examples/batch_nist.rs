@@ -0,0 +1,67 @@
+//! Batch NIST testing over every file in a directory
+//!
+//! Usage: cargo run --example batch_nist -- <directory>
+
+use entropy_forge::quality::BatchReport;
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+fn main() {
+    let dir = match env::args().nth(1) {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            eprintln!("Usage: batch_nist <directory>");
+            process::exit(1);
+        }
+    };
+    
+    println!("=== Batch NIST Testing ===\n");
+    println!("Directory: {}\n", dir.display());
+    
+    // Load every regular file in the directory
+    let mut paths: Vec<PathBuf> = match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file())
+            .collect(),
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", dir.display(), e);
+            process::exit(1);
+        }
+    };
+    paths.sort();
+    
+    let mut samples = Vec::new();
+    for path in &paths {
+        match fs::read(path) {
+            Ok(data) => {
+                let name = path.file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                samples.push((name, data));
+            }
+            Err(e) => eprintln!("Skipping {}: {}", path.display(), e),
+        }
+    }
+    
+    let report = BatchReport::run(
+        samples.iter().map(|(name, data)| (name.as_str(), data.as_slice()))
+    );
+    
+    println!("{:<32} {:>10} {:>8}  Weakest test", "File", "Bytes", "Passed");
+    for entry in &report.entries {
+        println!("{:<32} {:>10} {:>5}/{:<2}  {} (p={:.4})",
+            entry.name,
+            entry.bytes,
+            entry.passed,
+            entry.total,
+            entry.weakest_test,
+            entry.weakest_p_value
+        );
+    }
+    
+    println!("\nFiles passing all tests: {}/{}",
+        report.samples_passed(),
+        report.entries.len()
+    );
+}
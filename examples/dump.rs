@@ -0,0 +1,71 @@
+//! Dump raw entropy to a file for external test suites
+//!
+//! Usage: cargo run --release --example dump -- --source <system|mock|chacha>
+//!        --bytes <n> --out <path|-> [--seed <n>]
+//!
+//! Example: feed 100 MB of ChaCha20 output to PractRand
+//!
+//!     cargo run --release --example dump -- --source chacha --seed 1 \
+//!         --bytes 100000000 --out - | RNG_test stdin8
+
+use entropy_forge::entropy::{
+    dump_to_writer, ChaChaEntropy, EntropySource, MockEntropy, SeedableEntropy, SystemEntropy,
+};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::{env, process};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+fn usage() -> ! {
+    eprintln!("Usage: dump --source <system|mock|chacha> --bytes <n> --out <path|-> [--seed <n>]");
+    process::exit(1);
+}
+
+fn main() {
+    let mut source_kind = String::from("system");
+    let mut bytes: Option<usize> = None;
+    let mut out: Option<String> = None;
+    let mut seed: u64 = 42;
+
+    let mut args = env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args.next().unwrap_or_else(|| usage());
+        match flag.as_str() {
+            "--source" => source_kind = value,
+            "--bytes" => bytes = Some(value.parse().unwrap_or_else(|_| usage())),
+            "--out" => out = Some(value),
+            "--seed" => seed = value.parse().unwrap_or_else(|_| usage()),
+            _ => usage(),
+        }
+    }
+
+    let (Some(bytes), Some(out)) = (bytes, out) else { usage() };
+
+    let mut source: Box<dyn EntropySource> = match source_kind.as_str() {
+        "system" => Box::new(SystemEntropy::new()),
+        "mock" => Box::new(MockEntropy::new(seed)),
+        "chacha" => Box::new(ChaChaEntropy::from_seed_u64(seed)),
+        _ => usage(),
+    };
+
+    let writer: Box<dyn Write> = if out == "-" {
+        Box::new(io::stdout().lock())
+    } else {
+        match File::create(&out) {
+            Ok(file) => Box::new(file),
+            Err(e) => {
+                eprintln!("Failed to create {}: {}", out, e);
+                process::exit(1);
+            }
+        }
+    };
+
+    match dump_to_writer(&mut source, bytes, CHUNK_SIZE, BufWriter::new(writer)) {
+        Ok(written) => eprintln!("Wrote {} bytes from {}", written, source.name()),
+        Err(e) => {
+            eprintln!("Write failed: {}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -1,23 +1,34 @@
 //! Comprehensive quality check example
+//!
+//! Usage: cargo run --example quality_check -- [--preset <name>]
+//!
+//! Presets: quick-smoke, fips-quick, standard-audit, full-nist. Without
+//! one, every built-in test runs on 500,000 bytes.
 
 use entropy_forge::entropy::{EntropySource, SystemEntropy};
-use entropy_forge::quality::{QualityMetrics, NistTests};
+use entropy_forge::quality::{Preset, QualityMetrics, TestSuite};
+use std::{env, process};
 
 fn main() {
+    let (sample_size, suite) = match parse_preset() {
+        Some(preset) => preset.configure(),
+        None => (500_000, TestSuite::default()),
+    };
+    
     println!("=== Entropy Quality Check ===\n");
     
     let mut entropy = SystemEntropy::new();
     println!("Testing: {}", entropy.name());
-    println!("Sample size: 500,000 bytes\n");
+    println!("Sample size: {} bytes\n", sample_size);
     
     // Run quality metrics
     println!("--- Basic Metrics ---");
-    let metrics = QualityMetrics::analyze(&mut entropy, 500_000);
+    let metrics = QualityMetrics::analyze(&mut entropy, sample_size);
     
     println!("Shannon Entropy:  {:.4} bits/byte (max: 8.0)", metrics.shannon_entropy);
     println!("Min-Entropy:      {:.4} bits/byte", metrics.min_entropy);
     println!("Mean byte value:  {:.2} (ideal: 127.5)", metrics.mean);
-    println!("Chi-square stat:  {:.2}", metrics.chi_square);
+    println!("Chi-square stat:  {:.2} (p = {:.4})", metrics.chi_square, metrics.chi_square_p_value);
     println!("Longest bit run:  {} bits", metrics.longest_run);
     println!("Overall Score:    {:.1}/100", metrics.overall_score());
     
@@ -33,12 +44,18 @@ fn main() {
     
     // Run NIST tests
     println!("\n--- NIST SP 800-22 Tests ---");
-    let mut data = vec![0u8; 500_000];
+    let mut data = vec![0u8; sample_size];
     entropy.fill_bytes(&mut data);
-    let nist_results = NistTests::run_all_tests(&data);
+    let nist_results = suite.run(&data);
     
     let mut passed = 0;
+    let mut total = 0;
     for (name, p_value) in nist_results {
+        let Some(p_value) = p_value else {
+            println!("{:<30} skipped (sample too small)", name);
+            continue;
+        };
+        total += 1;
         let status = if p_value >= 0.01 {
             passed += 1;
             "✓ Pass"
@@ -46,12 +63,12 @@ fn main() {
             "✗ Fail"
         };
         
-        println!("{:<20} p={:.4}  {}", name, p_value, status);
+        println!("{:<30} p={:.4}  {}", name, p_value, status);
     }
     
-    println!("\nNIST Tests Passed: {}/5", passed);
+    println!("\nNIST Tests Passed: {}/{}", passed, total);
     
-    if passed >= 4 {
+    if passed + 1 >= total {
         println!("\n✓ Source passes statistical randomness tests");
     } else {
         println!("\n⚠ Source shows signs of non-randomness");
@@ -62,9 +79,27 @@ fn main() {
     println!("First 10 bytes: {}", 
         hex::encode(&data[..10]));
     println!("Middle 10 bytes: {}", 
-        hex::encode(&data[250_000..250_010]));
+        hex::encode(&data[sample_size / 2..sample_size / 2 + 10]));
     println!("Last 10 bytes: {}", 
         hex::encode(&data[data.len()-10..]));
     
     println!("\nQuality check complete!");
 }
+
+fn parse_preset() -> Option<Preset> {
+    let mut args = env::args().skip(1);
+    match (args.next().as_deref(), args.next(), args.next()) {
+        (None, _, _) => None,
+        (Some("--preset"), Some(name), None) => match name.parse() {
+            Ok(preset) => Some(preset),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        },
+        _ => {
+            eprintln!("Usage: quality_check [--preset <name>]");
+            process::exit(1);
+        }
+    }
+}
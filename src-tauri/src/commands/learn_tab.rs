@@ -1,6 +1,7 @@
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
 use entropy_forge::learn::{EncryptionProcess, EntropyProcess, NistProcess};
+use entropy_forge::entropy::SystemEntropy;
 
 #[derive(Serialize, Deserialize)]
 pub struct SerializedBitOp {
@@ -170,7 +171,7 @@ pub fn get_nist_steps(text: String) -> NistStepsResponse {
 #[tauri::command]
 pub fn get_nist_steps_random(count: usize) -> NistStepsResponse {
     let mut process = NistProcess::new();
-    process.generate_random(count);
+    process.generate_random(&mut SystemEntropy::new(), count);
 
     let mut serialized_steps = Vec::new();
     for step in process.steps {
@@ -1,6 +1,6 @@
 use serde::{Serialize, Deserialize};
-use entropy_forge::quality::{QualityMetrics, NistTests};
-use entropy_forge::entropy::{SystemEntropy, EntropySource};
+use entropy_forge::quality::QualityReport;
+use entropy_forge::entropy::SystemEntropy;
 
 #[derive(Serialize, Deserialize)]
 pub struct NistResult {
@@ -14,6 +14,7 @@ pub struct QualityResponse {
     pub shannon_entropy: f64,
     pub min_entropy: f64,
     pub chi_square: f64,
+    pub chi_square_p_value: f64,
     pub mean: f64,
     pub longest_run: usize,
     pub overall_score: f64,
@@ -25,17 +26,14 @@ pub struct QualityResponse {
 pub fn run_quality_tests(sample_size: usize) -> QualityResponse {
     let mut entropy = SystemEntropy::new();
 
-    // Run quality metrics
-    let metrics = QualityMetrics::analyze(&mut entropy, sample_size);
+    // Metrics and NIST results share one sample
+    let report = QualityReport::generate(&mut entropy, sample_size);
+    let metrics = &report.metrics;
 
-    // Run NIST tests
-    let mut data = vec![0u8; sample_size];
-    entropy.fill_bytes(&mut data);
-
-    let nist = NistTests::run_all_tests(&data);
-    let nist_results = nist.into_iter().map(|(name, p_value)| {
+    let nist_results = report.nist_results.iter().map(|(name, p_value)| {
+        let p_value = *p_value;
         NistResult {
-            name: name.to_string(),
+            name: name.clone(),
             p_value,
             passed: p_value >= 0.01
         }
@@ -45,6 +43,7 @@ pub fn run_quality_tests(sample_size: usize) -> QualityResponse {
         shannon_entropy: metrics.shannon_entropy,
         min_entropy: metrics.min_entropy,
         chi_square: metrics.chi_square,
+        chi_square_p_value: metrics.chi_square_p_value,
         mean: metrics.mean,
         longest_run: metrics.longest_run,
         overall_score: metrics.overall_score(),
@@ -1,5 +1,5 @@
 use serde::{Serialize, Deserialize};
-use entropy_forge::crypto::StreamCipher;
+use entropy_forge::crypto::{self, StreamCipher, OutputFormat};
 use entropy_forge::entropy::SystemEntropy;
 
 #[derive(Serialize, Deserialize)]
@@ -9,16 +9,13 @@ pub struct EncryptResponse {
 }
 
 #[tauri::command]
-pub fn encrypt_decrypt(plaintext: String, hex_output: bool) -> EncryptResponse {
+pub fn encrypt_decrypt(plaintext: String, hex_output: bool) -> Result<EncryptResponse, String> {
     let temp_entropy = SystemEntropy::new();
-    let mut cipher = StreamCipher::new(temp_entropy);
+    let mut cipher = StreamCipher::new_secure(temp_entropy).map_err(|e| e.to_string())?;
     let output = cipher.process(plaintext.as_bytes());
 
-    let ciphertext = if hex_output {
-        hex::encode(&output)
-    } else {
-        String::from_utf8_lossy(&output).to_string()
-    };
+    let format = if hex_output { OutputFormat::Hex } else { OutputFormat::Text };
+    let ciphertext = crypto::encode(&output, format);
 
     let mut keystream_bytes = cipher.state().to_vec();
 
@@ -29,8 +26,8 @@ pub fn encrypt_decrypt(plaintext: String, hex_output: bool) -> EncryptResponse {
         keystream_bytes.truncate(64);
     }
 
-    EncryptResponse {
+    Ok(EncryptResponse {
         ciphertext,
         keystream_bytes,
-    }
+    })
 }
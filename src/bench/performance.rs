@@ -1,6 +1,6 @@
 //! Performance benchmarking for entropy sources
 
-use crate::entropy::EntropySource;
+use crate::entropy::{EntropySource, SeekableEntropy};
 use std::time::{Duration, Instant};
 
 /// Performance benchmark results
@@ -15,6 +15,9 @@ pub struct BenchmarkResult {
     /// Total bytes generated
     pub bytes_generated: usize,
     
+    /// Number of `fill_bytes` calls the bytes were requested in
+    pub requests: usize,
+    
     /// Total time taken
     pub duration: Duration,
 }
@@ -23,6 +26,9 @@ pub struct BenchmarkResult {
 pub struct PerformanceBench;
 
 impl PerformanceBench {
+    /// Bytes generated by [`estimate_duration`](Self::estimate_duration) to calibrate
+    pub const CALIBRATION_BYTES: usize = 64 * 1024;
+    
     /// Benchmark an entropy source
     ///
     /// Generates `total_bytes` and measures throughput and latency.
@@ -59,10 +65,271 @@ impl PerformanceBench {
             throughput_mbps,
             latency_us,
             bytes_generated: total_bytes,
+            requests: 1,
+            duration,
+        }
+    }
+    
+    /// Benchmark a trait-object entropy source
+    ///
+    /// Same as [`benchmark`](Self::benchmark), for callers holding
+    /// `Box<dyn EntropySource>` or `&mut dyn EntropySource`, e.g. a
+    /// heterogeneous list of sources.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::{EntropySource, MockEntropy, SystemEntropy};
+    /// use entropy_forge::bench::PerformanceBench;
+    ///
+    /// let mut sources: Vec<Box<dyn EntropySource>> = vec![
+    ///     Box::new(SystemEntropy::new()),
+    ///     Box::new(MockEntropy::new(1)),
+    /// ];
+    ///
+    /// for source in &mut sources {
+    ///     let result = PerformanceBench::benchmark_dyn(source.as_mut(), 100_000);
+    ///     println!("{}: {:.2} MB/s", source.name(), result.throughput_mbps);
+    /// }
+    /// ```
+    pub fn benchmark_dyn(source: &mut dyn EntropySource, total_bytes: usize) -> BenchmarkResult {
+        Self::benchmark(source, total_bytes)
+    }
+    
+    /// Benchmark an entropy source for a fixed wall-clock duration
+    ///
+    /// Repeatedly fills a `chunk_size` buffer until `duration` has elapsed,
+    /// then reports throughput based on the bytes actually produced. At
+    /// least one chunk is always generated, so even a zero duration yields
+    /// a usable result.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Entropy source to benchmark
+    /// * `duration` - Wall-clock budget
+    /// * `chunk_size` - Bytes requested per `fill_bytes` call (minimum 1)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use entropy_forge::entropy::SystemEntropy;
+    /// use entropy_forge::bench::PerformanceBench;
+    ///
+    /// let mut source = SystemEntropy::new();
+    /// let result = PerformanceBench::benchmark_duration(
+    ///     &mut source,
+    ///     Duration::from_millis(50),
+    ///     4096,
+    /// );
+    ///
+    /// println!("Throughput: {:.2} MB/s", result.throughput_mbps);
+    /// ```
+    pub fn benchmark_duration<E: ?Sized + EntropySource>(
+        source: &mut E,
+        duration: Duration,
+        chunk_size: usize,
+    ) -> BenchmarkResult {
+        let mut buffer = vec![0u8; chunk_size.max(1)];
+        let mut bytes_generated = 0;
+        let mut requests = 0;
+        
+        let start = Instant::now();
+        loop {
+            source.fill_bytes(&mut buffer);
+            bytes_generated += buffer.len();
+            requests += 1;
+            
+            if start.elapsed() >= duration {
+                break;
+            }
+        }
+        let elapsed = start.elapsed();
+        
+        let duration_secs = elapsed.as_secs_f64();
+        let throughput_mbps = (bytes_generated as f64 / duration_secs) / 1_000_000.0;
+        let latency_us = (duration_secs * 1_000_000.0) / bytes_generated as f64;
+        
+        BenchmarkResult {
+            throughput_mbps,
+            latency_us,
+            bytes_generated,
+            requests,
+            duration: elapsed,
+        }
+    }
+    
+    /// Benchmark many small `fill_bytes` calls
+    ///
+    /// Issues `num_requests` separate fills of `request_size` bytes, the
+    /// pattern of key-generation workloads. Per-call overhead that
+    /// [`benchmark`](Self::benchmark) amortizes away shows up here as lower
+    /// throughput and in [`BenchmarkResult::request_latency_us`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::SystemEntropy;
+    /// use entropy_forge::bench::PerformanceBench;
+    ///
+    /// let mut source = SystemEntropy::new();
+    /// let result = PerformanceBench::benchmark_small_requests(&mut source, 32, 10_000);
+    ///
+    /// println!("{:.2} µs per 32-byte key", result.request_latency_us());
+    /// ```
+    pub fn benchmark_small_requests<E: ?Sized + EntropySource>(
+        source: &mut E,
+        request_size: usize,
+        num_requests: usize,
+    ) -> BenchmarkResult {
+        let mut buffer = vec![0u8; request_size];
+        
+        let start = Instant::now();
+        for _ in 0..num_requests {
+            source.fill_bytes(&mut buffer);
+        }
+        let duration = start.elapsed();
+        
+        let bytes_generated = request_size * num_requests;
+        let duration_secs = duration.as_secs_f64();
+        let throughput_mbps = (bytes_generated as f64 / duration_secs) / 1_000_000.0;
+        let latency_us = (duration_secs * 1_000_000.0) / bytes_generated as f64;
+        
+        BenchmarkResult {
+            throughput_mbps,
+            latency_us,
+            bytes_generated,
+            requests: num_requests,
+            duration,
+        }
+    }
+    
+    /// Benchmark generating one stream with several threads
+    ///
+    /// Splits `total_bytes` into `threads` contiguous ranges (aligned to
+    /// [`SEEK_ALIGNMENT`](SeekableEntropy::SEEK_ALIGNMENT)); each worker
+    /// builds its own source with `source_factory`, seeks to the start of
+    /// its range and fills it. Throughput is the aggregate over the
+    /// wall-clock time of all workers, so comparing against one thread
+    /// shows how generation scales.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::ChaChaEntropy;
+    /// use entropy_forge::bench::PerformanceBench;
+    ///
+    /// let factory = || ChaChaEntropy::new([0; 32]);
+    /// let single = PerformanceBench::benchmark_parallel(factory, 4_000_000, 1);
+    /// let parallel = PerformanceBench::benchmark_parallel(factory, 4_000_000, 4);
+    ///
+    /// println!("4 threads: {:.1}x", parallel.throughput_mbps / single.throughput_mbps);
+    /// ```
+    pub fn benchmark_parallel<S, F>(source_factory: F, total_bytes: usize, threads: usize) -> BenchmarkResult
+    where
+        S: SeekableEntropy,
+        F: Fn() -> S + Sync,
+    {
+        let mut buffer = vec![0u8; total_bytes];
+        
+        let start = Instant::now();
+        let requests = Self::fill_parallel(&source_factory, &mut buffer, threads);
+        let duration = start.elapsed();
+        
+        let duration_secs = duration.as_secs_f64();
+        let throughput_mbps = (total_bytes as f64 / duration_secs) / 1_000_000.0;
+        let latency_us = (duration_secs * 1_000_000.0) / total_bytes as f64;
+        
+        BenchmarkResult {
+            throughput_mbps,
+            latency_us,
+            bytes_generated: total_bytes,
+            requests,
             duration,
         }
     }
     
+    // Fill `dest` as one stream split across up to `threads` workers,
+    // returning how many workers ran
+    fn fill_parallel<S, F>(source_factory: &F, dest: &mut [u8], threads: usize) -> usize
+    where
+        S: SeekableEntropy,
+        F: Fn() -> S + Sync,
+    {
+        if dest.is_empty() {
+            return 0;
+        }
+        
+        let align = S::SEEK_ALIGNMENT.max(1) as usize;
+        let chunk = dest.len().div_ceil(threads.max(1)).next_multiple_of(align);
+        
+        std::thread::scope(|scope| {
+            for (i, range) in dest.chunks_mut(chunk).enumerate() {
+                scope.spawn(move || {
+                    let mut source = source_factory();
+                    source.seek((i * chunk) as u64);
+                    source.fill_bytes(range);
+                });
+            }
+        });
+        
+        dest.len().div_ceil(chunk)
+    }
+    
+    /// Estimate how long generating `target_bytes` will take
+    ///
+    /// Times a single calibration fill of up to
+    /// [`CALIBRATION_BYTES`](Self::CALIBRATION_BYTES) and extrapolates
+    /// linearly. The calibration bytes are drawn from `source`, so a
+    /// deterministic source is advanced by them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::SystemEntropy;
+    /// use entropy_forge::bench::PerformanceBench;
+    ///
+    /// let mut source = SystemEntropy::new();
+    /// let estimate = PerformanceBench::estimate_duration(&mut source, 10_000_000);
+    ///
+    /// println!("≈{:.2} s", estimate.as_secs_f64());
+    /// ```
+    pub fn estimate_duration<E: ?Sized + EntropySource>(source: &mut E, target_bytes: usize) -> Duration {
+        let calibration = Self::CALIBRATION_BYTES.min(target_bytes.max(1));
+        let mut buffer = vec![0u8; calibration];
+        
+        let start = Instant::now();
+        source.fill_bytes(&mut buffer);
+        let elapsed = start.elapsed();
+        
+        elapsed.mul_f64(target_bytes as f64 / calibration as f64)
+    }
+    
+    /// Throughput of `result` as a percentage of `baseline`
+    ///
+    /// Gives raw numbers a reference point, e.g. 200.0 means twice as fast
+    /// as the baseline. Returns 0.0 if the baseline throughput is not a
+    /// positive finite number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::{MockEntropy, SystemEntropy};
+    /// use entropy_forge::bench::PerformanceBench;
+    ///
+    /// let baseline = PerformanceBench::benchmark(&mut SystemEntropy::new(), 1_000_000);
+    /// let result = PerformanceBench::benchmark(&mut MockEntropy::new(42), 1_000_000);
+    ///
+    /// println!("{:.0}% of SystemEntropy", PerformanceBench::relative_to(&result, &baseline));
+    /// ```
+    pub fn relative_to(result: &BenchmarkResult, baseline: &BenchmarkResult) -> f64 {
+        if !(baseline.throughput_mbps.is_finite() && baseline.throughput_mbps > 0.0) {
+            return 0.0;
+        }
+        
+        result.throughput_mbps / baseline.throughput_mbps * 100.0
+    }
+    
     /// Run multiple iterations and return average
     pub fn benchmark_avg<E: ?Sized + EntropySource>(
         source: &mut E,
@@ -85,12 +352,68 @@ impl PerformanceBench {
             throughput_mbps: total_throughput / iterations as f64,
             latency_us: total_latency / iterations as f64,
             bytes_generated: total_bytes,
+            requests: iterations,
             duration: total_duration,
         }
     }
 }
 
 impl BenchmarkResult {
+    /// Average time per `fill_bytes` call in microseconds
+    ///
+    /// Returns 0.0 if no requests were made.
+    pub fn request_latency_us(&self) -> f64 {
+        if self.requests == 0 {
+            return 0.0;
+        }
+        
+        self.duration.as_secs_f64() * 1_000_000.0 / self.requests as f64
+    }
+    
+    /// Check that throughput hasn't regressed past `tolerance` of `baseline`
+    ///
+    /// `tolerance` is a fraction: 0.05 fails when this result's throughput
+    /// is below 95% of the baseline's. Being faster always passes. The error
+    /// gives both throughputs and the percentages, ready to print from a CI
+    /// gate. Passes if the baseline throughput is not a positive finite
+    /// number, as there is nothing to regress from.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use entropy_forge::entropy::ChaChaEntropy;
+    /// use entropy_forge::bench::PerformanceBench;
+    ///
+    /// let mut source = ChaChaEntropy::new([0; 32]);
+    /// let baseline = PerformanceBench::benchmark(&mut source, 10_000_000);
+    /// let current = PerformanceBench::benchmark(&mut source, 10_000_000);
+    ///
+    /// if let Err(message) = current.assert_not_slower_than(&baseline, 0.05) {
+    ///     eprintln!("{}", message);
+    ///     std::process::exit(1);
+    /// }
+    /// ```
+    pub fn assert_not_slower_than(&self, baseline: &BenchmarkResult, tolerance: f64) -> Result<(), String> {
+        let base = baseline.throughput_mbps;
+        if !(base.is_finite() && base > 0.0) {
+            return Ok(());
+        }
+        
+        let threshold = base * (1.0 - tolerance);
+        // Written so a NaN throughput fails too
+        if self.throughput_mbps >= threshold {
+            return Ok(());
+        }
+        
+        Err(format!(
+            "throughput regressed: {:.2} MB/s is {:.1}% below baseline {:.2} MB/s (tolerance {:.1}%)",
+            self.throughput_mbps,
+            (1.0 - self.throughput_mbps / base) * 100.0,
+            base,
+            tolerance * 100.0
+        ))
+    }
+    
     /// Format result as human-readable string
     pub fn format(&self) -> String {
         format!(
@@ -108,7 +431,7 @@ impl BenchmarkResult {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entropy::SystemEntropy;
+    use crate::entropy::{ChaChaEntropy, SystemEntropy};
     
     #[test]
     fn test_benchmark() {
@@ -120,6 +443,25 @@ mod tests {
         assert_eq!(result.bytes_generated, 10_000);
     }
     
+    #[test]
+    fn test_benchmark_parallel_matches_single_fill() {
+        let factory = || ChaChaEntropy::new([5; 32]);
+        
+        let result = PerformanceBench::benchmark_parallel(factory, 100_000, 2);
+        assert!(result.throughput_mbps > 0.0 && result.throughput_mbps.is_finite());
+        assert_eq!(result.bytes_generated, 100_000);
+        assert_eq!(result.requests, 2);
+        
+        // Uneven and unaligned lengths still split into word-aligned ranges
+        for (len, threads) in [(100_000, 2), (1_001, 3), (10, 8)] {
+            let mut expected = vec![0u8; len];
+            factory().fill_bytes(&mut expected);
+            let mut actual = vec![0u8; len];
+            PerformanceBench::fill_parallel(&factory, &mut actual, threads);
+            assert_eq!(actual, expected, "{} bytes on {} threads", len, threads);
+        }
+    }
+    
     #[test]
     fn test_benchmark_avg() {
         let mut source = SystemEntropy::new();
@@ -128,4 +470,97 @@ mod tests {
         assert!(result.throughput_mbps > 0.0);
         assert_eq!(result.bytes_generated, 5_000);
     }
+    
+    #[test]
+    fn test_benchmark_duration() {
+        let mut source = SystemEntropy::new();
+        let budget = Duration::from_millis(10);
+        let result = PerformanceBench::benchmark_duration(&mut source, budget, 1_024);
+        
+        assert!(result.bytes_generated > 0);
+        assert_eq!(result.bytes_generated % 1_024, 0);
+        assert!(result.duration >= budget);
+        assert!(result.duration < Duration::from_secs(5));
+    }
+    
+    #[test]
+    fn test_small_requests_expose_call_overhead() {
+        let mut source = SystemEntropy::new();
+        let small = PerformanceBench::benchmark_small_requests(&mut source, 32, 10_000);
+        let bulk = PerformanceBench::benchmark(&mut source, 32 * 10_000);
+        
+        assert_eq!(small.bytes_generated, bulk.bytes_generated);
+        assert_eq!(small.requests, 10_000);
+        assert_eq!(bulk.requests, 1);
+        assert!(small.throughput_mbps < bulk.throughput_mbps);
+        assert!(small.request_latency_us() < bulk.request_latency_us());
+    }
+    
+    #[test]
+    fn test_estimate_duration_scales() {
+        let mut source = SystemEntropy::new();
+        let small = PerformanceBench::estimate_duration(&mut source, 100_000);
+        let large = PerformanceBench::estimate_duration(&mut source, 100_000_000);
+        
+        // 1000x the bytes; allow wide margin for calibration timing noise
+        assert!(small > Duration::ZERO);
+        assert!(large > small * 10);
+        
+        assert_eq!(PerformanceBench::estimate_duration(&mut source, 0), Duration::ZERO);
+    }
+    
+    #[test]
+    fn test_relative_to_baseline() {
+        let result = |throughput_mbps| BenchmarkResult {
+            throughput_mbps,
+            latency_us: 0.0,
+            bytes_generated: 0,
+            requests: 0,
+            duration: Duration::ZERO,
+        };
+        
+        assert_eq!(PerformanceBench::relative_to(&result(240.0), &result(120.0)), 200.0);
+        assert_eq!(PerformanceBench::relative_to(&result(60.0), &result(120.0)), 50.0);
+        assert_eq!(PerformanceBench::relative_to(&result(60.0), &result(0.0)), 0.0);
+        assert_eq!(PerformanceBench::relative_to(&result(60.0), &result(f64::INFINITY)), 0.0);
+    }
+    
+    #[test]
+    fn test_assert_not_slower_than() {
+        let result = |throughput_mbps| BenchmarkResult {
+            throughput_mbps,
+            latency_us: 0.0,
+            bytes_generated: 0,
+            requests: 0,
+            duration: Duration::ZERO,
+        };
+        let baseline = result(200.0);
+        
+        let slower = result(180.0);
+        let message = slower.assert_not_slower_than(&baseline, 0.05).unwrap_err();
+        assert!(message.contains("10.0% below"), "{}", message);
+        assert!(message.contains("tolerance 5.0%"), "{}", message);
+        assert_eq!(slower.assert_not_slower_than(&baseline, 0.20), Ok(()));
+        
+        assert_eq!(result(250.0).assert_not_slower_than(&baseline, 0.0), Ok(()));
+        assert!(result(f64::NAN).assert_not_slower_than(&baseline, 0.5).is_err());
+        assert_eq!(slower.assert_not_slower_than(&result(0.0), 0.05), Ok(()));
+    }
+    
+    #[test]
+    fn test_benchmark_dyn_over_boxed_sources() {
+        use crate::entropy::{ChaChaEntropy, MockEntropy};
+        
+        let mut sources: Vec<Box<dyn EntropySource>> = vec![
+            Box::new(SystemEntropy::new()),
+            Box::new(MockEntropy::new(1)),
+            Box::new(ChaChaEntropy::new([0; 32])),
+        ];
+        
+        for source in &mut sources {
+            let result = PerformanceBench::benchmark_dyn(source.as_mut(), 10_000);
+            assert_eq!(result.bytes_generated, 10_000);
+            assert!(result.throughput_mbps > 0.0);
+        }
+    }
 }
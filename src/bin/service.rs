@@ -0,0 +1,7 @@
+//! Entropy Forge - JSON service over stdin/stdout
+
+use std::io;
+
+fn main() -> io::Result<()> {
+    entropy_forge::service::serve(io::stdin().lock(), io::stdout().lock())
+}
@@ -1,6 +1,34 @@
 //! Simple stream cipher implementation
 
-use crate::entropy::EntropySource;
+use crate::entropy::{EntropySource, SeekableEntropy};
+use thiserror::Error;
+
+/// Errors from constructing or using a [`StreamCipher`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CipherError {
+    /// The entropy source does not claim to be cryptographically secure
+    #[error("entropy source \"{0}\" is not cryptographically secure")]
+    InsecureSource(String),
+    
+    /// Encrypt or decrypt needs a keystream that can be replayed
+    #[error("entropy source \"{0}\" is not deterministic, so its keystream cannot be replayed")]
+    NotReplayable(String),
+    
+    /// The byte counter would pass `u64::MAX`
+    #[error("keystream position would pass 2^64 bytes")]
+    CounterOverflow,
+    
+    /// A finite source has less keystream left than the data needs
+    #[error("entropy source \"{name}\" has {available} keystream bytes left, {requested} needed")]
+    KeystreamExhausted {
+        /// Source name
+        name: String,
+        /// Bytes of data to process
+        requested: usize,
+        /// Bytes the source can still supply
+        available: u64,
+    },
+}
 
 /// Simple XOR stream cipher
 ///
@@ -32,19 +60,78 @@ use crate::entropy::EntropySource;
 pub struct StreamCipher<E: EntropySource> {
     entropy: E,
     state: Vec<u8>,
-    bytes_processed: usize,
+    state_capacity: usize,
+    bytes_processed: u64,
+    counter_overflowed: bool,
 }
 
 impl<E: EntropySource> StreamCipher<E> {
+    /// Keystream bytes kept for [`state`](Self::state) unless configured otherwise
+    pub const DEFAULT_STATE_CAPACITY: usize = 64;
+    
     /// Create a new cipher with given entropy source
     pub fn new(entropy: E) -> Self {
+        Self::with_state_capacity(entropy, Self::DEFAULT_STATE_CAPACITY)
+    }
+    
+    /// Create a cipher that keeps the last `capacity` keystream bytes
+    ///
+    /// [`state`](Self::state) then returns up to `capacity` of the most
+    /// recent keystream bytes, across calls to [`process`](Self::process),
+    /// for larger visualizations than the default 8×8 grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::SystemEntropy;
+    /// use entropy_forge::crypto::StreamCipher;
+    ///
+    /// let mut cipher = StreamCipher::with_state_capacity(SystemEntropy::new(), 256);
+    /// cipher.process(&[0u8; 1_000]);
+    /// assert_eq!(cipher.state().len(), 256);
+    /// ```
+    pub fn with_state_capacity(entropy: E, capacity: usize) -> Self {
         Self {
             entropy,
             state: Vec::new(),
+            state_capacity: capacity,
             bytes_processed: 0,
+            counter_overflowed: false,
         }
     }
     
+    /// Change how many recent keystream bytes are kept
+    ///
+    /// Shrinking drops the oldest bytes already kept.
+    pub fn set_state_capacity(&mut self, capacity: usize) {
+        self.state_capacity = capacity;
+        self.trim_state();
+    }
+    
+    /// Create a new cipher, refusing insecure entropy sources
+    ///
+    /// Returns [`CipherError::InsecureSource`] unless the source reports
+    /// [`is_cryptographically_secure`](EntropySource::is_cryptographically_secure).
+    /// Use this when encrypting real data so a test source such as
+    /// `MockEntropy` cannot slip through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::{MockEntropy, SystemEntropy};
+    /// use entropy_forge::crypto::StreamCipher;
+    ///
+    /// assert!(StreamCipher::new_secure(SystemEntropy::new()).is_ok());
+    /// assert!(StreamCipher::new_secure(MockEntropy::new(42)).is_err());
+    /// ```
+    pub fn new_secure(entropy: E) -> Result<Self, CipherError> {
+        if !entropy.is_cryptographically_secure() {
+            return Err(CipherError::InsecureSource(entropy.name().to_string()));
+        }
+        
+        Ok(Self::new(entropy))
+    }
+    
     /// Process data (encrypt or decrypt - XOR is symmetric)
     ///
     /// Generates a fresh keystream and XORs with input data.
@@ -61,11 +148,10 @@ impl<E: EntropySource> StreamCipher<E> {
         let mut keystream = vec![0u8; data.len()];
         self.entropy.fill_bytes(&mut keystream);
         
-        // Store state for visualization (last 64 bytes)
-        self.state = keystream.iter()
-            .take(64.min(keystream.len()))
-            .copied()
-            .collect();
+        // Keep the most recent keystream for visualization
+        let recent = keystream.len().saturating_sub(self.state_capacity);
+        self.state.extend_from_slice(&keystream[recent..]);
+        self.trim_state();
         
         // XOR data with keystream
         let output: Vec<u8> = data.iter()
@@ -73,27 +159,134 @@ impl<E: EntropySource> StreamCipher<E> {
             .map(|(d, k)| d ^ k)
             .collect();
         
-        self.bytes_processed += data.len();
+        match self.bytes_processed.checked_add(data.len() as u64) {
+            Some(total) => self.bytes_processed = total,
+            None => {
+                self.bytes_processed = u64::MAX;
+                self.counter_overflowed = true;
+            }
+        }
         output
     }
     
+    /// Like [`process`](Self::process), but refuse data the keystream can't cover
+    ///
+    /// Returns [`CipherError::CounterOverflow`] if the byte counter would
+    /// pass `u64::MAX`, and [`CipherError::KeystreamExhausted`] if a finite
+    /// source (see [`remaining_bytes`](EntropySource::remaining_bytes)) has
+    /// less keystream left than `data` needs. `process` would instead
+    /// saturate the counter or XOR with the source's filler bytes. Nothing
+    /// is consumed on error.
+    pub fn try_process(&mut self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        if self.bytes_processed.checked_add(data.len() as u64).is_none() {
+            return Err(CipherError::CounterOverflow);
+        }
+        if let Some(available) = self.entropy.remaining_bytes() {
+            if available < data.len() as u64 {
+                return Err(CipherError::KeystreamExhausted {
+                    name: self.entropy.name().to_string(),
+                    requested: data.len(),
+                    available,
+                });
+            }
+        }
+        Ok(self.process(data))
+    }
+    
+    /// Encrypt `plaintext` from the start of the keystream
+    ///
+    /// Resets the cipher first, so the result depends only on the key
+    /// (the entropy source's seed) and can be undone by
+    /// [`decrypt`](Self::decrypt) on a cipher with the same key. Requires a
+    /// deterministic source; see [`is_replayable`](Self::is_replayable).
+    ///
+    /// Every call reuses the same keystream, so never encrypt two messages
+    /// under one key: XORing the ciphertexts cancels the keystream. Derive
+    /// a fresh key per message instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::ChaChaEntropy;
+    /// use entropy_forge::crypto::StreamCipher;
+    ///
+    /// let key = [42u8; 32];
+    /// let ciphertext = StreamCipher::new(ChaChaEntropy::new(key)).encrypt(b"attack at dawn").unwrap();
+    ///
+    /// let mut cipher = StreamCipher::new(ChaChaEntropy::new(key));
+    /// assert_eq!(cipher.decrypt(&ciphertext).unwrap(), b"attack at dawn");
+    /// ```
+    pub fn encrypt(&mut self, plaintext: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.process_from_start(plaintext)
+    }
+    
+    /// Decrypt `ciphertext` produced by [`encrypt`](Self::encrypt)
+    ///
+    /// Resets the cipher so the keystream lines up with the one used for
+    /// encryption. Requires a deterministic source.
+    pub fn decrypt(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>, CipherError> {
+        self.process_from_start(ciphertext)
+    }
+    
+    fn process_from_start(&mut self, data: &[u8]) -> Result<Vec<u8>, CipherError> {
+        if !self.is_replayable() {
+            return Err(CipherError::NotReplayable(self.entropy.name().to_string()));
+        }
+        
+        self.reset();
+        self.try_process(data)
+    }
+    
     /// Get current cipher state (for visualization)
     ///
-    /// Returns the last 64 bytes of keystream generated, or fewer if
-    /// less has been generated.
+    /// Returns the most recent keystream bytes, oldest first: up to
+    /// [`DEFAULT_STATE_CAPACITY`](Self::DEFAULT_STATE_CAPACITY) unless the
+    /// cipher was created [`with_state_capacity`](Self::with_state_capacity).
     pub fn state(&self) -> &[u8] {
         &self.state
     }
     
+    fn trim_state(&mut self) {
+        let excess = self.state.len().saturating_sub(self.state_capacity);
+        self.state.drain(..excess);
+    }
+    
     /// Get total bytes processed
-    pub fn bytes_processed(&self) -> usize {
+    ///
+    /// Counted in a `u64` on every target, so a 32-bit build does not wrap
+    /// after 4 GiB. The count saturates at `u64::MAX`; use
+    /// [`bytes_processed_checked`](Self::bytes_processed_checked) to tell
+    /// that apart from an exact count.
+    pub fn bytes_processed(&self) -> u64 {
         self.bytes_processed
     }
     
+    /// Total bytes processed, or `None` if the count passed `u64::MAX`
+    ///
+    /// Only reachable by [`seek`](Self::seek)ing close to the end; at
+    /// 1 GB/s, 2^64 bytes take over 500 years.
+    pub fn bytes_processed_checked(&self) -> Option<u64> {
+        (!self.counter_overflowed).then_some(self.bytes_processed)
+    }
+    
+    /// Whether [`reset`](Self::reset) replays the keystream
+    ///
+    /// True when the entropy source is deterministic, in which case
+    /// processing ciphertext after a reset decrypts it.
+    pub fn is_replayable(&self) -> bool {
+        self.entropy.is_deterministic()
+    }
+    
     /// Reset the cipher (restarts byte counter)
+    ///
+    /// Also resets the entropy source. For deterministic sources (see
+    /// [`is_replayable`](Self::is_replayable)) the keystream starts over, so
+    /// earlier output can be decrypted; otherwise only the counter and
+    /// visualization state are cleared.
     pub fn reset(&mut self) {
         self.state.clear();
         self.bytes_processed = 0;
+        self.counter_overflowed = false;
         self.entropy.reset();
     }
     
@@ -136,10 +329,25 @@ impl<E: EntropySource> StreamCipher<E> {
     }
 }
 
+impl<E: SeekableEntropy> StreamCipher<E> {
+    /// Continue from keystream byte `offset`
+    ///
+    /// Moves the source and sets [`bytes_processed`](Self::bytes_processed)
+    /// to the position it landed on, which is `offset` rounded down to
+    /// [`SEEK_ALIGNMENT`](SeekableEntropy::SEEK_ALIGNMENT). Clears the
+    /// visualization state.
+    pub fn seek(&mut self, offset: u64) {
+        self.entropy.seek(offset);
+        self.state.clear();
+        self.bytes_processed = self.entropy.position();
+        self.counter_overflowed = false;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entropy::MockEntropy;
+    use crate::entropy::{ChaChaEntropy, MockEntropy, RecordedEntropy};
     
     #[test]
     fn test_cipher_basic() {
@@ -182,6 +390,25 @@ mod tests {
         assert!(state.len() <= 64);
     }
     
+    #[test]
+    fn test_state_capacity_keeps_latest_keystream() {
+        let mut cipher = StreamCipher::with_state_capacity(MockEntropy::new(7), 128);
+        
+        // Zero plaintext makes the output the keystream itself
+        let mut keystream = cipher.process(&[0u8; 150]);
+        keystream.extend(cipher.process(&[0u8; 50]));
+        assert_eq!(cipher.state().len(), 128);
+        assert_eq!(cipher.state(), &keystream[200 - 128..]);
+        
+        cipher.set_state_capacity(16);
+        assert_eq!(cipher.state(), &keystream[200 - 16..]);
+        
+        // The default keeps the last 64 bytes, not the first
+        let mut default = StreamCipher::new(MockEntropy::new(7));
+        default.process(&[0u8; 200]);
+        assert_eq!(default.state(), &keystream[200 - 64..]);
+    }
+    
     #[test]
     fn test_bytes_processed() {
         let entropy = MockEntropy::new(42);
@@ -194,6 +421,89 @@ mod tests {
         assert_eq!(cipher.bytes_processed(), 10);
     }
     
+    #[test]
+    fn test_bytes_processed_does_not_wrap() {
+        // Past 4 GiB, where a 32-bit usize counter would have wrapped
+        let mut cipher = StreamCipher::new(ChaChaEntropy::new([1; 32]));
+        cipher.seek(5_000_000_000);
+        cipher.process(b"12345");
+        assert_eq!(cipher.bytes_processed(), 5_000_000_005);
+        assert_eq!(cipher.bytes_processed_checked(), Some(5_000_000_005));
+        
+        // Near the end of the counter, try_process refuses and process saturates
+        cipher.seek(u64::MAX - 3);
+        assert_eq!(cipher.bytes_processed(), u64::MAX - 3);
+        assert_eq!(cipher.try_process(b"12345"), Err(CipherError::CounterOverflow));
+        assert_eq!(cipher.bytes_processed(), u64::MAX - 3);
+        assert_eq!(cipher.try_process(b"123").unwrap().len(), 3);
+        assert_eq!(cipher.bytes_processed_checked(), Some(u64::MAX));
+        
+        cipher.process(b"1");
+        assert_eq!(cipher.bytes_processed(), u64::MAX);
+        assert_eq!(cipher.bytes_processed_checked(), None);
+        
+        cipher.reset();
+        assert_eq!(cipher.bytes_processed_checked(), Some(0));
+    }
+    
+    #[test]
+    fn test_try_process_respects_finite_sources() {
+        let mut cipher = StreamCipher::new(RecordedEntropy::new(vec![0xAA; 8]));
+        assert_eq!(cipher.try_process(b"12345").unwrap(), cipher_xor(b"12345", 0xAA));
+        assert_eq!(
+            cipher.try_process(b"6789"),
+            Err(CipherError::KeystreamExhausted { name: "Recorded".to_string(), requested: 4, available: 3 })
+        );
+        assert_eq!(cipher.bytes_processed(), 5);
+        assert_eq!(cipher.try_process(b"678").unwrap().len(), 3);
+    }
+    
+    fn cipher_xor(data: &[u8], key: u8) -> Vec<u8> {
+        data.iter().map(|b| b ^ key).collect()
+    }
+    
+    #[test]
+    fn test_reset_replays_deterministic_keystream() {
+        let mut cipher = StreamCipher::new(MockEntropy::new(42));
+        assert!(cipher.is_replayable());
+        
+        let plaintext = b"Round trip";
+        let ciphertext = cipher.process(plaintext);
+        cipher.reset();
+        assert_eq!(cipher.process(&ciphertext), plaintext);
+        
+        let cipher = StreamCipher::new(crate::entropy::SystemEntropy::new());
+        assert!(!cipher.is_replayable());
+    }
+    
+    #[test]
+    fn test_decrypt_inverts_encrypt() {
+        let plaintext = b"The quick brown fox";
+        let mut cipher = StreamCipher::new(ChaChaEntropy::new([3; 32]));
+        
+        // Earlier use of the cipher does not shift the keystream
+        cipher.process(b"warm-up");
+        let ciphertext = cipher.encrypt(plaintext).unwrap();
+        assert_ne!(ciphertext, plaintext);
+        assert_eq!(cipher.decrypt(&ciphertext).unwrap(), plaintext);
+        
+        let mut other = StreamCipher::new(ChaChaEntropy::new([3; 32]));
+        assert_eq!(other.decrypt(&ciphertext).unwrap(), plaintext);
+        let mut wrong_key = StreamCipher::new(ChaChaEntropy::new([4; 32]));
+        assert_ne!(wrong_key.decrypt(&ciphertext).unwrap(), plaintext);
+        
+        let mut random = StreamCipher::new(crate::entropy::SystemEntropy::new());
+        assert!(matches!(random.encrypt(plaintext), Err(CipherError::NotReplayable(_))));
+    }
+    
+    #[test]
+    fn test_new_secure() {
+        let err = StreamCipher::new_secure(MockEntropy::new(1)).err();
+        assert_eq!(err, Some(CipherError::InsecureSource("Mock RNG (for testing only)".to_string())));
+        
+        assert!(StreamCipher::new_secure(crate::entropy::SystemEntropy::new()).is_ok());
+    }
+    
     #[test]
     fn test_avalanche_effect() {
         let entropy = MockEntropy::new(42);
@@ -204,6 +514,6 @@ mod tests {
         
         // Should have some avalanche effect (though it won't be 50% with
         // this simple implementation and random keystream changes)
-        assert!(avalanche >= 0.0 && avalanche <= 100.0);
+        assert!((0.0..=100.0).contains(&avalanche));
     }
 }
@@ -0,0 +1,151 @@
+//! Diffusion (avalanche) measurement for ciphers
+
+use crate::entropy::{EntropySource, SystemEntropy};
+use super::StreamCipher;
+
+/// A cipher that can encrypt a whole message
+pub trait Cipher {
+    /// Encrypt `plaintext`, returning the ciphertext
+    fn encrypt(&mut self, plaintext: &[u8]) -> Vec<u8>;
+}
+
+impl<E: EntropySource> Cipher for StreamCipher<E> {
+    fn encrypt(&mut self, plaintext: &[u8]) -> Vec<u8> {
+        self.process(plaintext)
+    }
+}
+
+/// Measure a cipher's avalanche effect
+///
+/// For each trial, flips one randomly chosen bit of `plaintext`, encrypts
+/// both versions with fresh ciphers from `cipher_factory` (so both use the
+/// same key and nonce), and records the percentage of ciphertext bits that
+/// changed. Returns the average over all trials.
+///
+/// A cipher with full diffusion converges to ~50%. Stream ciphers,
+/// including ChaCha20, report ~0%: each plaintext bit only affects the
+/// matching ciphertext bit, which is expected and not a weakness.
+///
+/// Unlike [`StreamCipher::avalanche_effect`], which encrypts the two inputs
+/// with different keystream, this compares like with like.
+///
+/// Returns 0.0 for empty plaintext or zero trials.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::crypto::{diffusion, StreamCipher};
+/// use entropy_forge::entropy::ChaChaEntropy;
+///
+/// let percent = diffusion::avalanche(
+///     || StreamCipher::new(ChaChaEntropy::new([7; 32])),
+///     b"attack at dawn",
+///     100,
+/// );
+///
+/// // Stream ciphers don't diffuse: one flipped bit in, one flipped bit out
+/// assert!(percent < 1.0);
+/// ```
+pub fn avalanche<C, F>(cipher_factory: F, plaintext: &[u8], trials: usize) -> f64
+where
+    C: Cipher,
+    F: Fn() -> C,
+{
+    if plaintext.is_empty() || trials == 0 {
+        return 0.0;
+    }
+
+    let total_bits = plaintext.len() * 8;
+    let baseline = cipher_factory().encrypt(plaintext);
+    let mut rng = SystemEntropy::new();
+    let mut total_percent = 0.0;
+
+    for _ in 0..trials {
+        let bit = (rng.next_u64() % total_bits as u64) as usize;
+        let mut flipped = plaintext.to_vec();
+        flipped[bit / 8] ^= 1 << (bit % 8);
+
+        let output = cipher_factory().encrypt(&flipped);
+        let changed: u32 = baseline.iter()
+            .zip(output.iter())
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum();
+
+        total_percent += changed as f64 / total_bits as f64 * 100.0;
+    }
+
+    total_percent / trials as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::hmac_sha256;
+    use crate::entropy::{ChaChaEntropy, MockEntropy};
+
+    /// Wide-block Feistel cipher with an HMAC-SHA256 round function
+    ///
+    /// Every output bit depends on every input bit after a few rounds,
+    /// which is what the avalanche metric should detect.
+    struct FeistelCipher {
+        key: [u8; 32],
+    }
+
+    impl FeistelCipher {
+        fn round_function(&self, round: u8, input: &[u8], len: usize) -> Vec<u8> {
+            let mut out = Vec::with_capacity(len);
+            let mut counter = 0u32;
+            while out.len() < len {
+                let mut message = vec![round];
+                message.extend_from_slice(&counter.to_be_bytes());
+                message.extend_from_slice(input);
+                out.extend_from_slice(&hmac_sha256(&self.key, &message));
+                counter += 1;
+            }
+            out.truncate(len);
+            out
+        }
+    }
+
+    impl Cipher for FeistelCipher {
+        fn encrypt(&mut self, plaintext: &[u8]) -> Vec<u8> {
+            let mid = plaintext.len() / 2;
+            let (mut left, mut right) = (plaintext[..mid].to_vec(), plaintext[mid..].to_vec());
+
+            for round in 0..4 {
+                let mask = self.round_function(round, &right, left.len());
+                for (l, m) in left.iter_mut().zip(mask) {
+                    *l ^= m;
+                }
+                std::mem::swap(&mut left, &mut right);
+            }
+
+            // Undo the last swap so the halves keep their original sizes
+            std::mem::swap(&mut left, &mut right);
+            left.extend_from_slice(&right);
+            left
+        }
+    }
+
+    #[test]
+    fn test_avalanche_xor_vs_diffusing_cipher() {
+        let plaintext = b"The quick brown fox jumps over the lazy dog";
+
+        // Keystream is independent of the plaintext: exactly 1 bit changes
+        let xor = avalanche(|| StreamCipher::new(MockEntropy::new(1)), plaintext, 200);
+        let one_bit = 100.0 / (plaintext.len() * 8) as f64;
+        assert!((xor - one_bit).abs() < 1e-9);
+
+        let chacha = avalanche(|| StreamCipher::new(ChaChaEntropy::new([9; 32])), plaintext, 200);
+        assert!((chacha - one_bit).abs() < 1e-9);
+
+        let feistel = avalanche(|| FeistelCipher { key: [3; 32] }, plaintext, 200);
+        assert!((45.0..55.0).contains(&feistel), "feistel avalanche {feistel}");
+    }
+
+    #[test]
+    fn test_avalanche_degenerate_inputs() {
+        assert_eq!(avalanche(|| StreamCipher::new(MockEntropy::new(1)), b"", 10), 0.0);
+        assert_eq!(avalanche(|| StreamCipher::new(MockEntropy::new(1)), b"abc", 0), 0.0);
+    }
+}
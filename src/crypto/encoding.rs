@@ -0,0 +1,319 @@
+//! Text encodings for cipher output and keystream bytes
+
+use base64::alphabet;
+use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig, STANDARD, URL_SAFE_NO_PAD};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+use thiserror::Error;
+
+// Decoders take input with or without padding but otherwise stay strict:
+// non-zero trailing bits and misplaced `=` are rejected
+const BASE64_DECODE: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+const BASE64_URL_DECODE: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Output format for displaying or exporting bytes
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::crypto::{encode, decode, OutputFormat};
+///
+/// let text = encode(b"Hi", OutputFormat::Base64);
+/// assert_eq!(text, "SGk=");
+/// assert_eq!(decode(&text, OutputFormat::Base64).unwrap(), b"Hi");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Lowercase hexadecimal, two characters per byte
+    #[default]
+    Hex,
+    /// Standard Base64 (RFC 4648 §4) with `=` padding
+    Base64,
+    /// URL-safe Base64 (RFC 4648 §5) without padding
+    Base64Url,
+    /// Space-separated decimal byte values
+    Decimal,
+    /// Space-separated 8-bit binary groups
+    Binary,
+    /// Raw UTF-8 (invalid sequences are replaced, so this is lossy)
+    Text,
+}
+
+impl OutputFormat {
+    /// All formats, in display order
+    pub const ALL: [OutputFormat; 6] = [
+        OutputFormat::Hex,
+        OutputFormat::Base64,
+        OutputFormat::Base64Url,
+        OutputFormat::Decimal,
+        OutputFormat::Binary,
+        OutputFormat::Text,
+    ];
+
+    /// Short human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutputFormat::Hex => "Hex",
+            OutputFormat::Base64 => "Base64",
+            OutputFormat::Base64Url => "Base64 (URL-safe)",
+            OutputFormat::Decimal => "Decimal",
+            OutputFormat::Binary => "Binary",
+            OutputFormat::Text => "UTF-8 text",
+        }
+    }
+}
+
+/// Error returned when text cannot be decoded in the requested format
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DecodeError {
+    /// A character that is not valid in this format
+    #[error("invalid character {0:?}")]
+    InvalidCharacter(char),
+
+    /// Input length is not valid for this format
+    #[error("invalid input length {0}")]
+    InvalidLength(usize),
+
+    /// A decimal or binary group that does not fit in a byte
+    #[error("invalid byte value {0:?}")]
+    InvalidValue(String),
+}
+
+/// Encode bytes as text in the given format
+pub fn encode(bytes: &[u8], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Hex => hex::encode(bytes),
+        OutputFormat::Base64 => STANDARD.encode(bytes),
+        OutputFormat::Base64Url => URL_SAFE_NO_PAD.encode(bytes),
+        OutputFormat::Decimal => bytes.iter()
+            .map(|b| b.to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+        OutputFormat::Binary => bytes.iter()
+            .map(|b| format!("{:08b}", b))
+            .collect::<Vec<_>>()
+            .join(" "),
+        OutputFormat::Text => String::from_utf8_lossy(bytes).to_string(),
+    }
+}
+
+/// Decode text produced by [`encode`] back into bytes
+///
+/// Surrounding whitespace is ignored. Hex and Base64 input may also contain
+/// whitespace anywhere (e.g. line-wrapped dumps pasted from other tools).
+/// Base64 input is accepted with or without padding, but must otherwise be
+/// canonical: bits left over after the last byte must be zero. `Text`
+/// returns the UTF-8 bytes of the input, so it only round-trips for data
+/// that was valid UTF-8.
+pub fn decode(text: &str, format: OutputFormat) -> Result<Vec<u8>, DecodeError> {
+    let text = text.trim();
+
+    match format {
+        OutputFormat::Hex => {
+            let compact = strip_whitespace(text);
+            hex::decode(&compact).map_err(|e| match e {
+                hex::FromHexError::InvalidHexCharacter { c, .. } => DecodeError::InvalidCharacter(c),
+                _ => DecodeError::InvalidLength(compact.len()),
+            })
+        }
+        OutputFormat::Base64 => base64_decode(&strip_whitespace(text), &BASE64_DECODE),
+        OutputFormat::Base64Url => base64_decode(&strip_whitespace(text), &BASE64_URL_DECODE),
+        OutputFormat::Decimal => text.split_whitespace()
+            .map(|group| group.parse::<u8>().map_err(|_| DecodeError::InvalidValue(group.to_string())))
+            .collect(),
+        OutputFormat::Binary => text.split_whitespace()
+            .map(|group| {
+                if group.len() != 8 {
+                    return Err(DecodeError::InvalidValue(group.to_string()));
+                }
+                u8::from_str_radix(group, 2).map_err(|_| DecodeError::InvalidValue(group.to_string()))
+            })
+            .collect(),
+        OutputFormat::Text => Ok(text.as_bytes().to_vec()),
+    }
+}
+
+/// Render bytes as ASCII text with `\x00`-style escapes
+///
+/// Printable ASCII is kept as is, `\\`, `\n`, `\r` and `\t` use their
+/// usual escapes, and every other byte (including each byte of a multi-byte
+/// UTF-8 character) becomes `\xHH`. Unlike [`OutputFormat::Text`] this is
+/// lossless: [`unescape_bytes`] recovers the exact input.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::crypto::{escape_bytes, unescape_bytes};
+///
+/// let text = escape_bytes(b"Hi\n\x00\xFF");
+/// assert_eq!(text, "Hi\\n\\x00\\xff");
+/// assert_eq!(unescape_bytes(&text).unwrap(), b"Hi\n\x00\xFF");
+/// ```
+pub fn escape_bytes(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    for &b in bytes {
+        match b {
+            b'\\' => out.push_str("\\\\"),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            0x20..=0x7E => out.push(b as char),
+            _ => out.push_str(&format!("\\x{:02x}", b)),
+        }
+    }
+    out
+}
+
+/// Parse text produced by [`escape_bytes`] back into bytes
+///
+/// `\x` must be followed by exactly two hex digits, upper or lower case.
+/// Any other character is taken as its UTF-8 bytes.
+pub fn unescape_bytes(text: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut out = Vec::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0u8; 4];
+            out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push(b'\\'),
+            Some('n') => out.push(b'\n'),
+            Some('r') => out.push(b'\r'),
+            Some('t') => out.push(b'\t'),
+            Some('x') => {
+                let digits: String = chars.by_ref().take(2).collect();
+                // from_str_radix alone would also take a sign, as in `\x+1`
+                let value = (digits.len() == 2 && digits.chars().all(|d| d.is_ascii_hexdigit()))
+                    .then(|| u8::from_str_radix(&digits, 16).ok())
+                    .flatten()
+                    .ok_or_else(|| DecodeError::InvalidValue(format!("\\x{}", digits)))?;
+                out.push(value);
+            }
+            Some(other) => return Err(DecodeError::InvalidCharacter(other)),
+            None => return Err(DecodeError::InvalidLength(text.len())),
+        }
+    }
+
+    Ok(out)
+}
+
+fn strip_whitespace(text: &str) -> String {
+    text.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+fn base64_decode(text: &str, engine: &GeneralPurpose) -> Result<Vec<u8>, DecodeError> {
+    engine.decode(text).map_err(|e| match e {
+        base64::DecodeError::InvalidByte(_, byte) | base64::DecodeError::InvalidLastSymbol(_, byte) => {
+            DecodeError::InvalidCharacter(byte as char)
+        }
+        base64::DecodeError::InvalidLength(_) => DecodeError::InvalidLength(text.len()),
+        base64::DecodeError::InvalidPadding => DecodeError::InvalidCharacter('='),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::EntropySource;
+
+    #[test]
+    fn test_round_trip_all_formats() {
+        let data: Vec<u8> = (0..=255).collect();
+
+        for format in OutputFormat::ALL {
+            if format == OutputFormat::Text {
+                continue;
+            }
+            for len in 0..8 {
+                let bytes = &data[..len];
+                let encoded = encode(bytes, format);
+                assert_eq!(decode(&encoded, format).unwrap(), bytes, "{:?}", format);
+            }
+            let encoded = encode(&data, format);
+            assert_eq!(decode(&encoded, format).unwrap(), data, "{:?}", format);
+        }
+
+        let text = encode(b"Hello", OutputFormat::Text);
+        assert_eq!(decode(&text, OutputFormat::Text).unwrap(), b"Hello");
+    }
+
+    #[test]
+    fn test_escape_round_trip() {
+        let all: Vec<u8> = (0..=255).collect();
+        let escaped = escape_bytes(&all);
+        assert!(escaped.is_ascii());
+        assert_eq!(unescape_bytes(&escaped).unwrap(), all);
+
+        let mut random = vec![0u8; 4_096];
+        crate::entropy::MockEntropy::new(9).fill_bytes(&mut random);
+        assert_eq!(unescape_bytes(&escape_bytes(&random)).unwrap(), random);
+
+        assert_eq!(escape_bytes(b"a\\b\tc\x7f"), "a\\\\b\\tc\\x7f");
+        assert_eq!(unescape_bytes("\\xAB é").unwrap(), [0xAB, b' ', 0xC3, 0xA9]);
+
+        assert_eq!(unescape_bytes("\\q"), Err(DecodeError::InvalidCharacter('q')));
+        assert_eq!(unescape_bytes("ab\\"), Err(DecodeError::InvalidLength(3)));
+        assert_eq!(unescape_bytes("\\x4"), Err(DecodeError::InvalidValue("\\x4".to_string())));
+        assert_eq!(unescape_bytes("\\xg0"), Err(DecodeError::InvalidValue("\\xg0".to_string())));
+        assert_eq!(unescape_bytes("\\x+1"), Err(DecodeError::InvalidValue("\\x+1".to_string())));
+        assert_eq!(unescape_bytes("\\x-1"), Err(DecodeError::InvalidValue("\\x-1".to_string())));
+    }
+
+    #[test]
+    fn test_known_vectors() {
+        // RFC 4648 test vectors
+        assert_eq!(encode(b"", OutputFormat::Base64), "");
+        assert_eq!(encode(b"f", OutputFormat::Base64), "Zg==");
+        assert_eq!(encode(b"fo", OutputFormat::Base64), "Zm8=");
+        assert_eq!(encode(b"foo", OutputFormat::Base64), "Zm9v");
+        assert_eq!(encode(b"foobar", OutputFormat::Base64), "Zm9vYmFy");
+
+        assert_eq!(encode(&[0xFB, 0xFF], OutputFormat::Base64), "+/8=");
+        assert_eq!(encode(&[0xFB, 0xFF], OutputFormat::Base64Url), "-_8");
+        assert_eq!(encode(&[72, 105], OutputFormat::Decimal), "72 105");
+        assert_eq!(encode(&[72, 105], OutputFormat::Binary), "01001000 01101001");
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(decode("Zg!=", OutputFormat::Base64), Err(DecodeError::InvalidCharacter('!')));
+        assert_eq!(decode("Z", OutputFormat::Base64), Err(DecodeError::InvalidLength(1)));
+        assert!(decode("256", OutputFormat::Decimal).is_err());
+        assert!(decode("0101", OutputFormat::Binary).is_err());
+        assert!(decode("zz", OutputFormat::Hex).is_err());
+    }
+
+    #[test]
+    fn test_decode_pasted_dumps() {
+        // Line-wrapped hex, as produced by `xxd -p`
+        let hex = "  48656c6c6f\n2c20576f\r\n726c6421 \n";
+        assert_eq!(decode(hex, OutputFormat::Hex).unwrap(), b"Hello, World!");
+        assert_eq!(decode("48 65 6C 6C 6F", OutputFormat::Hex).unwrap(), b"Hello");
+
+        // MIME-style wrapped Base64
+        let b64 = "SGVsbG8s\nIFdvcmxk\nIQ==\n";
+        assert_eq!(decode(b64, OutputFormat::Base64).unwrap(), b"Hello, World!");
+
+        // Malformed input
+        assert_eq!(decode("48 6", OutputFormat::Hex), Err(DecodeError::InvalidLength(3)));
+        assert_eq!(decode("48\n6g", OutputFormat::Hex), Err(DecodeError::InvalidCharacter('g')));
+        assert_eq!(decode("SGVs\nbG8*", OutputFormat::Base64), Err(DecodeError::InvalidCharacter('*')));
+        assert_eq!(decode("SGVsb", OutputFormat::Base64), Err(DecodeError::InvalidLength(5)));
+        assert_eq!(decode("SGVs-_8", OutputFormat::Base64), Err(DecodeError::InvalidCharacter('-')));
+
+        // Only canonical encodings: "Zh" would smuggle non-zero trailing bits
+        assert_eq!(decode("Zg", OutputFormat::Base64).unwrap(), b"f");
+        assert_eq!(decode("Zh==", OutputFormat::Base64), Err(DecodeError::InvalidCharacter('h')));
+        assert_eq!(decode("Zh", OutputFormat::Base64Url), Err(DecodeError::InvalidCharacter('h')));
+        assert!(decode("Zg==Zg==", OutputFormat::Base64).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Key derivation (HMAC-SHA256, PBKDF2 and Argon2id)
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use thiserror::Error;
+
+/// Errors from [`argon2id`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum KdfError {
+    /// Rejected parameters, such as a salt under 8 bytes
+    #[error("Argon2id: {0}")]
+    Argon2(argon2::Error),
+}
+
+/// HMAC-SHA256 keyed once, so it can be reused cheaply for many messages
+/// under the same key.
+#[derive(Clone)]
+pub(crate) struct HmacSha256 {
+    keyed: Hmac<Sha256>,
+}
+
+impl HmacSha256 {
+    pub(crate) fn new(key: &[u8]) -> Self {
+        Self {
+            keyed: Hmac::new_from_slice(key).expect("HMAC accepts keys of any length"),
+        }
+    }
+
+    pub(crate) fn mac(&self, parts: &[&[u8]]) -> [u8; 32] {
+        let mut mac = self.keyed.clone();
+        for part in parts {
+            mac.update(part);
+        }
+        mac.finalize().into_bytes().into()
+    }
+}
+
+/// Compute HMAC-SHA256 (RFC 2104)
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::crypto::hmac_sha256;
+///
+/// let tag = hmac_sha256(b"key", b"message");
+/// assert_eq!(tag.len(), 32);
+/// ```
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    HmacSha256::new(key).mac(&[message])
+}
+
+/// Derive key material with PBKDF2-HMAC-SHA256 (RFC 8018)
+///
+/// Fills `output` with key material derived from `password` and `salt`
+/// using `iterations` rounds (minimum 1). Higher iteration counts make
+/// brute-forcing the password slower.
+pub fn pbkdf2_hmac_sha256(password: &[u8], salt: &[u8], iterations: u32, output: &mut [u8]) {
+    pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, iterations.max(1), output);
+}
+
+/// Derive key material with Argon2id (RFC 9106)
+///
+/// Uses `memory_kib` KiB of memory, `passes` passes over it and one lane.
+/// Memory-hard, so unlike PBKDF2 it resists GPU and ASIC guessing; prefer
+/// it for passwords. [`ARGON2_MEMORY_KIB`] and [`ARGON2_PASSES`] are the
+/// library defaults (19 MiB, 2 passes), as recommended by OWASP.
+///
+/// Returns [`KdfError::Argon2`] if `salt` is under 8 bytes, `output` is
+/// under 4 bytes, or the costs are below Argon2's minimums.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::crypto::argon2id;
+///
+/// let mut key = [0u8; 32];
+/// argon2id(b"hunter2", b"per-user salt", 64, 1, &mut key).unwrap();
+/// ```
+pub fn argon2id(password: &[u8], salt: &[u8], memory_kib: u32, passes: u32, output: &mut [u8]) -> Result<(), KdfError> {
+    let params = Params::new(memory_kib, passes, 1, None).map_err(KdfError::Argon2)?;
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password, salt, output)
+        .map_err(KdfError::Argon2)
+}
+
+/// Default Argon2id memory cost in KiB
+pub const ARGON2_MEMORY_KIB: u32 = Params::DEFAULT_M_COST;
+
+/// Default Argon2id pass count
+pub const ARGON2_PASSES: u32 = Params::DEFAULT_T_COST;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_sha256_rfc4231() {
+        // RFC 4231 test case 2
+        let tag = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            hex::encode(tag),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        // RFC 4231 test case 6 (key longer than block size)
+        let key = [0xAAu8; 131];
+        let tag = hmac_sha256(&key, b"Test Using Larger Than Block-Size Key - Hash Key First");
+        assert_eq!(
+            hex::encode(tag),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_pbkdf2_rfc7914() {
+        // RFC 7914 section 11
+        let mut output = [0u8; 64];
+        pbkdf2_hmac_sha256(b"passwd", b"salt", 1, &mut output);
+        assert_eq!(
+            hex::encode(output),
+            "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+             49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+        );
+    }
+
+    #[test]
+    fn test_argon2id() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        let mut c = [0u8; 32];
+        argon2id(b"password", b"somesalt", 64, 1, &mut a).unwrap();
+        argon2id(b"password", b"somesalt", 64, 1, &mut b).unwrap();
+        argon2id(b"password", b"othersalt", 64, 1, &mut c).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        // Reference implementation test vector (phc-winner-argon2 test.c,
+        // Argon2id v1.3, t = 2, m = 2^16, p = 1)
+        argon2id(b"password", b"somesalt", 1 << 16, 2, &mut a).unwrap();
+        assert_eq!(
+            hex::encode(a),
+            "09316115d5cf24ed5a15a31a3ba326e5cf32edc24702987c02b6566f61913cf7"
+        );
+
+        assert!(matches!(argon2id(b"password", b"short", 64, 1, &mut a), Err(KdfError::Argon2(_))));
+    }
+}
@@ -4,5 +4,17 @@
 //! entropy source system.
 
 mod cipher;
+mod encoding;
+mod kdf;
+mod nonce;
+#[cfg(feature = "png")]
+mod picture;
+pub mod diffusion;
 
-pub use cipher::StreamCipher;
+pub use cipher::{StreamCipher, CipherError};
+pub use encoding::{encode, decode, escape_bytes, unescape_bytes, OutputFormat, DecodeError};
+pub use kdf::{argon2id, hmac_sha256, pbkdf2_hmac_sha256, KdfError, ARGON2_MEMORY_KIB, ARGON2_PASSES};
+pub(crate) use kdf::HmacSha256;
+pub use nonce::{NonceManager, NonceError};
+#[cfg(feature = "png")]
+pub use picture::{keystream_image, keystream_image_scaled, PictureError};
@@ -0,0 +1,119 @@
+//! PNG export of keystream bytes
+
+use std::io::Cursor;
+use image::{GrayImage, ImageFormat, Luma};
+use thiserror::Error;
+
+/// Error returned when a keystream can't be drawn as a PNG
+#[derive(Debug, Error)]
+pub enum PictureError {
+    /// There are no bytes to draw
+    #[error("cannot render an empty keystream")]
+    EmptyKeystream,
+
+    /// The grid width or cell size is zero
+    #[error("width and cell size must be non-zero")]
+    ZeroSize,
+
+    /// The image would be larger than a PNG or memory can hold
+    #[error("a {width}-column grid of {cell_size}-pixel cells for {len} bytes is too large")]
+    TooLarge { len: usize, width: usize, cell_size: usize },
+
+    /// PNG encoding failed
+    #[error("PNG encoding failed: {0}")]
+    Encode(#[from] image::ImageError),
+}
+
+/// Render keystream bytes as a grayscale PNG grid
+///
+/// Each byte becomes one pixel whose brightness is the byte value, laid
+/// out left to right in rows of `width`. A short last row is padded with
+/// black. This is the same picture as the Use tab's keystream grid.
+///
+/// Requires the `png` feature (enabled by `gui`).
+///
+/// # Errors
+///
+/// Fails if `width` is 0, `keystream` is empty or the image is too large.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::crypto::keystream_image;
+///
+/// let png = keystream_image(&[0x00, 0x80, 0xFF, 0x40], 2).unwrap();
+/// assert_eq!(&png[1..4], b"PNG");
+/// ```
+pub fn keystream_image(keystream: &[u8], width: usize) -> Result<Vec<u8>, PictureError> {
+    keystream_image_scaled(keystream, width, 1)
+}
+
+/// Like [`keystream_image`], drawing each byte as a `cell_size` square
+///
+/// Use this for report-sized images; a 64-byte keystream at one pixel
+/// per byte is only 8×8.
+///
+/// # Errors
+///
+/// Fails if `width` or `cell_size` is 0, `keystream` is empty or the
+/// image is too large.
+pub fn keystream_image_scaled(keystream: &[u8], width: usize, cell_size: usize) -> Result<Vec<u8>, PictureError> {
+    if width == 0 || cell_size == 0 {
+        return Err(PictureError::ZeroSize);
+    }
+    if keystream.is_empty() {
+        return Err(PictureError::EmptyKeystream);
+    }
+
+    let too_large = || PictureError::TooLarge { len: keystream.len(), width, cell_size };
+    let rows = keystream.len().div_ceil(width);
+    let pixels_wide = width.checked_mul(cell_size).and_then(|w| u32::try_from(w).ok()).ok_or_else(too_large)?;
+    let pixels_high = rows.checked_mul(cell_size).and_then(|h| u32::try_from(h).ok()).ok_or_else(too_large)?;
+    // The pixel buffer itself must fit in memory's address space
+    (pixels_wide as usize).checked_mul(pixels_high as usize).ok_or_else(too_large)?;
+
+    let image = GrayImage::from_fn(pixels_wide, pixels_high, |x, y| {
+        let index = (y as usize / cell_size) * width + x as usize / cell_size;
+        Luma([keystream.get(index).copied().unwrap_or(0)])
+    });
+
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok(png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png_dimensions_and_pixels() {
+        let keystream: Vec<u8> = (0..=20).collect();
+        let png = keystream_image(&keystream, 8).unwrap();
+
+        let decoded = image::load_from_memory_with_format(&png, ImageFormat::Png)
+            .unwrap()
+            .into_luma8();
+        assert_eq!(decoded.dimensions(), (8, 3));
+        assert_eq!(decoded.get_pixel(3, 1), &Luma([11]));
+        assert_eq!(decoded.get_pixel(7, 2), &Luma([0]));
+
+        let scaled = image::load_from_memory(&keystream_image_scaled(&keystream, 8, 30).unwrap())
+            .unwrap()
+            .into_luma8();
+        assert_eq!(scaled.dimensions(), (240, 90));
+        assert_eq!(scaled.get_pixel(3 * 30 + 29, 30), &Luma([11]));
+    }
+
+    #[test]
+    fn test_bad_sizes_are_errors() {
+        assert!(matches!(keystream_image(&[], 8), Err(PictureError::EmptyKeystream)));
+        assert!(matches!(keystream_image(&[1, 2], 0), Err(PictureError::ZeroSize)));
+        assert!(matches!(keystream_image_scaled(&[1, 2], 2, 0), Err(PictureError::ZeroSize)));
+
+        // 2^32 pixels wide doesn't fit a u32 dimension, rather than wrapping
+        let wide = keystream_image_scaled(&[1, 2], 2, 1 << 31);
+        assert!(matches!(wide, Err(PictureError::TooLarge { len: 2, width: 2, .. })));
+        assert!(matches!(keystream_image_scaled(&[1], 1, usize::MAX), Err(PictureError::TooLarge { .. })));
+    }
+}
@@ -0,0 +1,143 @@
+//! Unbiased sampling of symbols from an alphabet
+
+use thiserror::Error;
+use super::EntropySource;
+
+/// Printable ASCII characters `!` through `~` (no space)
+pub const PRINTABLE_ASCII: &[u8] = b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+
+/// Consecutive out-of-range bytes after which [`fill_from_alphabet`] gives up
+///
+/// At most 127 of 256 byte values are rejected, so a working source hits
+/// this with probability below 2^-1000.
+pub const MAX_CONSECUTIVE_REJECTIONS: usize = 1_024;
+
+/// Errors from [`fill_from_alphabet`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AlphabetError {
+    /// The source kept producing bytes that map to no symbol unbiasedly
+    #[error("source produced {rejected} bytes in a row outside the unbiased range 0..{limit}")]
+    TooManyRejections {
+        /// Consecutive bytes rejected
+        rejected: usize,
+        /// Exclusive upper bound on accepted bytes
+        limit: usize,
+    },
+}
+
+/// Fill `dest` with symbols drawn uniformly from `alphabet`
+///
+/// Uses rejection sampling: random bytes at or above the largest multiple
+/// of the alphabet size are discarded, so every symbol is equally likely.
+/// A plain `byte % len` would favour the first `256 % len` symbols.
+///
+/// # Errors
+///
+/// Returns [`AlphabetError::TooManyRejections`] after
+/// [`MAX_CONSECUTIVE_REJECTIONS`] discarded bytes in a row, which only a
+/// broken or heavily biased source produces (say, one stuck at `0xFF`).
+/// `dest` is then partly filled.
+///
+/// # Panics
+///
+/// Panics if `alphabet` is empty or longer than 256 symbols.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{fill_from_alphabet, SystemEntropy};
+///
+/// let mut digits = [0u8; 6];
+/// fill_from_alphabet(&mut SystemEntropy::new(), b"0123456789", &mut digits).unwrap();
+///
+/// assert!(digits.iter().all(u8::is_ascii_digit));
+/// ```
+pub fn fill_from_alphabet<E: ?Sized + EntropySource>(
+    source: &mut E,
+    alphabet: &[u8],
+    dest: &mut [u8],
+) -> Result<(), AlphabetError> {
+    assert!(
+        !alphabet.is_empty() && alphabet.len() <= 256,
+        "alphabet must have 1 to 256 symbols, got {}",
+        alphabet.len()
+    );
+
+    // Largest multiple of the alphabet size that fits in a byte
+    let limit = 256 - 256 % alphabet.len();
+    let mut buf = [0u8; 64];
+    let mut filled = 0;
+    let mut rejected = 0;
+
+    while filled < dest.len() {
+        source.fill_bytes(&mut buf);
+        for &byte in &buf {
+            if (byte as usize) < limit {
+                dest[filled] = alphabet[byte as usize % alphabet.len()];
+                filled += 1;
+                rejected = 0;
+                if filled == dest.len() {
+                    break;
+                }
+            } else {
+                rejected += 1;
+                if rejected == MAX_CONSECUTIVE_REJECTIONS {
+                    return Err(AlphabetError::TooManyRejections { rejected, limit });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{BiasedEntropy, ChaChaEntropy, SystemEntropy};
+
+    #[test]
+    fn test_symbols_from_alphabet() {
+        let mut out = [0u8; 1_000];
+        fill_from_alphabet(&mut SystemEntropy::new(), PRINTABLE_ASCII, &mut out).unwrap();
+        assert!(out.iter().all(|b| PRINTABLE_ASCII.contains(b)));
+
+        assert_eq!(PRINTABLE_ASCII.len(), 94);
+        assert!(PRINTABLE_ASCII.iter().all(|b| b.is_ascii_graphic()));
+    }
+
+    #[test]
+    fn test_unbiased() {
+        // 256 % 3 == 1, so `byte % 3` would pick 'a' ~0.8% too often
+        // (+7,800 here); sampling noise is ~800 (1 sigma)
+        let mut out = vec![0u8; 3_000_000];
+        fill_from_alphabet(&mut ChaChaEntropy::new([3; 32]), b"abc", &mut out).unwrap();
+
+        for symbol in b"abc" {
+            let count = out.iter().filter(|&b| b == symbol).count();
+            assert!((count as i64 - 1_000_000).abs() < 4_000, "{} appeared {} times", *symbol as char, count);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_alphabet_panics() {
+        let _ = fill_from_alphabet(&mut SystemEntropy::new(), b"", &mut [0u8; 4]);
+    }
+
+    #[test]
+    fn test_stuck_source_errors() {
+        // Always 0xFF, above the limit of 188 for 94 symbols, so every byte
+        // is rejected; this used to loop forever
+        let mut stuck = BiasedEntropy::new(7, 1.0);
+        let mut out = [0u8; 8];
+        assert_eq!(
+            fill_from_alphabet(&mut stuck, PRINTABLE_ASCII, &mut out),
+            Err(AlphabetError::TooManyRejections { rejected: MAX_CONSECUTIVE_REJECTIONS, limit: 188 })
+        );
+
+        // A 256-symbol alphabet rejects nothing
+        let all: Vec<u8> = (0..=255).collect();
+        assert_eq!(fill_from_alphabet(&mut stuck, &all, &mut out), Ok(()));
+        assert_eq!(out, [0xFF; 8]);
+    }
+}
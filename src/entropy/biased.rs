@@ -0,0 +1,117 @@
+//! Entropy source with a tunable bit bias
+
+use super::{ChaChaEntropy, EntropySource, SeedableEntropy};
+
+/// Deterministic source whose bits are 1 with a chosen probability
+///
+/// Each output bit compares a fresh 32-bit ChaCha20 draw against a
+/// threshold, so bits are independent and 1 with probability `p_one`. At
+/// 0.5 it behaves like a good PRNG; at 0.9 it fails the frequency test
+/// badly. Meant for teaching and for testing the test suite. DO NOT use in
+/// production!
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{BiasedEntropy, EntropySource};
+/// use entropy_forge::quality::NistTests;
+///
+/// let mut source = BiasedEntropy::new(1, 0.9);
+/// let mut data = vec![0u8; 1_000];
+/// source.fill_bytes(&mut data);
+///
+/// assert!(NistTests::frequency_test(&data).unwrap() < 0.01);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BiasedEntropy {
+    rng: ChaChaEntropy,
+    p_one: f64,
+    threshold: u64,
+    name: String,
+}
+
+impl BiasedEntropy {
+    /// Create a source from a seed and the probability of a 1 bit
+    ///
+    /// `p_one` is clamped to `[0.0, 1.0]`; NaN is treated as 0.5.
+    pub fn new(seed: u64, p_one: f64) -> Self {
+        let p_one = if p_one.is_nan() { 0.5 } else { p_one.clamp(0.0, 1.0) };
+
+        Self {
+            rng: ChaChaEntropy::from_seed_u64(seed),
+            p_one,
+            // A 32-bit draw below the threshold yields a 1
+            threshold: (p_one * (1u64 << 32) as f64) as u64,
+            name: format!("Biased RNG (p(1) = {:.2})", p_one),
+        }
+    }
+
+    /// Probability that an output bit is 1
+    pub fn p_one(&self) -> f64 {
+        self.p_one
+    }
+}
+
+impl EntropySource for BiasedEntropy {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for byte in dest.iter_mut() {
+            let mut value = 0u8;
+            for _ in 0..8 {
+                let bit = u64::from(self.rng.next_u32()) < self.threshold;
+                value = (value << 1) | bit as u8;
+            }
+            *byte = value;
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn reset(&mut self) {
+        self.rng.reset();
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        // Binary entropy of one bit, times 8
+        let p = self.p_one;
+        let h = |x: f64| if x > 0.0 { -x * x.log2() } else { 0.0 };
+        Some(8.0 * (h(p) + h(1.0 - p)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::assert_replays;
+
+    fn ones_fraction(source: &mut BiasedEntropy, bytes: usize) -> f64 {
+        let mut data = vec![0u8; bytes];
+        source.fill_bytes(&mut data);
+        let ones: u32 = data.iter().map(|b| b.count_ones()).sum();
+        ones as f64 / (bytes * 8) as f64
+    }
+
+    #[test]
+    fn test_ones_fraction_matches_bias() {
+        for p in [0.1, 0.5, 0.75, 0.9] {
+            let measured = ones_fraction(&mut BiasedEntropy::new(7, p), 100_000);
+            assert!((measured - p).abs() < 0.005, "p = {}, measured {}", p, measured);
+        }
+
+        assert_eq!(ones_fraction(&mut BiasedEntropy::new(7, 0.0), 1_000), 0.0);
+        assert_eq!(ones_fraction(&mut BiasedEntropy::new(7, 1.0), 1_000), 1.0);
+    }
+
+    #[test]
+    fn test_biased_replays() {
+        assert_replays(&mut BiasedEntropy::new(3, 0.7));
+
+        assert_eq!(BiasedEntropy::new(3, 0.5).estimated_entropy_per_byte(), Some(8.0));
+        assert_eq!(BiasedEntropy::new(3, 1.0).estimated_entropy_per_byte(), Some(0.0));
+    }
+}
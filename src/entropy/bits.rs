@@ -0,0 +1,131 @@
+//! Byte-to-bit expansion shared by the tests and visualizers
+
+/// Order in which bits are extracted from each byte
+///
+/// SP 800-22 treats the sequence as written left to right, so the default is
+/// most-significant bit first. This also matches the Learn tab visualizers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitOrder {
+    /// Bit 7 first, bit 0 last
+    #[default]
+    MsbFirst,
+    /// Bit 0 first, bit 7 last
+    LsbFirst,
+}
+
+impl BitOrder {
+    /// Iterate over the bits of `data` in this order
+    pub fn iter(self, data: &[u8]) -> BitIter<'_> {
+        BitIter::new(data, self)
+    }
+
+    /// Expand bytes into one bit (0 or 1) per element in this order
+    pub fn extract(self, data: &[u8]) -> Vec<u8> {
+        self.iter(data).collect()
+    }
+}
+
+/// Iterator over the bits of a byte slice, yielding 0 or 1
+///
+/// Streams bits without allocating, so large samples can be scanned bit by
+/// bit without first expanding them eightfold.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::bits::{BitIter, BitOrder};
+///
+/// let bits: Vec<u8> = BitIter::new(&[0b1010_0000], BitOrder::MsbFirst).collect();
+/// assert_eq!(bits, [1, 0, 1, 0, 0, 0, 0, 0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BitIter<'a> {
+    data: &'a [u8],
+    order: BitOrder,
+    front: usize,
+    back: usize,
+}
+
+impl<'a> BitIter<'a> {
+    /// Iterate over the bits of `data` in `order`
+    pub fn new(data: &'a [u8], order: BitOrder) -> Self {
+        Self {
+            data,
+            order,
+            front: 0,
+            back: data.len() * 8,
+        }
+    }
+
+    fn bit(&self, index: usize) -> u8 {
+        let byte = self.data[index / 8];
+        let shift = match self.order {
+            BitOrder::MsbFirst => 7 - index % 8,
+            BitOrder::LsbFirst => index % 8,
+        };
+        (byte >> shift) & 1
+    }
+}
+
+impl Iterator for BitIter<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let bit = self.bit(self.front);
+        self.front += 1;
+        Some(bit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for BitIter<'_> {
+    fn next_back(&mut self) -> Option<u8> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.bit(self.back))
+    }
+}
+
+impl ExactSizeIterator for BitIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_orders() {
+        let msb: Vec<u8> = BitIter::new(&[0b1010_0000], BitOrder::MsbFirst).collect();
+        assert_eq!(msb, [1, 0, 1, 0, 0, 0, 0, 0]);
+
+        let lsb: Vec<u8> = BitIter::new(&[0b1010_0000], BitOrder::LsbFirst).collect();
+        assert_eq!(lsb, [0, 0, 0, 0, 0, 1, 0, 1]);
+
+        let reversed: Vec<u8> = BitIter::new(&[0b1010_0000], BitOrder::MsbFirst).rev().collect();
+        assert_eq!(reversed, lsb);
+    }
+
+    #[test]
+    fn test_bit_iter_len() {
+        let data = [0xFF, 0x00, 0x0F];
+        let mut bits = BitOrder::MsbFirst.iter(&data);
+        assert_eq!(bits.len(), 24);
+
+        bits.next();
+        bits.next_back();
+        assert_eq!(bits.len(), 22);
+        assert_eq!(bits.map(u32::from).sum::<u32>(), 7 + 3);
+
+        assert_eq!(BitIter::new(&[], BitOrder::LsbFirst).next(), None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Seekable BLAKE3 XOF entropy source
+
+use super::{EntropySource, SeedableEntropy, SeekableEntropy};
+use ::blake3::{Hasher, OutputReader};
+
+/// Deterministic, seekable stream from BLAKE3's extendable output
+///
+/// The stream is the keyed BLAKE3 XOF of an empty message, so a 32-byte
+/// key fully determines it. Unlike ChaCha20 through `rand_chacha`, the
+/// reader can jump to any byte offset in constant time with
+/// [`seek`](Self::seek). Use [`from_source`](Self::from_source) to
+/// condition another source: its bytes become the key.
+///
+/// [`is_cryptographically_secure`](EntropySource::is_cryptographically_secure)
+/// trusts a key passed to [`new`](Self::new), but reports `false` for
+/// sources built by [`from_seed_u64`](SeedableEntropy::from_seed_u64),
+/// whose 64-bit seeds can be brute-forced, and by
+/// [`from_source`](Self::from_source) with an insecure source.
+///
+/// Requires the `blake3` feature.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{Blake3Entropy, EntropySource};
+///
+/// let mut source = Blake3Entropy::new([7u8; 32]);
+/// let mut stream = [0u8; 64];
+/// source.fill_bytes(&mut stream);
+///
+/// // Jump back and re-read the second half
+/// source.seek(32);
+/// let mut tail = [0u8; 32];
+/// source.fill_bytes(&mut tail);
+/// assert_eq!(tail, stream[32..]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Blake3Entropy {
+    reader: OutputReader,
+    // Keyed from a 64-bit seed or an insecure source, so guessable
+    weak_seed: bool,
+}
+
+impl Blake3Entropy {
+    /// Create a source from a 32-byte key
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            reader: Hasher::new_keyed(&key).finalize_xof(),
+            weak_seed: false,
+        }
+    }
+
+    /// Create a source keyed with 32 bytes drawn from `source`
+    ///
+    /// The result is cryptographically secure only if `source` is.
+    pub fn from_source<E: ?Sized + EntropySource>(source: &mut E) -> Self {
+        let mut key = [0u8; 32];
+        source.fill_bytes(&mut key);
+        Self {
+            weak_seed: !source.is_cryptographically_secure(),
+            ..Self::new(key)
+        }
+    }
+
+    /// Move to byte `offset` of the output stream
+    pub fn seek(&mut self, offset: u64) {
+        self.reader.set_position(offset);
+    }
+
+    /// Current byte offset in the output stream
+    pub fn position(&self) -> u64 {
+        self.reader.position()
+    }
+}
+
+impl SeedableEntropy for Blake3Entropy {
+    fn from_seed_u64(seed: u64) -> Self {
+        let mut full = [0u8; 32];
+        full[..8].copy_from_slice(&seed.to_le_bytes());
+        Self {
+            weak_seed: true,
+            ..Self::new(full)
+        }
+    }
+
+    fn from_seed_bytes(seed: &[u8]) -> Self {
+        Self::new(::blake3::hash(seed).into())
+    }
+}
+
+impl SeekableEntropy for Blake3Entropy {
+    fn seek(&mut self, offset: u64) {
+        Blake3Entropy::seek(self, offset)
+    }
+
+    fn position(&self) -> u64 {
+        Blake3Entropy::position(self)
+    }
+}
+
+impl EntropySource for Blake3Entropy {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.reader.fill(dest);
+    }
+
+    fn name(&self) -> &str {
+        "BLAKE3-XOF"
+    }
+
+    fn reset(&mut self) {
+        self.seek(0);
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        Some(8.0)
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        !self.weak_seed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{assert_replays, MockEntropy, SystemEntropy};
+
+    #[test]
+    fn test_blake3_known_answer() {
+        // Keyed hash of the empty input from the official BLAKE3 test vectors
+        let mut source = Blake3Entropy::new(*b"whats the Elvish word for friend");
+        let mut buf = [0u8; 64];
+        source.fill_bytes(&mut buf);
+        assert_eq!(
+            hex::encode(buf),
+            "92b2b75604ed3c761f9d6f62392c8a9227ad0ea3f09573e783f1498a4ed60d26\
+             b18171a2f22a4b94822c701f107153dba24918c4bae4d2945c20ece13387627d"
+        );
+    }
+
+    #[test]
+    fn test_seek_matches_sequential() {
+        let mut source = Blake3Entropy::from_seed_u64(9);
+        let mut sequential = vec![0u8; 5_000];
+        source.fill_bytes(&mut sequential);
+        assert_eq!(source.position(), 5_000);
+
+        for offset in [0, 1, 63, 64, 1_023, 1_024, 4_000] {
+            source.seek(offset as u64);
+            let mut chunk = [0u8; 100];
+            source.fill_bytes(&mut chunk);
+            assert_eq!(chunk, sequential[offset..offset + 100]);
+        }
+
+        assert_replays(&mut source);
+    }
+
+    #[test]
+    fn test_weak_seeds_are_insecure() {
+        assert!(Blake3Entropy::new([1; 32]).is_cryptographically_secure());
+        assert!(Blake3Entropy::from_source(&mut SystemEntropy::new()).is_cryptographically_secure());
+
+        let mut weak = Blake3Entropy::from_seed_u64(9);
+        weak.reset();
+        assert!(!weak.is_cryptographically_secure());
+        assert!(!Blake3Entropy::from_source(&mut MockEntropy::new(1)).is_cryptographically_secure());
+    }
+}
@@ -0,0 +1,134 @@
+//! Wrapper for sources with a maximum request size
+
+use super::{EntropySource, SourceConfig};
+
+/// Splits large `fill_bytes` requests into bounded sub-requests
+///
+/// Some hardware or OS backends can only return a limited number of bytes
+/// per call. Wrapping such a source in `ChunkedFill` lets the rest of the
+/// framework request any amount.
+///
+/// The default limit is 256 bytes, matching historical `getrandom` caps.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{ChunkedFill, EntropySource, SystemEntropy};
+///
+/// let mut source = ChunkedFill::new(SystemEntropy::new()).with_max_request(64);
+/// let mut buffer = [0u8; 1000];
+/// source.fill_bytes(&mut buffer); // 16 calls of at most 64 bytes
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChunkedFill<E: EntropySource> {
+    inner: E,
+    max_request: usize,
+}
+
+impl<E: EntropySource> ChunkedFill<E> {
+    /// Default maximum bytes per inner `fill_bytes` call
+    pub const DEFAULT_MAX_REQUEST: usize = 256;
+
+    /// Wrap a source with the default request limit
+    pub fn new(inner: E) -> Self {
+        Self {
+            inner,
+            max_request: Self::DEFAULT_MAX_REQUEST,
+        }
+    }
+
+    /// Set the maximum bytes per inner `fill_bytes` call (minimum 1)
+    pub fn with_max_request(mut self, max_request: usize) -> Self {
+        self.max_request = max_request.max(1);
+        self
+    }
+
+    /// Current maximum request size
+    pub fn max_request(&self) -> usize {
+        self.max_request
+    }
+
+    /// Unwrap and return the inner source
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E: EntropySource> EntropySource for ChunkedFill<E> {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(self.max_request) {
+            self.inner.fill_bytes(chunk);
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset()
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.inner.is_deterministic()
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        self.inner.estimated_entropy_per_byte()
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        self.inner.is_cryptographically_secure()
+    }
+
+    fn source_config(&self) -> Option<SourceConfig> {
+        self.inner.source_config()
+    }
+
+    fn remaining_bytes(&self) -> Option<u64> {
+        self.inner.remaining_bytes()
+    }
+
+    fn is_throttled(&self) -> bool {
+        self.inner.is_throttled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::MockEntropy;
+
+    struct LimitedSource {
+        inner: MockEntropy,
+    }
+
+    impl EntropySource for LimitedSource {
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            assert!(dest.len() <= 16, "requested {} bytes", dest.len());
+            self.inner.fill_bytes(dest);
+        }
+    }
+
+    #[test]
+    fn test_chunked_fill_respects_limit() {
+        let mut source = ChunkedFill::new(LimitedSource { inner: MockEntropy::new(42) })
+            .with_max_request(16);
+        let mut buf = [0u8; 100];
+        source.fill_bytes(&mut buf);
+
+        // Chunking must not change the stream
+        let mut expected = [0u8; 100];
+        MockEntropy::new(42).fill_bytes(&mut expected);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "requested 100 bytes")]
+    fn test_unwrapped_limited_source_panics() {
+        let mut source = LimitedSource { inner: MockEntropy::new(42) };
+        let mut buf = [0u8; 100];
+        source.fill_bytes(&mut buf);
+    }
+
+}
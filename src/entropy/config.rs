@@ -0,0 +1,194 @@
+//! Serializable entropy source configuration
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use super::{ChaChaEntropy, EntropySource, MockEntropy, SystemEntropy};
+
+/// Errors from [`SourceConfig::build`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum SourceConfigError {
+    /// The config names a key by fingerprint but doesn't include it
+    #[error("config has only the fingerprint {0} of its ChaCha key, so the source can't be rebuilt")]
+    KeyNotEmbedded(String),
+}
+
+/// Serializable description of how to build an entropy source
+///
+/// Saved alongside a [`QualityReport`](crate::quality::QualityReport) so a
+/// run can be reproduced. The JSON form is tagged by `kind`:
+///
+/// ```json
+/// { "kind": "system" }
+/// { "kind": "mock", "seed": 42 }
+/// { "kind": "chacha", "key": "<64 hex chars>", "nonce": 0 }
+/// { "kind": "chacha-fingerprint", "fingerprint": "<16 hex chars>", "nonce": 0 }
+/// ```
+///
+/// Sources describe a secret key by fingerprint unless asked to embed it
+/// (see [`ChaChaEntropy::embed_key_in_config`]), so reports and saved
+/// settings don't leak keys. A fingerprint identifies the key but can't
+/// rebuild the source.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::SourceConfig;
+///
+/// let config = SourceConfig::Mock { seed: 42 };
+/// let mut source = config.build().unwrap();
+///
+/// assert!(source.is_deterministic());
+/// assert_eq!(SourceConfig::describe(&*source), Some(config));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum SourceConfig {
+    /// [`SystemEntropy`]; not reproducible
+    System,
+
+    /// [`MockEntropy`] with the given seed
+    Mock {
+        /// LCG seed
+        seed: u64,
+    },
+
+    /// [`ChaChaEntropy`] with the given key and stream nonce
+    ChaCha {
+        /// 32-byte key, hex-encoded in serialized form
+        #[serde(with = "hex::serde")]
+        key: [u8; 32],
+        /// Stream (nonce) number
+        #[serde(default)]
+        nonce: u64,
+        /// Key expanded from a guessable 64-bit seed
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        weak_seed: bool,
+    },
+
+    /// [`ChaChaEntropy`] whose key is known only by its fingerprint
+    #[serde(rename = "chacha-fingerprint")]
+    ChaChaFingerprint {
+        /// [`key_fingerprint`](SourceConfig::key_fingerprint) of the key
+        fingerprint: String,
+        /// Stream (nonce) number
+        #[serde(default)]
+        nonce: u64,
+        /// Key expanded from a guessable 64-bit seed
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        weak_seed: bool,
+    },
+}
+
+impl SourceConfig {
+    /// Build a fresh source from this configuration
+    ///
+    /// Returns [`SourceConfigError::KeyNotEmbedded`] for a
+    /// [`ChaChaFingerprint`](SourceConfig::ChaChaFingerprint), which
+    /// doesn't carry the key.
+    pub fn build(&self) -> Result<Box<dyn EntropySource>, SourceConfigError> {
+        Ok(match *self {
+            SourceConfig::System => Box::new(SystemEntropy::new()),
+            SourceConfig::Mock { seed } => Box::new(MockEntropy::new(seed)),
+            SourceConfig::ChaCha { key, nonce, weak_seed } => Box::new(ChaChaEntropy::from_config(key, nonce, weak_seed)),
+            SourceConfig::ChaChaFingerprint { ref fingerprint, .. } => {
+                return Err(SourceConfigError::KeyNotEmbedded(fingerprint.clone()));
+            }
+        })
+    }
+
+    /// Replace an embedded key with its fingerprint
+    ///
+    /// For configs about to be shown or shared, such as a report sent back
+    /// to a client. Other configs are returned unchanged.
+    pub fn redacted(self) -> Self {
+        match self {
+            SourceConfig::ChaCha { key, nonce, weak_seed } => SourceConfig::ChaChaFingerprint {
+                fingerprint: Self::key_fingerprint(&key),
+                nonce,
+                weak_seed,
+            },
+            other => other,
+        }
+    }
+
+    /// Identify a key without revealing it: the first 8 bytes of its
+    /// SHA-256 hash, hex-encoded
+    pub fn key_fingerprint(key: &[u8; 32]) -> String {
+        hex::encode(&Sha256::digest(key)[..8])
+    }
+
+    /// Describe an existing source, if it knows its configuration
+    ///
+    /// This is the configuration the source was built with (the state
+    /// [`reset`](EntropySource::reset) returns to), not its current
+    /// position in the stream.
+    pub fn describe<E: ?Sized + EntropySource>(source: &E) -> Option<Self> {
+        source.source_config()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::SeedableEntropy;
+
+    #[test]
+    fn test_json_round_trip() {
+        let configs = [
+            SourceConfig::System,
+            SourceConfig::Mock { seed: 42 },
+            SourceConfig::ChaCha { key: [7; 32], nonce: 3, weak_seed: false },
+            SourceConfig::ChaCha { key: [7; 32], nonce: 0, weak_seed: true },
+        ];
+
+        for config in configs {
+            let json = serde_json::to_string(&config).unwrap();
+            let parsed: SourceConfig = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, config);
+
+            let source = parsed.build().unwrap();
+            assert_eq!(SourceConfig::describe(&*source), Some(config));
+        }
+
+        let parsed: SourceConfig = serde_json::from_str(r#"{ "kind": "mock", "seed": 42 }"#).unwrap();
+        assert_eq!(parsed, SourceConfig::Mock { seed: 42 });
+    }
+
+    #[test]
+    fn test_built_source_matches_direct() {
+        let json = format!(r#"{{ "kind": "chacha", "key": "{}" }}"#, "07".repeat(32));
+        let config: SourceConfig = serde_json::from_str(&json).unwrap();
+
+        let mut built = config.build().unwrap();
+        let mut direct = ChaChaEntropy::new([7; 32]);
+        assert_eq!(built.next_u64(), direct.next_u64());
+        assert!(built.is_cryptographically_secure());
+
+        let mut built = SourceConfig::Mock { seed: 9 }.build().unwrap();
+        let mut direct = MockEntropy::new(9);
+        assert_eq!(built.next_u64(), direct.next_u64());
+    }
+
+    #[test]
+    fn test_keys_stay_out_by_default() {
+        let fingerprint = SourceConfig::key_fingerprint(&[7; 32]);
+        assert_eq!(fingerprint.len(), 16);
+
+        let config = SourceConfig::describe(&ChaChaEntropy::with_nonce([7; 32], 2)).unwrap();
+        assert_eq!(config, SourceConfig::ChaChaFingerprint { fingerprint: fingerprint.clone(), nonce: 2, weak_seed: false });
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains(&"07".repeat(32)), "{}", json);
+        assert_eq!(config.build().err(), Some(SourceConfigError::KeyNotEmbedded(fingerprint)));
+
+        let embedded = SourceConfig::ChaCha { key: [7; 32], nonce: 2, weak_seed: false };
+        assert_eq!(embedded.redacted(), config);
+        assert_eq!(SourceConfig::System.redacted(), SourceConfig::System);
+
+        // Opting in embeds the key, and a weak seed survives the round trip
+        let weak = ChaChaEntropy::from_seed_u64(5).embed_key_in_config();
+        let rebuilt = SourceConfig::describe(&weak).unwrap().build().unwrap();
+        assert!(!rebuilt.is_cryptographically_secure());
+        assert_eq!(SourceConfig::describe(&*rebuilt), SourceConfig::describe(&weak));
+    }
+}
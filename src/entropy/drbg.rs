@@ -0,0 +1,304 @@
+//! NIST SP 800-90A deterministic random bit generators
+
+use thiserror::Error;
+use crate::crypto::HmacSha256;
+use super::EntropySource;
+
+/// Errors from [`HmacDrbg`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DrbgError {
+    /// Entropy input shorter than the 256-bit security strength
+    #[error("entropy input must be at least 32 bytes, got {0}")]
+    InsufficientEntropy(usize),
+
+    /// A single request asked for more than 2^19 bits
+    #[error("requested {0} bytes, more than the {max} allowed per request", max = HmacDrbg::MAX_BYTES_PER_REQUEST)]
+    RequestTooLarge(usize),
+
+    /// The reseed interval has been reached; call `reseed` first
+    #[error("reseed required after 2^48 requests")]
+    ReseedRequired,
+}
+
+/// HMAC_DRBG with SHA-256 (NIST SP 800-90A Rev. 1, section 10.1.2)
+///
+/// A standardized DRBG for when compliance matters: instantiate it with
+/// real entropy and a nonce, reseed it periodically, and every output block
+/// is HMAC-SHA256 under a key that ratchets forward after each request.
+/// Prediction resistance is not provided; reseed explicitly when fresh
+/// entropy is available.
+///
+/// After instantiation it is also an [`EntropySource`] whose `fill_bytes`
+/// generates without additional input. `reset()` returns to the
+/// just-instantiated state, so the stream is reproducible.
+///
+/// [`is_cryptographically_secure`](EntropySource::is_cryptographically_secure)
+/// trusts entropy passed to [`instantiate`](Self::instantiate), but a
+/// DRBG built by [`from_source`](Self::from_source) is only as secure as
+/// the source it was seeded from.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::drbg::HmacDrbg;
+/// use entropy_forge::entropy::{EntropySource, SystemEntropy};
+///
+/// let mut drbg = HmacDrbg::from_source(&mut SystemEntropy::new(), b"my app");
+/// let mut key = [0u8; 32];
+/// drbg.generate(&mut key, b"").unwrap();
+///
+/// // Or use it like any other source
+/// let word = drbg.next_u64();
+/// ```
+#[derive(Clone)]
+pub struct HmacDrbg {
+    key: [u8; 32],
+    value: [u8; 32],
+    reseed_counter: u64,
+    initial: ([u8; 32], [u8; 32]),
+    // Seeded from a source that is not cryptographically secure
+    weak_seed: bool,
+}
+
+impl HmacDrbg {
+    /// Largest single request: 2^19 bits
+    pub const MAX_BYTES_PER_REQUEST: usize = 1 << 16;
+
+    /// Requests allowed between reseeds
+    pub const RESEED_INTERVAL: u64 = 1 << 48;
+
+    /// Minimum entropy input length for 256-bit security strength
+    pub const MIN_ENTROPY_BYTES: usize = 32;
+
+    /// Instantiate from entropy input, a nonce and a personalization string
+    ///
+    /// Returns [`DrbgError::InsufficientEntropy`] if `entropy` is shorter
+    /// than [`MIN_ENTROPY_BYTES`](Self::MIN_ENTROPY_BYTES). The nonce should
+    /// be at least 16 bytes and never repeat for the same entropy.
+    pub fn instantiate(entropy: &[u8], nonce: &[u8], personalization: &[u8]) -> Result<Self, DrbgError> {
+        if entropy.len() < Self::MIN_ENTROPY_BYTES {
+            return Err(DrbgError::InsufficientEntropy(entropy.len()));
+        }
+
+        let mut drbg = Self {
+            key: [0x00; 32],
+            value: [0x01; 32],
+            reseed_counter: 1,
+            initial: ([0; 32], [0; 32]),
+            weak_seed: false,
+        };
+        drbg.update(&[entropy, nonce, personalization]);
+        drbg.initial = (drbg.key, drbg.value);
+
+        Ok(drbg)
+    }
+
+    /// Instantiate with 32 bytes of entropy and a 16-byte nonce from `source`
+    ///
+    /// The DRBG reports itself secure only if `source` does.
+    pub fn from_source<E: ?Sized + EntropySource>(source: &mut E, personalization: &[u8]) -> Self {
+        let mut entropy = [0u8; 32];
+        let mut nonce = [0u8; 16];
+        source.fill_bytes(&mut entropy);
+        source.fill_bytes(&mut nonce);
+
+        let drbg = Self::instantiate(&entropy, &nonce, personalization)
+            .expect("32 bytes meets the minimum entropy length");
+        Self {
+            weak_seed: !source.is_cryptographically_secure(),
+            ..drbg
+        }
+    }
+
+    /// Mix in fresh entropy and optional additional input
+    pub fn reseed(&mut self, entropy: &[u8], additional: &[u8]) -> Result<(), DrbgError> {
+        if entropy.len() < Self::MIN_ENTROPY_BYTES {
+            return Err(DrbgError::InsufficientEntropy(entropy.len()));
+        }
+
+        self.update(&[entropy, additional]);
+        self.reseed_counter = 1;
+        Ok(())
+    }
+
+    /// Fill `out` with generated bytes, mixing in optional additional input
+    ///
+    /// Returns [`DrbgError::RequestTooLarge`] above
+    /// [`MAX_BYTES_PER_REQUEST`](Self::MAX_BYTES_PER_REQUEST) and
+    /// [`DrbgError::ReseedRequired`] once the reseed interval is reached.
+    pub fn generate(&mut self, out: &mut [u8], additional: &[u8]) -> Result<(), DrbgError> {
+        if out.len() > Self::MAX_BYTES_PER_REQUEST {
+            return Err(DrbgError::RequestTooLarge(out.len()));
+        }
+        if self.reseed_counter > Self::RESEED_INTERVAL {
+            log::debug!("HMAC_DRBG reseed interval reached; refusing to generate");
+            return Err(DrbgError::ReseedRequired);
+        }
+
+        if !additional.is_empty() {
+            self.update(&[additional]);
+        }
+
+        let hmac = HmacSha256::new(&self.key);
+        for block in out.chunks_mut(32) {
+            self.value = hmac.mac(&[&self.value]);
+            block.copy_from_slice(&self.value[..block.len()]);
+        }
+
+        self.update(&[additional]);
+        self.reseed_counter += 1;
+        Ok(())
+    }
+
+    // HMAC_DRBG_Update over the concatenation of `provided`
+    fn update(&mut self, provided: &[&[u8]]) {
+        let empty = provided.iter().all(|part| part.is_empty());
+
+        for round in [0x00u8, 0x01] {
+            if round == 0x01 && empty {
+                break;
+            }
+
+            let value = self.value;
+            let round = [round];
+            let mut message: Vec<&[u8]> = vec![&value, &round];
+            message.extend_from_slice(provided);
+            self.key = HmacSha256::new(&self.key).mac(&message);
+            self.value = HmacSha256::new(&self.key).mac(&[&self.value]);
+        }
+    }
+}
+
+impl std::fmt::Debug for HmacDrbg {
+    // Never print the internal state
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HmacDrbg")
+            .field("reseed_counter", &self.reseed_counter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EntropySource for HmacDrbg {
+    /// # Panics
+    ///
+    /// Panics if the reseed interval is reached; call
+    /// [`reseed`](HmacDrbg::reseed) before then.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(Self::MAX_BYTES_PER_REQUEST) {
+            if let Err(e) = self.generate(chunk, &[]) {
+                panic!("HMAC_DRBG: {}", e);
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "HMAC_DRBG (SHA-256)"
+    }
+
+    fn reset(&mut self) {
+        (self.key, self.value) = self.initial;
+        self.reseed_counter = 1;
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        Some(8.0)
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        !self.weak_seed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{assert_replays, MockEntropy, SystemEntropy};
+
+    fn unhex(s: &str) -> Vec<u8> {
+        hex::decode(s).unwrap()
+    }
+
+    // CAVP drbgvectors HMAC_DRBG.rsp, [SHA-256], no prediction resistance,
+    // no reseed: instantiate, generate 1024 bits twice, check the second
+    fn cavp(entropy: &str, nonce: &str, additional: [&str; 2], expected: &str) {
+        let mut drbg = HmacDrbg::instantiate(&unhex(entropy), &unhex(nonce), b"").unwrap();
+        let mut out = [0u8; 128];
+        drbg.generate(&mut out, &unhex(additional[0])).unwrap();
+        drbg.generate(&mut out, &unhex(additional[1])).unwrap();
+        assert_eq!(hex::encode(out), expected);
+    }
+
+    #[test]
+    fn test_cavp_sha256() {
+        // COUNT = 0
+        cavp(
+            "ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488",
+            "659ba96c601dc69fc902940805ec0ca8",
+            ["", ""],
+            "e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89\
+             d54fbb978a15b5c443c9ec21036d2460b6f73ebad0dc2aba6e624abf07745bc1\
+             07694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668\
+             961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8",
+        );
+
+        // COUNT = 0, AdditionalInputLen = 256
+        cavp(
+            "d3cc4d1acf3dde0c4bd2290d262337042dc632948223d3a2eaab87da44295fbd",
+            "0109b0e729f457328aa18569a9224921",
+            [
+                "3c311848183c9a212a26f27f8c6647e40375e466a0857cc39c4e47575d53f1f6",
+                "fcb9abd19ccfbccef88c9c39bfb3dd7b1c12266c9808992e305bc3cff566e4e4",
+            ],
+            "9c7b758b212cd0fcecd5daa489821712e3cdea4467b560ef5ddc24ab47749a1f\
+             1ffdbbb118f4e62fcfca3371b8fbfc5b0646b83e06bfbbab5fac30ea09ea2bc7\
+             6f1ea568c9be0444b2cc90517b20ca825f2d0eccd88e7175538b85d90ab39018\
+             3ca6395535d34473af6b5a5b88f5a59ee7561573337ea819da0dcc3573a22974",
+        );
+    }
+
+    #[test]
+    fn test_limits_and_reseed() {
+        assert_eq!(
+            HmacDrbg::instantiate(&[0; 16], &[0; 16], b"").err(),
+            Some(DrbgError::InsufficientEntropy(16))
+        );
+
+        let mut drbg = HmacDrbg::instantiate(&[7; 32], &[1; 16], b"test").unwrap();
+        let mut big = vec![0u8; HmacDrbg::MAX_BYTES_PER_REQUEST + 1];
+        assert_eq!(drbg.generate(&mut big, b""), Err(DrbgError::RequestTooLarge(big.len())));
+
+        let mut copy = drbg.clone();
+        drbg.reseed(&[9; 32], b"").unwrap();
+        assert_ne!(drbg.next_u64(), copy.next_u64());
+
+        drbg.reseed_counter = HmacDrbg::RESEED_INTERVAL + 1;
+        assert_eq!(drbg.generate(&mut [0; 8], b""), Err(DrbgError::ReseedRequired));
+    }
+
+    #[test]
+    fn test_drbg_source_replays() {
+        let mut drbg = HmacDrbg::instantiate(&[3; 32], &[4; 16], b"").unwrap();
+
+        // fill_bytes splits large requests without changing the limit
+        let mut data = vec![0u8; HmacDrbg::MAX_BYTES_PER_REQUEST * 2 + 5];
+        drbg.fill_bytes(&mut data);
+
+        assert_replays(&mut drbg);
+        assert!(!format!("{:?}", drbg).contains("key"));
+    }
+
+    #[test]
+    fn test_from_source_inherits_security() {
+        assert!(HmacDrbg::instantiate(&[3; 32], &[4; 16], b"").unwrap().is_cryptographically_secure());
+        assert!(HmacDrbg::from_source(&mut SystemEntropy::new(), b"").is_cryptographically_secure());
+
+        let mut drbg = HmacDrbg::from_source(&mut MockEntropy::new(1), b"");
+        assert!(!drbg.is_cryptographically_secure());
+        drbg.reset();
+        assert!(!drbg.is_cryptographically_secure());
+    }
+}
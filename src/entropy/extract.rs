@@ -0,0 +1,222 @@
+//! Randomness extractors for conditioning weak sources
+
+use super::{ChaChaEntropy, EntropySource, SeedableEntropy};
+
+/// Seeded Toeplitz-matrix extractor (universal hashing over GF(2))
+///
+/// Multiplies each `input_bits` block of the raw input by a fixed random
+/// `output_bits × input_bits` Toeplitz matrix. Toeplitz hashing is a
+/// universal hash family, so by the leftover hash lemma the output is
+/// within ε of uniform whenever a block carries at least
+/// `output_bits + 2·log2(1/ε)` bits of min-entropy. Unlike an ad-hoc
+/// hash, this bound holds for any source with that much min-entropy; use
+/// [`recommended_output_bits`](Self::recommended_output_bits) to size the
+/// output from a min-entropy estimate.
+///
+/// The matrix is defined by `input_bits + output_bits - 1` bits expanded
+/// from `seed` with ChaCha20. The seed may be public but must not depend
+/// on the input.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::extract::ToeplitzExtractor;
+/// use entropy_forge::entropy::{BiasedEntropy, EntropySource};
+///
+/// // 70% ones: about 0.51 bits of min-entropy per bit
+/// let mut weak = BiasedEntropy::new(1, 0.7);
+/// let output_bits = ToeplitzExtractor::recommended_output_bits(1024, 8.0 * 0.51, 64);
+/// let extractor = ToeplitzExtractor::new(42, 1024, output_bits);
+///
+/// let mut raw = vec![0u8; 1024 / 8 * 10];
+/// weak.fill_bytes(&mut raw);
+/// let conditioned = extractor.extract(&raw);
+/// assert_eq!(conditioned.len(), (10 * output_bits).div_ceil(8));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ToeplitzExtractor {
+    // Diagonal bits in reverse, LSB-first within each word: row i of the
+    // matrix is the input_bits-wide window starting at bit output_bits - 1 - i
+    diagonals: Vec<u64>,
+    input_bits: usize,
+    output_bits: usize,
+}
+
+impl ToeplitzExtractor {
+    /// Create an extractor mapping `input_bits` blocks to `output_bits`
+    ///
+    /// # Panics
+    ///
+    /// Panics if either size is 0 or `output_bits` exceeds `input_bits`.
+    pub fn new(seed: u64, input_bits: usize, output_bits: usize) -> Self {
+        assert!(input_bits > 0 && output_bits > 0, "block sizes must be non-zero");
+        assert!(output_bits <= input_bits, "an extractor cannot output more bits than it reads");
+
+        let len = input_bits + output_bits - 1;
+        // One spare word so unaligned windows can always read the next word
+        let mut diagonals = vec![0u64; len.div_ceil(64) + 1];
+        let mut rng = ChaChaEntropy::from_seed_u64(seed);
+        for word in &mut diagonals[..len.div_ceil(64)] {
+            *word = rng.next_u64();
+        }
+        if !len.is_multiple_of(64) {
+            diagonals[len / 64] &= (1u64 << (len % 64)) - 1;
+        }
+
+        Self { diagonals, input_bits, output_bits }
+    }
+
+    /// Output length that keeps the result `2^-security_bits`-close to uniform
+    ///
+    /// Leftover hash lemma: with k bits of min-entropy per block, at most
+    /// `k - 2 * security_bits` nearly uniform bits can be extracted.
+    /// `min_entropy_per_byte` is in bits per byte, as reported by
+    /// [`QualityMetrics::min_entropy`](crate::quality::QualityMetrics::min_entropy).
+    /// Returns 0 if the block holds too little entropy for the requested
+    /// security.
+    pub fn recommended_output_bits(input_bits: usize, min_entropy_per_byte: f64, security_bits: u32) -> usize {
+        let per_bit = (min_entropy_per_byte / 8.0).clamp(0.0, 1.0);
+        let k = input_bits as f64 * per_bit;
+        (k - 2.0 * security_bits as f64).max(0.0).floor() as usize
+    }
+
+    /// Bits read per block
+    pub fn input_bits(&self) -> usize {
+        self.input_bits
+    }
+
+    /// Bits produced per block
+    pub fn output_bits(&self) -> usize {
+        self.output_bits
+    }
+
+    /// Condition `input`, one `input_bits` block at a time
+    ///
+    /// Bits are read most significant first. Each complete block
+    /// contributes `output_bits` bits to the output, packed most
+    /// significant first; a trailing partial input block is ignored, and a
+    /// final partial output byte is padded with zeros.
+    pub fn extract(&self, input: &[u8]) -> Vec<u8> {
+        let blocks = input.len() * 8 / self.input_bits;
+        let mut output = vec![0u8; (blocks * self.output_bits).div_ceil(8)];
+        let mut block = vec![0u64; self.input_bits.div_ceil(64)];
+
+        let mut out_bit = 0;
+        for b in 0..blocks {
+            self.load_block(input, b * self.input_bits, &mut block);
+            for row in 0..self.output_bits {
+                if self.row_product(row, &block) {
+                    output[out_bit / 8] |= 0x80 >> (out_bit % 8);
+                }
+                out_bit += 1;
+            }
+        }
+
+        output
+    }
+
+    // Pack input bits [start, start + input_bits) LSB-first into words
+    fn load_block(&self, input: &[u8], start: usize, block: &mut [u64]) {
+        block.fill(0);
+        for j in 0..self.input_bits {
+            let bit = start + j;
+            if input[bit / 8] & (0x80 >> (bit % 8)) != 0 {
+                block[j / 64] |= 1 << (j % 64);
+            }
+        }
+    }
+
+    // Inner product over GF(2) of matrix row `row` with the packed block
+    fn row_product(&self, row: usize, block: &[u64]) -> bool {
+        let offset = self.output_bits - 1 - row;
+        let shift = offset % 64;
+        let base = offset / 64;
+
+        let mut acc = 0u64;
+        for (w, &x) in block.iter().enumerate() {
+            let low = self.diagonals[base + w] >> shift;
+            let window = if shift == 0 {
+                low
+            } else {
+                low | self.diagonals[base + w + 1] << (64 - shift)
+            };
+            acc ^= window & x;
+        }
+
+        // Bits past input_bits in the last window meet zeros in `block`
+        acc.count_ones() % 2 == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::BiasedEntropy;
+    use crate::quality::{NistTests, QualityMetrics};
+
+    // Straightforward matrix product: T[i][j] = t[i - j + n - 1]
+    fn naive(extractor: &ToeplitzExtractor, input: &[u8]) -> Vec<bool> {
+        let (n, m) = (extractor.input_bits, extractor.output_bits);
+        let len = n + m - 1;
+        let reversed = |k: usize| extractor.diagonals[k / 64] >> (k % 64) & 1 == 1;
+        let t = |k: usize| reversed(len - 1 - k);
+        let x = |j: usize| input[j / 8] & (0x80 >> (j % 8)) != 0;
+
+        (0..m)
+            .map(|i| (0..n).filter(|&j| t(i + n - 1 - j) && x(j)).count() % 2 == 1)
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_naive_product() {
+        let mut input = [0u8; 40];
+        ChaChaEntropy::from_seed_u64(3).fill_bytes(&mut input);
+
+        for (n, m) in [(320, 130), (200, 64), (64, 1), (129, 129)] {
+            let extractor = ToeplitzExtractor::new(7, n, m);
+            let output = extractor.extract(&input[..n.div_ceil(8)]);
+            let bits: Vec<bool> = (0..m).map(|i| output[i / 8] & (0x80 >> (i % 8)) != 0).collect();
+            assert_eq!(bits, naive(&extractor, &input), "{}x{}", m, n);
+        }
+    }
+
+    #[test]
+    fn test_linear_and_blockwise() {
+        let extractor = ToeplitzExtractor::new(1, 256, 128);
+        let a: Vec<u8> = (0..96).collect();
+        let b: Vec<u8> = (0..96u8).map(|i| i.wrapping_mul(7).wrapping_add(3)).collect();
+        let xor: Vec<u8> = a.iter().zip(&b).map(|(x, y)| x ^ y).collect();
+
+        // Three 256-bit blocks, each extracted on its own
+        let (ea, eb) = (extractor.extract(&a), extractor.extract(&b));
+        assert_eq!(ea.len(), 48);
+        let expected: Vec<u8> = ea.iter().zip(&eb).map(|(x, y)| x ^ y).collect();
+        assert_eq!(extractor.extract(&xor), expected);
+        assert_eq!(extractor.extract(&a[32..64]), ea[16..32]);
+        assert!(extractor.extract(&a[..31]).is_empty());
+    }
+
+    #[test]
+    fn test_biased_source_passes_after_extraction() {
+        let mut weak = BiasedEntropy::new(9, 0.65);
+        let mut raw = vec![0u8; 200_000];
+        weak.fill_bytes(&mut raw);
+        assert!(NistTests::frequency_test(&raw).unwrap() < 0.01);
+
+        let min_entropy = QualityMetrics::min_entropy(&raw).unwrap();
+        let output_bits = ToeplitzExtractor::recommended_output_bits(1024, min_entropy, 64);
+        assert!(output_bits > 256 && output_bits < 1024 - 128, "{}", output_bits);
+
+        let extractor = ToeplitzExtractor::new(2024, 1024, output_bits / 8 * 8);
+        let conditioned = extractor.extract(&raw);
+        assert!(NistTests::frequency_test(&conditioned).unwrap() >= 0.01);
+        assert!(NistTests::runs_test(&conditioned).unwrap() >= 0.01);
+    }
+
+    #[test]
+    fn test_recommended_output_bits() {
+        assert_eq!(ToeplitzExtractor::recommended_output_bits(1024, 8.0, 64), 896);
+        assert_eq!(ToeplitzExtractor::recommended_output_bits(1024, 4.0, 64), 384);
+        assert_eq!(ToeplitzExtractor::recommended_output_bits(1024, 0.5, 64), 0);
+    }
+}
@@ -0,0 +1,165 @@
+//! Round-robin interleaving of two entropy sources
+
+use super::EntropySource;
+
+/// Alternates `stride` bytes from each of two sources
+///
+/// Unlike XOR-combining, the output keeps each source's bytes intact, so a
+/// single stream can carry both for correlation tests, adversarial test
+/// inputs or round-robin hardware sampling. The position within the
+/// current stride carries over between `fill_bytes` calls, so the pattern
+/// doesn't depend on how the output is requested.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, InterleavedEntropy, MockEntropy, SystemEntropy};
+///
+/// let mut source = InterleavedEntropy::new(
+///     Box::new(SystemEntropy::new()),
+///     Box::new(MockEntropy::new(42)),
+///     4,
+/// );
+/// let mut buffer = [0u8; 16]; // 4 system, 4 mock, 4 system, 4 mock
+/// source.fill_bytes(&mut buffer);
+/// ```
+pub struct InterleavedEntropy {
+    first: Box<dyn EntropySource>,
+    second: Box<dyn EntropySource>,
+    stride: usize,
+    offset: usize,
+    on_second: bool,
+    name: String,
+}
+
+impl InterleavedEntropy {
+    /// Interleave `first` and `second`, `stride` bytes at a time (minimum 1)
+    pub fn new(first: Box<dyn EntropySource>, second: Box<dyn EntropySource>, stride: usize) -> Self {
+        let name = format!("Interleaved ({} / {})", first.name(), second.name());
+
+        Self {
+            first,
+            second,
+            stride: stride.max(1),
+            offset: 0,
+            on_second: false,
+            name,
+        }
+    }
+
+    /// Bytes taken from each source before switching
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+}
+
+impl EntropySource for InterleavedEntropy {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut filled = 0;
+
+        while filled < dest.len() {
+            let len = (self.stride - self.offset).min(dest.len() - filled);
+            let source = if self.on_second { &mut self.second } else { &mut self.first };
+            source.fill_bytes(&mut dest[filled..filled + len]);
+
+            filled += len;
+            self.offset += len;
+            if self.offset == self.stride {
+                self.offset = 0;
+                self.on_second = !self.on_second;
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.offset = 0;
+        self.on_second = false;
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.first.is_deterministic() && self.second.is_deterministic()
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        // Both sources contribute equally in the long run
+        let first = self.first.estimated_entropy_per_byte()?;
+        let second = self.second.estimated_entropy_per_byte()?;
+        Some((first + second) / 2.0)
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        self.first.is_cryptographically_secure() && self.second.is_cryptographically_secure()
+    }
+
+    fn remaining_bytes(&self) -> Option<u64> {
+        // Conservative: the output stops being real data as soon as either
+        // source runs dry, and the smaller one may be up next
+        [self.first.remaining_bytes(), self.second.remaining_bytes()].into_iter().flatten().min()
+    }
+
+    fn is_throttled(&self) -> bool {
+        self.first.is_throttled() || self.second.is_throttled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{assert_replays, MockEntropy, RecordedEntropy};
+
+    /// Emits a constant byte
+    struct ConstSource(u8);
+
+    impl EntropySource for ConstSource {
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.fill(self.0);
+        }
+    }
+
+    #[test]
+    fn test_alternates_at_stride() {
+        let mut source = InterleavedEntropy::new(Box::new(ConstSource(0xAA)), Box::new(ConstSource(0x55)), 3);
+
+        let mut data = [0u8; 14];
+        source.fill_bytes(&mut data);
+        assert_eq!(data, [0xAA, 0xAA, 0xAA, 0x55, 0x55, 0x55, 0xAA, 0xAA, 0xAA, 0x55, 0x55, 0x55, 0xAA, 0xAA]);
+
+        // The stride position carries over between calls
+        let mut next = [0u8; 2];
+        source.fill_bytes(&mut next);
+        assert_eq!(next, [0xAA, 0x55]);
+    }
+
+    #[test]
+    fn test_remaining_bytes_is_the_smaller_source() {
+        let unbounded = InterleavedEntropy::new(Box::new(MockEntropy::new(1)), Box::new(MockEntropy::new(2)), 4);
+        assert_eq!(unbounded.remaining_bytes(), None);
+
+        let one_finite = InterleavedEntropy::new(Box::new(MockEntropy::new(1)), Box::new(RecordedEntropy::new(vec![0; 40])), 4);
+        assert_eq!(one_finite.remaining_bytes(), Some(40));
+
+        let mut both_finite = InterleavedEntropy::new(
+            Box::new(RecordedEntropy::new(vec![0; 100])),
+            Box::new(RecordedEntropy::new(vec![0; 40])),
+            4,
+        );
+        assert_eq!(both_finite.remaining_bytes(), Some(40));
+
+        let mut data = [0u8; 16];
+        both_finite.fill_bytes(&mut data);
+        assert_eq!(both_finite.remaining_bytes(), Some(32));
+    }
+
+    #[test]
+    fn test_interleaved_replays() {
+        let mut source = InterleavedEntropy::new(Box::new(MockEntropy::new(1)), Box::new(MockEntropy::new(2)), 5);
+        assert!(source.is_deterministic());
+        assert_replays(&mut source);
+    }
+}
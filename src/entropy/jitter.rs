@@ -0,0 +1,166 @@
+//! Last-resort entropy harvested from CPU timing jitter
+
+use std::hint::black_box;
+use std::time::Instant;
+use sha2::{Digest, Sha256};
+use super::EntropySource;
+
+/// Entropy from timing variations of a tight CPU loop
+///
+/// Each sample times a short busy loop with [`Instant::now`] and folds the
+/// nanosecond delta into one byte. Caches, interrupts, frequency scaling
+/// and scheduling make the low bits of such deltas vary. Every 32 output
+/// bytes are a SHA-256 hash of [`samples_per_byte`](Self::samples_per_byte)
+/// × 32 samples and a block counter.
+///
+/// **Weak assurance.** This is a fallback for platforms without a usable
+/// OS RNG, not a replacement for [`SystemEntropy`](super::SystemEntropy).
+/// How much entropy the jitter carries depends on the CPU, the clock's
+/// resolution and the system's load, and nothing here measures it: for
+/// full-entropy output the default 64 samples per byte need at least 1/8
+/// bit per sample, which a coarse or virtualized clock may not deliver.
+/// The hash makes the output look uniform either way, so passing the
+/// quality suite says nothing about the real entropy. Test the
+/// unconditioned samples from [`fill_raw`](Self::fill_raw) instead.
+///
+/// Generation is slow, typically tens to hundreds of KB/s. The source is
+/// not `Clone`, since a copy would repeat the buffered output.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, JitterEntropy};
+///
+/// let mut source = JitterEntropy::new();
+/// let mut seed = [0u8; 32];
+/// source.fill_bytes(&mut seed);
+/// ```
+#[derive(Debug)]
+pub struct JitterEntropy {
+    samples_per_byte: usize,
+    counter: u64,
+    block: [u8; 32],
+    used: usize,
+}
+
+impl JitterEntropy {
+    /// Default timing samples hashed per output byte
+    pub const DEFAULT_SAMPLES_PER_BYTE: usize = 64;
+
+    /// Busy-loop iterations timed per sample
+    const LOOP_ITERATIONS: u64 = 64;
+
+    /// Create a source with the default oversampling
+    pub fn new() -> Self {
+        Self::with_samples_per_byte(Self::DEFAULT_SAMPLES_PER_BYTE)
+    }
+
+    /// Create a source hashing `samples_per_byte` samples per output byte
+    ///
+    /// More samples are slower but tolerate less jitter per sample: full
+    /// entropy needs at least 8 / `samples_per_byte` bits per sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `samples_per_byte` is 0.
+    pub fn with_samples_per_byte(samples_per_byte: usize) -> Self {
+        assert!(samples_per_byte > 0, "samples_per_byte must be non-zero");
+        Self {
+            samples_per_byte,
+            counter: 0,
+            block: [0; 32],
+            used: 32,
+        }
+    }
+
+    /// Timing samples hashed per output byte
+    pub fn samples_per_byte(&self) -> usize {
+        self.samples_per_byte
+    }
+
+    /// Fill `dest` with raw, unconditioned samples, one per byte
+    ///
+    /// These are what the hash is fed, so running them through the quality
+    /// metrics (min-entropy in particular) shows how much entropy the
+    /// platform's jitter actually carries.
+    pub fn fill_raw(&mut self, dest: &mut [u8]) {
+        for byte in dest.iter_mut() {
+            *byte = Self::sample();
+        }
+    }
+
+    /// Time one busy loop and fold the nanosecond delta into a byte
+    fn sample() -> u8 {
+        let start = Instant::now();
+        let mut acc = 0u64;
+        for i in 0..Self::LOOP_ITERATIONS {
+            acc = black_box(acc.rotate_left(7) ^ i);
+        }
+        black_box(acc);
+        let delta = start.elapsed().as_nanos() as u64;
+        delta.to_le_bytes().iter().fold(0, |folded, &b| folded ^ b)
+    }
+
+    fn refill(&mut self) {
+        let mut hasher = Sha256::new();
+        hasher.update(self.counter.to_le_bytes());
+        self.counter = self.counter.wrapping_add(1);
+
+        let mut raw = vec![0u8; self.samples_per_byte * self.block.len()];
+        self.fill_raw(&mut raw);
+        hasher.update(&raw);
+
+        self.block = hasher.finalize().into();
+        self.used = 0;
+    }
+}
+
+impl Default for JitterEntropy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EntropySource for JitterEntropy {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut written = 0;
+        while written < dest.len() {
+            if self.used == self.block.len() {
+                self.refill();
+            }
+            let n = (dest.len() - written).min(self.block.len() - self.used);
+            dest[written..written + n].copy_from_slice(&self.block[self.used..self.used + n]);
+            self.used += n;
+            written += n;
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Timing Jitter"
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        // Entropy content is unverified; see the type docs
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quality::NistTests;
+
+    #[test]
+    fn test_output_varies_and_passes_monobit() {
+        let mut source = JitterEntropy::with_samples_per_byte(8);
+        let mut data = vec![0u8; 20_000];
+        source.fill_bytes(&mut data[..5]);
+        source.fill_bytes(&mut data[5..]);
+
+        assert!(data.iter().any(|&b| b != data[0]), "constant output");
+        // Well below the 0.01 pass level, so an unlucky sample doesn't flake
+        assert!(NistTests::frequency_test(&data).unwrap() >= 1e-4);
+
+        assert_eq!(JitterEntropy::new().samples_per_byte(), JitterEntropy::DEFAULT_SAMPLES_PER_BYTE);
+    }
+}
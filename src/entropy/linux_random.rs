@@ -0,0 +1,129 @@
+//! Direct reads from Linux's `/dev/random`
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read};
+use std::os::unix::fs::OpenOptionsExt;
+use super::EntropySource;
+
+/// Reads `/dev/random` directly, optionally without blocking
+///
+/// [`SystemEntropy`](super::SystemEntropy) goes through `getrandom`, which
+/// is the right choice almost everywhere. This source is for callers that
+/// specifically want the `/dev/random` device, e.g. for high-assurance key
+/// generation policies that name it. Only available on Linux.
+///
+/// In blocking mode reads wait until the kernel considers its pool ready.
+/// Since Linux 5.6 that only happens once, early in boot; older kernels
+/// also block when their entropy estimate runs low. In non-blocking mode a
+/// read that would wait fails instead, surfaced as
+/// [`io::ErrorKind::WouldBlock`] by [`try_fill_bytes`](Self::try_fill_bytes).
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(target_os = "linux")]
+/// # fn main() -> std::io::Result<()> {
+/// use entropy_forge::entropy::{EntropySource, LinuxRandomEntropy};
+///
+/// let mut source = LinuxRandomEntropy::open(true)?;
+/// let mut key = [0u8; 32];
+/// source.try_fill_bytes(&mut key)?;
+/// assert_eq!(source.name(), "/dev/random (blocking)");
+/// # Ok(())
+/// # }
+/// # #[cfg(not(target_os = "linux"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+pub struct LinuxRandomEntropy {
+    file: File,
+    blocking: bool,
+}
+
+impl LinuxRandomEntropy {
+    /// Device this source reads from
+    pub const PATH: &str = "/dev/random";
+
+    /// Open `/dev/random`, in blocking or non-blocking mode
+    pub fn open(blocking: bool) -> io::Result<Self> {
+        let mut options = OpenOptions::new();
+        options.read(true);
+        if !blocking {
+            options.custom_flags(libc::O_NONBLOCK);
+        }
+        Ok(Self {
+            file: options.open(Self::PATH)?,
+            blocking,
+        })
+    }
+
+    /// Whether reads wait for the kernel pool
+    pub fn is_blocking(&self) -> bool {
+        self.blocking
+    }
+
+    /// Fill `dest`, reporting failures instead of panicking
+    ///
+    /// In non-blocking mode, fails with [`io::ErrorKind::WouldBlock`] when
+    /// the kernel has no bytes ready. Bytes read before an error are lost,
+    /// and `dest` may be partly overwritten.
+    pub fn try_fill_bytes(&mut self, dest: &mut [u8]) -> io::Result<()> {
+        // read_exact retries interrupted reads and passes WouldBlock through
+        self.file.read_exact(dest)
+    }
+}
+
+impl EntropySource for LinuxRandomEntropy {
+    /// # Panics
+    ///
+    /// Panics if the read fails, including a non-blocking read that would
+    /// block. Use [`try_fill_bytes`](LinuxRandomEntropy::try_fill_bytes)
+    /// to handle that.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("reading {} failed: {}", Self::PATH, e);
+        }
+    }
+
+    fn name(&self) -> &str {
+        if self.blocking {
+            "/dev/random (blocking)"
+        } else {
+            "/dev/random (non-blocking)"
+        }
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        // Same kernel CSPRNG as getrandom
+        Some(8.0)
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_varying_bytes() {
+        for blocking in [true, false] {
+            let mut source = LinuxRandomEntropy::open(blocking).unwrap();
+            assert_eq!(source.is_blocking(), blocking);
+
+            let mut buf = [0u8; 256];
+            match source.try_fill_bytes(&mut buf) {
+                Ok(()) => {}
+                // Only possible before the kernel pool is initialized
+                Err(e) if !blocking && e.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(e) => panic!("{}", e),
+            }
+            assert!(buf.iter().any(|&b| b != buf[0]), "constant output");
+        }
+
+        assert_eq!(LinuxRandomEntropy::open(true).unwrap().name(), "/dev/random (blocking)");
+        assert_eq!(LinuxRandomEntropy::open(false).unwrap().name(), "/dev/random (non-blocking)");
+    }
+}
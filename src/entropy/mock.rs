@@ -1,6 +1,6 @@
 //! Mock entropy source for testing
 
-use super::EntropySource;
+use super::{EntropySource, SeedableEntropy, SourceConfig};
 
 /// Mock entropy source for testing
 ///
@@ -32,11 +32,6 @@ impl MockEntropy {
         }
     }
     
-    /// Create with default seed (42)
-    pub fn default() -> Self {
-        Self::new(42)
-    }
-    
     // Simple LCG (Linear Congruential Generator)
     // Using glibc's constants
     fn next(&mut self) -> u8 {
@@ -45,6 +40,19 @@ impl MockEntropy {
     }
 }
 
+impl Default for MockEntropy {
+    /// Create with default seed (42)
+    fn default() -> Self {
+        Self::new(42)
+    }
+}
+
+impl SeedableEntropy for MockEntropy {
+    fn from_seed_u64(seed: u64) -> Self {
+        Self::new(seed)
+    }
+}
+
 impl EntropySource for MockEntropy {
     fn fill_bytes(&mut self, dest: &mut [u8]) {
         for byte in dest.iter_mut() {
@@ -59,6 +67,14 @@ impl EntropySource for MockEntropy {
     fn reset(&mut self) {
         self.state = self.initial_state;
     }
+    
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+    
+    fn source_config(&self) -> Option<SourceConfig> {
+        Some(SourceConfig::Mock { seed: self.initial_state })
+    }
 }
 
 #[cfg(test)]
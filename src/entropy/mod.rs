@@ -5,9 +5,53 @@
 
 mod system;
 mod mock;
+mod chunked;
+mod chacha;
+mod passphrase;
+mod config;
+mod dump;
+mod alphabet;
+mod biased;
+mod interleaved;
+mod named;
+mod rate_limited;
+mod recorded;
+mod xoshiro;
+mod pcg;
+mod closure;
+mod jitter;
+#[cfg(target_os = "linux")]
+mod linux_random;
+#[cfg(feature = "blake3")]
+mod blake3_xof;
+pub mod estimate;
+pub mod bits;
+pub mod drbg;
+pub mod extract;
 
-pub use system::SystemEntropy;
+use sha2::{Digest, Sha256};
+
+pub use system::{SystemBackend, SystemEntropy};
 pub use mock::MockEntropy;
+pub use chunked::ChunkedFill;
+pub use chacha::ChaChaEntropy;
+pub use passphrase::PassphraseEntropy;
+pub use biased::BiasedEntropy;
+pub use interleaved::InterleavedEntropy;
+pub use named::NamedEntropy;
+pub use rate_limited::RateLimitedEntropy;
+pub use recorded::RecordedEntropy;
+pub use xoshiro::{fast_rng, XoshiroEntropy};
+pub use pcg::Pcg64Entropy;
+pub use closure::FnEntropy;
+pub use jitter::JitterEntropy;
+#[cfg(target_os = "linux")]
+pub use linux_random::LinuxRandomEntropy;
+#[cfg(feature = "blake3")]
+pub use blake3_xof::Blake3Entropy;
+pub use config::{SourceConfig, SourceConfigError};
+pub use dump::{dump_gzip, dump_to_writer};
+pub use alphabet::{fill_from_alphabet, AlphabetError, MAX_CONSECUTIVE_REJECTIONS, PRINTABLE_ASCII};
 
 /// Core trait for entropy sources
 ///
@@ -79,6 +123,86 @@ pub trait EntropySource {
         u64::from_le_bytes(buf)
     }
     
+    /// Get a random u32 assembled big-endian
+    ///
+    /// Same bytes as [`next_u32`](Self::next_u32) would consume, but
+    /// interpreted with `from_be_bytes` for big-endian protocols.
+    fn next_u32_be(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_be_bytes(buf)
+    }
+    
+    /// Get a random u64 assembled big-endian
+    ///
+    /// Same bytes as [`next_u64`](Self::next_u64) would consume, but
+    /// interpreted with `from_be_bytes` for big-endian protocols.
+    fn next_u64_be(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_be_bytes(buf)
+    }
+    
+    /// Get a random fixed-size byte array, e.g. a key or nonce
+    ///
+    /// Default implementation uses `fill_bytes`, so it draws the same bytes
+    /// as filling an `[0u8; N]` buffer would. Not available on
+    /// `dyn EntropySource`; fill a buffer there instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::{EntropySource, SystemEntropy};
+    ///
+    /// let key: [u8; 32] = SystemEntropy::new().gen_array();
+    /// ```
+    fn gen_array<const N: usize>(&mut self) -> [u8; N]
+    where
+        Self: Sized,
+    {
+        let mut buf = [0u8; N];
+        self.fill_bytes(&mut buf);
+        buf
+    }
+    
+    /// Fold `f` over `total_bytes` bytes, generated `chunk` bytes at a time
+    ///
+    /// Computes a streaming statistic without holding the whole sample:
+    /// only one chunk (minimum 1 byte) is in memory at once. Every chunk
+    /// but the last has exactly `chunk` bytes. Sources that discard part of
+    /// a word per call, like [`ChaChaEntropy`], only produce the same bytes
+    /// as a single `fill_bytes` when `chunk` is a multiple of their word
+    /// size. Not available on `dyn EntropySource`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::{EntropySource, MockEntropy};
+    ///
+    /// // Count zero bytes in 10 MB using a 64 KB buffer
+    /// let zeros = MockEntropy::new(1).generate_fold(10_000_000, 65_536, 0usize, |n, chunk| {
+    ///     n + chunk.iter().filter(|&&b| b == 0).count()
+    /// });
+    /// assert!(zeros > 0);
+    /// ```
+    fn generate_fold<T, F: FnMut(T, &[u8]) -> T>(&mut self, total_bytes: usize, chunk: usize, init: T, mut f: F) -> T
+    where
+        Self: Sized,
+    {
+        let mut buffer = vec![0u8; chunk.max(1).min(total_bytes)];
+        let mut remaining = total_bytes;
+        let mut acc = init;
+        
+        while remaining > 0 {
+            let len = remaining.min(buffer.len());
+            self.fill_bytes(&mut buffer[..len]);
+            acc = f(acc, &buffer[..len]);
+            remaining -= len;
+        }
+        
+        acc
+    }
+    
     /// Get the source name for display purposes
     ///
     /// This is used in the GUI and logging to identify which entropy
@@ -91,9 +215,172 @@ pub trait EntropySource {
     ///
     /// Some sources may support resetting to a known state for testing.
     /// Default implementation does nothing.
+    ///
+    /// Sources that return `true` from [`is_deterministic`](Self::is_deterministic)
+    /// must reproduce exactly the same byte stream after `reset()` as they
+    /// produced from construction. Use [`assert_replays`] to check this.
     fn reset(&mut self) {
         // Default: no-op
     }
+    
+    /// Whether `reset()` replays the same stream
+    ///
+    /// Default is `false`. Seeded sources such as [`MockEntropy`] return
+    /// `true`; OS and hardware sources never can.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+    
+    /// Optional hint: expected entropy per output byte (0.0 to 8.0)
+    ///
+    /// Sources that know their quality can report it so callers can pick
+    /// sensible sample sizes or use it as a prior. Default is `None`
+    /// (unknown).
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        None
+    }
+    
+    /// Whether the source is suitable for cryptographic use
+    ///
+    /// Default is `false`. Only sources whose output is unpredictable to an
+    /// attacker (OS RNGs, CSPRNGs with a secret seed) should return `true`.
+    /// Used by [`StreamCipher::new_secure`](crate::crypto::StreamCipher::new_secure)
+    /// to refuse insecure sources.
+    fn is_cryptographically_secure(&self) -> bool {
+        false
+    }
+    
+    /// Configuration that rebuilds this source, if it has one
+    ///
+    /// Default is `None`. Sources that can be described by a
+    /// [`SourceConfig`] return it so reports can record how they were
+    /// produced.
+    fn source_config(&self) -> Option<SourceConfig> {
+        None
+    }
+    
+    /// Bytes left before a finite source runs dry
+    ///
+    /// Default is `None`, meaning unbounded. Finite sources such as
+    /// [`RecordedEntropy`] report how much real data is left so callers
+    /// can refuse oversized requests instead of reading filler.
+    fn remaining_bytes(&self) -> Option<u64> {
+        None
+    }
+    
+    /// Whether the source is deliberately slowed down
+    ///
+    /// Default is `false`. Throttling wrappers such as
+    /// [`RateLimitedEntropy`] return `true`, so callers can avoid blocking
+    /// on them for incidental draws like time estimates.
+    fn is_throttled(&self) -> bool {
+        false
+    }
+}
+
+/// Assert that a deterministic source replays its stream after `reset()`
+///
+/// Resets the source, draws a sample, resets again and checks the second
+/// sample matches. Intended for testing custom [`EntropySource`]
+/// implementations.
+///
+/// # Panics
+///
+/// Panics if the source does not report itself as deterministic or if the
+/// replayed bytes differ.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{assert_replays, MockEntropy};
+///
+/// assert_replays(&mut MockEntropy::new(7));
+/// ```
+pub fn assert_replays<E: ?Sized + EntropySource>(source: &mut E) {
+    assert!(source.is_deterministic(), "{} is not deterministic", source.name());
+    
+    let mut first = vec![0u8; 256];
+    let mut second = vec![0u8; 256];
+    
+    source.reset();
+    source.fill_bytes(&mut first);
+    source.reset();
+    source.fill_bytes(&mut second);
+    
+    assert_eq!(first, second, "{} did not replay its stream after reset()", source.name());
+}
+
+/// Deterministic entropy sources that can be constructed from a seed
+///
+/// This lets generic code build any seedable source uniformly, e.g. a test
+/// harness over `S: EntropySource + SeedableEntropy`. Sources without a
+/// reproducible seed, such as [`SystemEntropy`], do not implement it.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, MockEntropy, SeedableEntropy};
+///
+/// fn first_bytes<S: EntropySource + SeedableEntropy>(seed: &[u8]) -> [u8; 8] {
+///     let mut source = S::from_seed_bytes(seed);
+///     let mut buf = [0u8; 8];
+///     source.fill_bytes(&mut buf);
+///     buf
+/// }
+///
+/// assert_eq!(
+///     first_bytes::<MockEntropy>(b"seed"),
+///     first_bytes::<MockEntropy>(b"seed"),
+/// );
+/// ```
+pub trait SeedableEntropy: Sized {
+    /// Create the source from a 64-bit seed
+    fn from_seed_u64(seed: u64) -> Self;
+    
+    /// Create the source from arbitrary seed bytes
+    ///
+    /// Default implementation hashes the bytes with SHA-256 and uses the
+    /// first 8 bytes of the digest as a `u64` seed. Override if the source
+    /// can use more seed material directly.
+    fn from_seed_bytes(seed: &[u8]) -> Self {
+        let digest = Sha256::digest(seed);
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(&digest[..8]);
+        Self::from_seed_u64(u64::from_le_bytes(buf))
+    }
+}
+
+/// Deterministic entropy sources that can jump to any byte of their stream
+///
+/// Seeking lets several workers generate disjoint parts of one stream in
+/// parallel, each starting from a fresh source, as in
+/// [`PerformanceBench::benchmark_parallel`](crate::bench::PerformanceBench::benchmark_parallel).
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{ChaChaEntropy, EntropySource, SeekableEntropy};
+///
+/// let mut whole = [0u8; 64];
+/// ChaChaEntropy::new([3; 32]).fill_bytes(&mut whole);
+///
+/// let mut source = ChaChaEntropy::new([3; 32]);
+/// source.seek(32);
+/// let mut tail = [0u8; 32];
+/// source.fill_bytes(&mut tail);
+/// assert_eq!(tail, whole[32..]);
+/// ```
+pub trait SeekableEntropy: EntropySource {
+    /// Offsets that are a multiple of this seek exactly
+    ///
+    /// Sources that produce whole words round other offsets down.
+    const SEEK_ALIGNMENT: u64 = 1;
+    
+    /// Move to byte `offset` of the output stream
+    fn seek(&mut self, offset: u64);
+    
+    /// Current byte offset in the output stream
+    fn position(&self) -> u64;
 }
 
 // Blanket implementation for boxed trait objects
@@ -105,6 +392,34 @@ impl EntropySource for Box<dyn EntropySource> {
     fn name(&self) -> &str {
         (**self).name()
     }
+    
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+    
+    fn is_deterministic(&self) -> bool {
+        (**self).is_deterministic()
+    }
+    
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        (**self).estimated_entropy_per_byte()
+    }
+    
+    fn is_cryptographically_secure(&self) -> bool {
+        (**self).is_cryptographically_secure()
+    }
+    
+    fn source_config(&self) -> Option<SourceConfig> {
+        (**self).source_config()
+    }
+    
+    fn remaining_bytes(&self) -> Option<u64> {
+        (**self).remaining_bytes()
+    }
+    
+    fn is_throttled(&self) -> bool {
+        (**self).is_throttled()
+    }
 }
 
 // Blanket implementation for mutable references
@@ -116,6 +431,34 @@ impl<T: ?Sized + EntropySource> EntropySource for &mut T {
     fn name(&self) -> &str {
         (**self).name()
     }
+
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+
+    fn is_deterministic(&self) -> bool {
+        (**self).is_deterministic()
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        (**self).estimated_entropy_per_byte()
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        (**self).is_cryptographically_secure()
+    }
+
+    fn source_config(&self) -> Option<SourceConfig> {
+        (**self).source_config()
+    }
+
+    fn remaining_bytes(&self) -> Option<u64> {
+        (**self).remaining_bytes()
+    }
+
+    fn is_throttled(&self) -> bool {
+        (**self).is_throttled()
+    }
 }
 
 #[cfg(test)]
@@ -133,6 +476,7 @@ mod tests {
     }
     
     #[test]
+    #[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
     fn test_next_methods() {
         let mut entropy = SystemEntropy::new();
         
@@ -145,4 +489,126 @@ mod tests {
         assert!(u32_val <= u32::MAX);
         assert!(u64_val <= u64::MAX);
     }
+    
+    fn assert_seed_deterministic<S: EntropySource + SeedableEntropy>() {
+        let mut a = S::from_seed_bytes(b"entropy-forge");
+        let mut b = S::from_seed_bytes(b"entropy-forge");
+        let mut c = S::from_seed_u64(7);
+        let mut d = S::from_seed_u64(7);
+        
+        let mut buf_a = [0u8; 64];
+        let mut buf_b = [0u8; 64];
+        a.fill_bytes(&mut buf_a);
+        b.fill_bytes(&mut buf_b);
+        assert_eq!(buf_a, buf_b);
+        
+        c.fill_bytes(&mut buf_a);
+        d.fill_bytes(&mut buf_b);
+        assert_eq!(buf_a, buf_b);
+    }
+    
+    #[test]
+    fn test_seedable_sources() {
+        assert_seed_deterministic::<MockEntropy>();
+        assert_seed_deterministic::<ChaChaEntropy>();
+        assert_seed_deterministic::<XoshiroEntropy>();
+        assert_seed_deterministic::<Pcg64Entropy>();
+    }
+    
+    #[test]
+    fn test_is_deterministic() {
+        assert!(!SystemEntropy::new().is_deterministic());
+        assert!(MockEntropy::new(42).is_deterministic());
+        
+        let boxed: Box<dyn EntropySource> = Box::new(MockEntropy::new(42));
+        assert!(boxed.is_deterministic());
+    }
+    
+    #[test]
+    fn test_replay_contract() {
+        assert_replays(&mut MockEntropy::new(42));
+        
+        // Blanket impls must forward reset()
+        let mut boxed: Box<dyn EntropySource> = Box::new(MockEntropy::new(42));
+        assert_replays(&mut boxed);
+    }
+    
+    #[test]
+    #[should_panic(expected = "not deterministic")]
+    fn test_replay_rejects_system() {
+        assert_replays(&mut SystemEntropy::new());
+    }
+    
+    #[test]
+    fn test_entropy_per_byte_hint() {
+        assert_eq!(SystemEntropy::new().estimated_entropy_per_byte(), Some(8.0));
+        assert_eq!(MockEntropy::new(42).estimated_entropy_per_byte(), None);
+        
+        let boxed: Box<dyn EntropySource> = Box::new(SystemEntropy::new());
+        assert_eq!(boxed.estimated_entropy_per_byte(), Some(8.0));
+    }
+    
+    #[test]
+    fn test_big_endian_variants() {
+        let mut le = MockEntropy::new(42);
+        let mut be = MockEntropy::new(42);
+        
+        assert_eq!(le.next_u32(), be.next_u32_be().swap_bytes());
+        assert_eq!(le.next_u64(), be.next_u64_be().swap_bytes());
+    }
+    
+    #[test]
+    fn test_gen_array_matches_fill_bytes() {
+        let mut buf = [0u8; 32];
+        ChaChaEntropy::from_seed_u64(11).fill_bytes(&mut buf);
+        
+        let mut source = ChaChaEntropy::from_seed_u64(11);
+        let first: [u8; 16] = source.gen_array::<16>();
+        let second: [u8; 16] = source.gen_array();
+        assert_eq!(first, buf[..16]);
+        assert_eq!(second, buf[16..]);
+        assert_eq!(MockEntropy::new(1).gen_array::<0>(), [0u8; 0]);
+    }
+    
+    #[test]
+    fn test_generate_fold_matches_buffer() {
+        let mut buf = vec![0u8; 100_000];
+        ChaChaEntropy::from_seed_u64(3).fill_bytes(&mut buf);
+        let expected: u64 = buf.iter().map(|&b| u64::from(b)).sum();
+        
+        for chunk in [4, 4_096, 100_000, 1_000_000] {
+            let mut chunks = 0;
+            let sum = ChaChaEntropy::from_seed_u64(3).generate_fold(100_000, chunk, 0u64, |sum, bytes| {
+                chunks += 1;
+                sum + bytes.iter().map(|&b| u64::from(b)).sum::<u64>()
+            });
+            assert_eq!(sum, expected, "chunk {}", chunk);
+            assert_eq!(chunks, 100_000usize.div_ceil(chunk.clamp(1, 100_000)));
+        }
+        
+        // Byte-at-a-time chunks, from a source that doesn't care about call sizes
+        let mut recorded = RecordedEntropy::new(buf.clone());
+        assert_eq!(recorded.generate_fold(100_000, 0, 0u64, |sum, bytes| sum + u64::from(bytes[0])), expected);
+        
+        assert_eq!(MockEntropy::new(1).generate_fold(0, 16, 7, |n, _| n + 1), 7);
+    }
+    
+    #[test]
+    fn test_security_flags() {
+        assert!(SystemEntropy::new().is_cryptographically_secure());
+        assert!(ChaChaEntropy::new([0u8; 32]).is_cryptographically_secure());
+        
+        // A 64-bit seed is guessable, even after a reset
+        let mut weak = ChaChaEntropy::from_seed_u64(42);
+        assert!(!weak.is_cryptographically_secure());
+        weak.reset();
+        assert!(!weak.is_cryptographically_secure());
+        assert!(crate::crypto::StreamCipher::new_secure(weak).is_err());
+        assert!(!MockEntropy::new(42).is_cryptographically_secure());
+        assert!(!XoshiroEntropy::from_seed_u64(42).is_cryptographically_secure());
+        assert!(!Pcg64Entropy::from_seed_u64(42).is_cryptographically_secure());
+        
+        let boxed: Box<dyn EntropySource> = Box::new(MockEntropy::new(42));
+        assert!(!boxed.is_cryptographically_secure());
+    }
 }
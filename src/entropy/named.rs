@@ -0,0 +1,99 @@
+//! Wrapper that gives any source a custom display name
+
+use super::{EntropySource, SourceConfig};
+
+/// Labels a source with a custom name
+///
+/// `name()` returns the label; everything else, including the byte stream,
+/// is delegated to the inner source unchanged. Useful for keeping reports
+/// readable, e.g. "Customer A's HWRNG", or when composed wrappers would
+/// otherwise produce awkward names.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, NamedEntropy, SystemEntropy};
+///
+/// let source = NamedEntropy::with_name(SystemEntropy::new(), "Customer A's HWRNG");
+/// assert_eq!(source.name(), "Customer A's HWRNG");
+/// ```
+#[derive(Debug, Clone)]
+pub struct NamedEntropy<E: EntropySource> {
+    inner: E,
+    name: String,
+}
+
+impl<E: EntropySource> NamedEntropy<E> {
+    /// Wrap `inner`, reporting `name` from `name()`
+    pub fn with_name(inner: E, name: impl Into<String>) -> Self {
+        Self {
+            inner,
+            name: name.into(),
+        }
+    }
+
+    /// Unwrap and return the inner source
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E: EntropySource> EntropySource for NamedEntropy<E> {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner.fill_bytes(dest)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset()
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.inner.is_deterministic()
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        self.inner.estimated_entropy_per_byte()
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        self.inner.is_cryptographically_secure()
+    }
+
+    fn source_config(&self) -> Option<SourceConfig> {
+        self.inner.source_config()
+    }
+
+    fn remaining_bytes(&self) -> Option<u64> {
+        self.inner.remaining_bytes()
+    }
+
+    fn is_throttled(&self) -> bool {
+        self.inner.is_throttled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::MockEntropy;
+
+    #[test]
+    fn test_named_delegates_output() {
+        let mut named = NamedEntropy::with_name(MockEntropy::new(42), "Bench rig #2");
+        assert_eq!(named.name(), "Bench rig #2");
+
+        let mut expected = [0u8; 256];
+        MockEntropy::new(42).fill_bytes(&mut expected);
+        let mut actual = [0u8; 256];
+        named.fill_bytes(&mut actual);
+        assert_eq!(actual, expected);
+
+        assert!(named.is_deterministic());
+        assert_eq!(named.source_config(), MockEntropy::new(42).source_config());
+        assert_eq!(named.into_inner().name(), MockEntropy::new(42).name());
+    }
+}
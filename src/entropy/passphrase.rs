@@ -0,0 +1,172 @@
+//! Deterministic entropy source derived from a passphrase
+
+use sha2::{Digest, Sha256};
+use super::{ChaChaEntropy, EntropySource};
+use crate::crypto::{argon2id, pbkdf2_hmac_sha256, ARGON2_MEMORY_KIB, ARGON2_PASSES};
+
+/// Deterministic high-quality stream from a passphrase and salt
+///
+/// Derives a 32-byte seed with Argon2id ([`new`](Self::new)) or
+/// PBKDF2-HMAC-SHA256 ([`with_iterations`](Self::with_iterations)) and
+/// uses it to drive a [`ChaChaEntropy`] stream. The same passphrase and
+/// salt always reproduce the same bytes, which makes demos and test runs
+/// reproducible. Argon2id is memory-hard and the better choice for
+/// passphrases; PBKDF2 is much cheaper to compute, for quick demos.
+///
+/// [`is_cryptographically_secure`](EntropySource::is_cryptographically_secure)
+/// reports `false` for an empty passphrase or fewer than
+/// [`MIN_SECURE_ITERATIONS`](Self::MIN_SECURE_ITERATIONS) PBKDF2
+/// iterations. Otherwise it describes the construction only, since a
+/// passphrase's strength can't be judged here: a guessable passphrase
+/// still gives a guessable stream.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, PassphraseEntropy};
+///
+/// let mut a = PassphraseEntropy::with_iterations("correct horse", b"demo", 1_000);
+/// let mut b = PassphraseEntropy::with_iterations("correct horse", b"demo", 1_000);
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// ```
+#[derive(Clone)]
+pub struct PassphraseEntropy {
+    inner: ChaChaEntropy,
+    kdf: Kdf,
+    // Empty passphrase or too few KDF iterations
+    weak: bool,
+}
+
+/// How the seed was derived
+#[derive(Debug, Clone, Copy)]
+enum Kdf {
+    Argon2id,
+    Pbkdf2,
+}
+
+impl PassphraseEntropy {
+    /// Default PBKDF2 iteration count
+    pub const DEFAULT_ITERATIONS: u32 = 100_000;
+
+    /// Fewest PBKDF2 iterations reported as secure (NIST SP 800-63B)
+    pub const MIN_SECURE_ITERATIONS: u32 = 10_000;
+
+    /// Derive a source with Argon2id at the default costs
+    ///
+    /// Uses [`ARGON2_MEMORY_KIB`] of memory and [`ARGON2_PASSES`] passes.
+    /// The salt is hashed with SHA-256 first, so salts shorter than
+    /// Argon2's 8-byte minimum are accepted too.
+    pub fn new(passphrase: &str, salt: &[u8]) -> Self {
+        let mut seed = [0u8; 32];
+        argon2id(passphrase.as_bytes(), &Sha256::digest(salt), ARGON2_MEMORY_KIB, ARGON2_PASSES, &mut seed)
+            .expect("default Argon2id parameters with a 32-byte salt are valid");
+
+        Self {
+            inner: ChaChaEntropy::new(seed),
+            kdf: Kdf::Argon2id,
+            weak: passphrase.is_empty(),
+        }
+    }
+
+    /// Derive a source with PBKDF2-HMAC-SHA256 and an explicit iteration
+    /// count, such as [`DEFAULT_ITERATIONS`](Self::DEFAULT_ITERATIONS)
+    pub fn with_iterations(passphrase: &str, salt: &[u8], iterations: u32) -> Self {
+        let mut seed = [0u8; 32];
+        pbkdf2_hmac_sha256(passphrase.as_bytes(), salt, iterations, &mut seed);
+
+        Self {
+            inner: ChaChaEntropy::new(seed),
+            kdf: Kdf::Pbkdf2,
+            weak: passphrase.is_empty() || iterations < Self::MIN_SECURE_ITERATIONS,
+        }
+    }
+}
+
+impl std::fmt::Debug for PassphraseEntropy {
+    // Never print the derived seed
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PassphraseEntropy")
+            .field("kdf", &self.kdf)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EntropySource for PassphraseEntropy {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner.fill_bytes(dest);
+    }
+
+    fn name(&self) -> &str {
+        match self.kdf {
+            Kdf::Argon2id => "Passphrase (Argon2id → ChaCha20)",
+            Kdf::Pbkdf2 => "Passphrase (PBKDF2-HMAC-SHA256 → ChaCha20)",
+        }
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        // Output quality is high, but an attacker only has to guess the
+        // passphrase, so this is not a claim about real unpredictability
+        Some(8.0)
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        // Beyond the checks here, the passphrase is the caller's
+        // responsibility (see the type docs)
+        !self.weak
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passphrase_reproducible() {
+        let mut a = PassphraseEntropy::new("correct horse battery staple", b"salt-1");
+        let mut b = PassphraseEntropy::new("correct horse battery staple", b"salt-1");
+        let mut c = PassphraseEntropy::new("correct horse battery staple", b"salt-2");
+
+        let mut buf_a = [0u8; 64];
+        let mut buf_b = [0u8; 64];
+        let mut buf_c = [0u8; 64];
+        a.fill_bytes(&mut buf_a);
+        b.fill_bytes(&mut buf_b);
+        c.fill_bytes(&mut buf_c);
+
+        assert_eq!(buf_a, buf_b);
+        assert_ne!(buf_a, buf_c);
+
+        // PBKDF2 derives a different seed from the same inputs
+        let mut d = PassphraseEntropy::with_iterations("correct horse battery staple", b"salt-1", 1_000);
+        let mut buf_d = [0u8; 64];
+        d.fill_bytes(&mut buf_d);
+        assert_ne!(buf_a, buf_d);
+        assert_eq!(a.name(), "Passphrase (Argon2id → ChaCha20)");
+        assert_eq!(d.name(), "Passphrase (PBKDF2-HMAC-SHA256 → ChaCha20)");
+    }
+
+    #[test]
+    fn test_weak_inputs_are_insecure() {
+        assert!(PassphraseEntropy::new("hunter2", b"salt").is_cryptographically_secure());
+        assert!(!PassphraseEntropy::new("", b"salt").is_cryptographically_secure());
+
+        let min = PassphraseEntropy::MIN_SECURE_ITERATIONS;
+        assert!(PassphraseEntropy::with_iterations("hunter2", b"salt", min).is_cryptographically_secure());
+        assert!(!PassphraseEntropy::with_iterations("hunter2", b"salt", min - 1).is_cryptographically_secure());
+        assert!(!PassphraseEntropy::with_iterations("", b"salt", min).is_cryptographically_secure());
+    }
+
+    #[test]
+    fn test_debug_hides_seed() {
+        let source = PassphraseEntropy::with_iterations("hunter2", b"salt", 1_000);
+        assert_eq!(format!("{:?}", source), "PassphraseEntropy { kdf: Pbkdf2, .. }");
+    }
+}
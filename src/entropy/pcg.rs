@@ -0,0 +1,124 @@
+//! PCG64 source for reproducible simulations
+
+use super::{EntropySource, SeedableEntropy};
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_pcg::Pcg64;
+use sha2::{Digest, Sha256};
+
+/// PCG64 (XSL 128/64) generator: fast, statistically strong, NOT secure
+///
+/// The generator behind `rand_pcg::Pcg64`, with a 128-bit state and a
+/// selectable stream. Like [`XoshiroEntropy`](super::XoshiroEntropy) it
+/// suits simulations and Monte Carlo work, and its state can be recovered
+/// from its output, so
+/// [`is_cryptographically_secure`](EntropySource::is_cryptographically_secure)
+/// returns `false`. Use it where results must match other PCG64
+/// implementations.
+///
+/// [`from_seed_bytes`](SeedableEntropy::from_seed_bytes) hashes the seed
+/// into the full 256 bits of state and stream; 64-bit seeds are expanded
+/// the way `rand_core` does for every `SeedableRng`.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, Pcg64Entropy, SeedableEntropy};
+///
+/// let mut a = Pcg64Entropy::from_seed_u64(7);
+/// let mut b = Pcg64Entropy::from_seed_u64(7);
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// assert!(!a.is_cryptographically_secure());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pcg64Entropy {
+    rng: Pcg64,
+    initial: Pcg64,
+}
+
+impl Pcg64Entropy {
+    /// Create a source from a raw state and stream selector
+    ///
+    /// Matches the reference `pcg64` seeding, so the output can be checked
+    /// against the PCG test vectors.
+    pub fn new(state: u128, stream: u128) -> Self {
+        Self::from_rng(Pcg64::new(state, stream))
+    }
+
+    fn from_rng(rng: Pcg64) -> Self {
+        Self { initial: rng.clone(), rng }
+    }
+}
+
+impl SeedableEntropy for Pcg64Entropy {
+    fn from_seed_u64(seed: u64) -> Self {
+        Self::from_rng(Pcg64::seed_from_u64(seed))
+    }
+
+    fn from_seed_bytes(seed: &[u8]) -> Self {
+        Self::from_rng(Pcg64::from_seed(Sha256::digest(seed).into()))
+    }
+}
+
+impl EntropySource for Pcg64Entropy {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn name(&self) -> &str {
+        "PCG64 (fast, not secure)"
+    }
+
+    fn reset(&mut self) {
+        self.rng = self.initial.clone();
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        Some(8.0)
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::assert_replays;
+    use crate::quality::QualityMetrics;
+
+    #[test]
+    fn test_pcg64_reference_vector() {
+        // From the official PCG test suite, state 42, stream 54
+        let mut rng = Pcg64Entropy::new(42, 54);
+        for expected in [0x86b1da1d72062b68, 0x1304aa46c9853d39, 0xa3670e9e0dd50358] {
+            assert_eq!(rng.next_u64(), expected);
+        }
+
+        // fill_bytes is the little-endian output stream
+        let mut rng = Pcg64Entropy::new(42, 54);
+        let mut buf = [0u8; 8];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, 0x86b1da1d72062b68u64.to_le_bytes());
+    }
+
+    #[test]
+    fn test_pcg64_quality_and_flags() {
+        let mut rng = Pcg64Entropy::from_seed_u64(2024);
+        assert!(!rng.is_cryptographically_secure());
+        assert!(rng.is_deterministic());
+        assert_replays(&mut rng);
+
+        let metrics = QualityMetrics::analyze(&mut rng, 100_000);
+        assert!(metrics.chi_square_p_value > 0.01);
+        assert!(metrics.shannon_entropy > 7.99);
+    }
+}
@@ -0,0 +1,145 @@
+//! Wrapper that throttles a source to a fixed byte rate
+
+use std::time::{Duration, Instant};
+use super::{EntropySource, SourceConfig};
+
+/// Caps a source's throughput by sleeping in `fill_bytes`
+///
+/// Simulates a slow hardware TRNG, e.g. to exercise timeout handling or
+/// [`PerformanceBench::benchmark_duration`](crate::bench::PerformanceBench::benchmark_duration)
+/// against a source whose rate is known in advance. Each call is charged
+/// `dest.len() / rate` seconds and returns no earlier than the previous
+/// call's deadline plus that charge, so the long-run rate never exceeds the
+/// limit. Time spent idle between calls is not banked: a caller that pauses
+/// does not get a burst afterwards.
+///
+/// The bytes themselves are delegated to the inner source unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, MockEntropy, RateLimitedEntropy};
+///
+/// // 1,000 bytes per second: 10 bytes take about 10 ms
+/// let mut slow = RateLimitedEntropy::with_bytes_per_second(MockEntropy::new(1), 1_000.0);
+/// let mut buf = [0u8; 10];
+/// slow.fill_bytes(&mut buf);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimitedEntropy<E: EntropySource> {
+    inner: E,
+    bytes_per_second: f64,
+    // When the bytes delivered so far are "paid for"; None before the first call
+    next_free: Option<Instant>,
+}
+
+impl<E: EntropySource> RateLimitedEntropy<E> {
+    /// Wrap `inner`, delivering at most `rate` bytes per second
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is not a positive, finite number.
+    pub fn with_bytes_per_second(inner: E, rate: f64) -> Self {
+        assert!(rate.is_finite() && rate > 0.0, "rate must be positive and finite, got {}", rate);
+        Self {
+            inner,
+            bytes_per_second: rate,
+            next_free: None,
+        }
+    }
+
+    /// The configured limit in bytes per second
+    pub fn bytes_per_second(&self) -> f64 {
+        self.bytes_per_second
+    }
+
+    /// Unwrap and return the inner source
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E: EntropySource> EntropySource for RateLimitedEntropy<E> {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let now = Instant::now();
+        let begin = self.next_free.map_or(now, |t| t.max(now));
+        let due = begin + Duration::from_secs_f64(dest.len() as f64 / self.bytes_per_second);
+        self.next_free = Some(due);
+
+        self.inner.fill_bytes(dest);
+
+        let now = Instant::now();
+        if due > now {
+            std::thread::sleep(due - now);
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    /// Reset the inner source and forget any outstanding pacing debt
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.next_free = None;
+    }
+
+    fn is_deterministic(&self) -> bool {
+        self.inner.is_deterministic()
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        self.inner.estimated_entropy_per_byte()
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        self.inner.is_cryptographically_secure()
+    }
+
+    fn source_config(&self) -> Option<SourceConfig> {
+        self.inner.source_config()
+    }
+
+    fn remaining_bytes(&self) -> Option<u64> {
+        self.inner.remaining_bytes()
+    }
+
+    fn is_throttled(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::PerformanceBench;
+    use crate::entropy::MockEntropy;
+
+    #[test]
+    fn test_throughput_matches_rate() {
+        let rate = 200_000.0;
+        let mut slow = RateLimitedEntropy::with_bytes_per_second(MockEntropy::new(3), rate);
+
+        // 100 ms each; sleeps only overshoot, so allow more slack below
+        let result = PerformanceBench::benchmark(&mut slow, 20_000);
+        let measured = result.throughput_mbps * 1_000_000.0;
+        assert!(measured > rate * 0.7 && measured < rate * 1.05, "{} B/s", measured);
+
+        let result = PerformanceBench::benchmark_duration(&mut slow, Duration::from_millis(100), 1_000);
+        let measured = result.throughput_mbps * 1_000_000.0;
+        assert!(measured > rate * 0.7 && measured < rate * 1.05, "{} B/s", measured);
+    }
+
+    #[test]
+    fn test_delegates_output() {
+        let mut slow = RateLimitedEntropy::with_bytes_per_second(MockEntropy::new(42), 1e9);
+        let mut expected = [0u8; 256];
+        MockEntropy::new(42).fill_bytes(&mut expected);
+        let mut actual = [0u8; 256];
+        slow.fill_bytes(&mut actual);
+        assert_eq!(actual, expected);
+
+        assert_eq!(slow.name(), MockEntropy::new(42).name());
+        assert_eq!(slow.bytes_per_second(), 1e9);
+    }
+}
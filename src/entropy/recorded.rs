@@ -0,0 +1,165 @@
+//! Replay of previously captured entropy
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+use flate2::read::GzDecoder;
+use super::EntropySource;
+
+/// Replays a fixed buffer of captured bytes
+///
+/// Use it to run the framework on output from another tool or device.
+/// The source is finite: once the recording is used up, further reads
+/// return zeros, so check [`remaining_bytes`](EntropySource::remaining_bytes)
+/// (or use [`QualityMetrics::try_analyze`](crate::quality::QualityMetrics::try_analyze))
+/// before drawing large samples. `reset()` rewinds to the start.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, RecordedEntropy};
+///
+/// let mut source = RecordedEntropy::new(vec![1, 2, 3, 4]);
+/// let mut buf = [0u8; 3];
+/// source.fill_bytes(&mut buf);
+///
+/// assert_eq!(buf, [1, 2, 3]);
+/// assert_eq!(source.remaining_bytes(), Some(1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecordedEntropy {
+    data: Vec<u8>,
+    position: usize,
+}
+
+impl RecordedEntropy {
+    /// Replay `data` from the start
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// Load a raw binary capture from a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(fs::read(path)?))
+    }
+
+    /// Load a gzip-compressed capture, as written by [`dump_gzip`](super::dump_gzip)
+    ///
+    /// Fails with an I/O error if the file is not valid gzip.
+    pub fn from_gzip_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut data = Vec::new();
+        GzDecoder::new(BufReader::new(File::open(path)?)).read_to_end(&mut data)?;
+        Ok(Self::new(data))
+    }
+
+    /// Total size of the recording in bytes
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the recording is empty
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+impl EntropySource for RecordedEntropy {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let available = (self.data.len() - self.position).min(dest.len());
+        let (recorded, exhausted) = dest.split_at_mut(available);
+
+        recorded.copy_from_slice(&self.data[self.position..self.position + available]);
+        if !exhausted.is_empty() {
+            log::debug!("recording exhausted; zero-filling {} bytes", exhausted.len());
+            exhausted.fill(0);
+        }
+        self.position += available;
+    }
+
+    fn name(&self) -> &str {
+        "Recorded"
+    }
+
+    fn reset(&mut self) {
+        self.position = 0;
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    fn remaining_bytes(&self) -> Option<u64> {
+        Some((self.data.len() - self.position) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{assert_replays, dump_gzip, ChaChaEntropy};
+    use crate::quality::{AnalyzeError, QualityMetrics};
+
+    #[test]
+    fn test_remaining_bytes_counts_down() {
+        let data: Vec<u8> = (0..100).collect();
+        let mut source = RecordedEntropy::new(data.clone());
+        assert_eq!(source.remaining_bytes(), Some(100));
+
+        let mut buf = [0u8; 30];
+        source.fill_bytes(&mut buf);
+        assert_eq!(buf[..], data[..30]);
+        assert_eq!(source.remaining_bytes(), Some(70));
+
+        // Reading past the end yields zeros
+        let mut tail = [0xAAu8; 80];
+        source.fill_bytes(&mut tail);
+        assert_eq!(tail[..70], data[30..]);
+        assert!(tail[70..].iter().all(|&b| b == 0));
+        assert_eq!(source.remaining_bytes(), Some(0));
+
+        assert_replays(&mut source);
+    }
+
+    #[test]
+    fn test_analyze_refuses_oversized_request() {
+        let mut source = RecordedEntropy::new((0..100).collect());
+
+        assert_eq!(
+            QualityMetrics::try_analyze(&mut source, 101),
+            Err(AnalyzeError::InsufficientData { requested: 101, available: 100 })
+        );
+        assert_eq!(source.remaining_bytes(), Some(100));
+
+        let metrics = QualityMetrics::try_analyze(&mut source, 100).unwrap();
+        assert_eq!(metrics.total_bytes, 100);
+        assert_eq!(source.remaining_bytes(), Some(0));
+    }
+
+    #[test]
+    fn test_gzip_round_trip() {
+        let path = std::env::temp_dir().join(format!("entropy-forge-gzip-{}.bin.gz", std::process::id()));
+        let written = dump_gzip(&mut ChaChaEntropy::new([7; 32]), 50_000, 4_096, File::create(&path).unwrap()).unwrap();
+        assert_eq!(written, 50_000);
+
+        let mut expected = vec![0u8; 50_000];
+        ChaChaEntropy::new([7; 32]).fill_bytes(&mut expected);
+
+        let mut source = RecordedEntropy::from_gzip_file(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(source.len(), expected.len());
+
+        let mut loaded = vec![0u8; 50_000];
+        source.fill_bytes(&mut loaded);
+        assert_eq!(loaded, expected);
+        assert_eq!(QualityMetrics::from_bytes(&loaded), QualityMetrics::from_bytes(&expected));
+    }
+
+    #[test]
+    fn test_gzip_rejects_plain_file() {
+        let path = std::env::temp_dir().join(format!("entropy-forge-plain-{}.bin", std::process::id()));
+        fs::write(&path, [0x5Au8; 64]).unwrap();
+        let result = RecordedEntropy::from_gzip_file(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+}
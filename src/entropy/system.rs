@@ -1,6 +1,6 @@
 //! System entropy source using OS random number generator
 
-use super::EntropySource;
+use super::{ChaChaEntropy, EntropySource, SeedableEntropy, SourceConfig};
 use getrandom::getrandom;
 
 /// System entropy source (uses OS RNG)
@@ -23,33 +23,117 @@ use getrandom::getrandom;
 /// let mut buffer = [0u8; 32];
 /// entropy.fill_bytes(&mut buffer);
 /// ```
-#[derive(Debug, Default, Clone)]
-pub struct SystemEntropy;
+#[derive(Debug, Clone)]
+pub struct SystemEntropy {
+    backend: SystemBackend,
+    stream: Option<ChaChaEntropy>,
+    name: String,
+}
+
+/// Where [`SystemEntropy`] draws its bytes from
+///
+/// `Os` is the default and the only backend production code should use.
+/// `Deterministic` swaps in a seeded ChaCha20 stream so tests that take a
+/// "system" source can be made reproducible.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SystemBackend {
+    /// The operating system's CSPRNG
+    #[default]
+    Os,
+    
+    /// A ChaCha20 stream seeded like [`ChaChaEntropy::from_seed_u64`]
+    Deterministic(u64),
+}
 
 impl SystemEntropy {
     /// Create a new system entropy source
     pub fn new() -> Self {
-        Self
+        Self::with_backend(SystemBackend::Os)
+    }
+    
+    /// Create a source drawing from the given backend
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::{EntropySource, SystemBackend, SystemEntropy};
+    ///
+    /// let mut a = SystemEntropy::with_backend(SystemBackend::Deterministic(7));
+    /// let mut b = SystemEntropy::with_backend(SystemBackend::Deterministic(7));
+    /// assert_eq!(a.next_u64(), b.next_u64());
+    /// ```
+    pub fn with_backend(backend: SystemBackend) -> Self {
+        let (stream, name) = match backend {
+            SystemBackend::Os => (None, OS_RNG_NAME.to_string()),
+            SystemBackend::Deterministic(seed) => (
+                Some(ChaChaEntropy::from_seed_u64(seed)),
+                format!("System RNG (deterministic test backend, seed {})", seed),
+            ),
+        };
+        Self { backend, stream, name }
+    }
+    
+    /// The backend this source draws from
+    pub fn backend(&self) -> SystemBackend {
+        self.backend
     }
 }
 
+impl Default for SystemEntropy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(target_os = "linux")]
+const OS_RNG_NAME: &str = "System RNG (Linux /dev/urandom)";
+
+#[cfg(target_os = "macos")]
+const OS_RNG_NAME: &str = "System RNG (macOS SecRandomCopyBytes)";
+
+#[cfg(target_os = "windows")]
+const OS_RNG_NAME: &str = "System RNG (Windows BCryptGenRandom)";
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+const OS_RNG_NAME: &str = "System RNG";
+
 impl EntropySource for SystemEntropy {
     fn fill_bytes(&mut self, dest: &mut [u8]) {
-        getrandom(dest).expect("Failed to get system entropy - this should never happen on supported platforms");
+        match &mut self.stream {
+            Some(stream) => stream.fill_bytes(dest),
+            None => getrandom(dest).expect("Failed to get system entropy - this should never happen on supported platforms"),
+        }
     }
     
     fn name(&self) -> &str {
-        #[cfg(target_os = "linux")]
-        return "System RNG (Linux /dev/urandom)";
-        
-        #[cfg(target_os = "macos")]
-        return "System RNG (macOS SecRandomCopyBytes)";
-        
-        #[cfg(target_os = "windows")]
-        return "System RNG (Windows BCryptGenRandom)";
-        
-        #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
-        return "System RNG";
+        &self.name
+    }
+    
+    fn reset(&mut self) {
+        if let Some(stream) = &mut self.stream {
+            stream.reset();
+        }
+    }
+    
+    fn is_deterministic(&self) -> bool {
+        self.stream.is_some()
+    }
+    
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        // OS CSPRNG output is computationally indistinguishable from uniform
+        Some(8.0)
+    }
+    
+    fn is_cryptographically_secure(&self) -> bool {
+        // A 64-bit test seed is guessable
+        self.stream.is_none()
+    }
+    
+    fn source_config(&self) -> Option<SourceConfig> {
+        match &self.stream {
+            Some(stream) => stream.source_config(),
+            None => Some(SourceConfig::System),
+        }
     }
 }
 
@@ -86,10 +170,41 @@ mod tests {
         assert!(zeros < expected_zeros * 2);
     }
     
+    #[test]
+    fn test_deterministic_backend_reproduces() {
+        let backend = SystemBackend::Deterministic(42);
+        let mut a = SystemEntropy::with_backend(backend);
+        let mut b = SystemEntropy::with_backend(backend);
+        
+        let mut buf_a = [0u8; 64];
+        let mut buf_b = [0u8; 64];
+        a.fill_bytes(&mut buf_a);
+        b.fill_bytes(&mut buf_b);
+        assert_eq!(buf_a, buf_b);
+        
+        assert!(a.is_deterministic());
+        assert!(!a.is_cryptographically_secure());
+        assert_eq!(a.backend(), backend);
+        crate::entropy::assert_replays(&mut a);
+        
+        let mut other = SystemEntropy::with_backend(SystemBackend::Deterministic(43));
+        let mut buf_other = [0u8; 64];
+        other.fill_bytes(&mut buf_other);
+        assert_ne!(buf_a, buf_other);
+        
+        assert_eq!(SystemEntropy::new().backend(), SystemBackend::Os);
+        assert!(!SystemEntropy::default().is_deterministic());
+    }
+    
     #[test]
     fn test_name() {
         let entropy = SystemEntropy::new();
         let name = entropy.name();
         assert!(name.contains("System RNG"));
+        assert_eq!(SystemEntropy::default().name(), name);
+        
+        // A test backend must not pass for the real OS RNG in reports
+        let test_backend = SystemEntropy::with_backend(SystemBackend::Deterministic(42));
+        assert_eq!(test_backend.name(), "System RNG (deterministic test backend, seed 42)");
     }
 }
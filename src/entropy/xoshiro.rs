@@ -0,0 +1,175 @@
+//! Fast non-cryptographic source for simulations
+
+use super::{EntropySource, SeedableEntropy};
+
+/// xoshiro256++ generator: fast, statistically strong, NOT secure
+///
+/// Passes the statistical tests and is faster than the ChaCha-based
+/// sources, which makes it the right choice for simulations and Monte
+/// Carlo work. Its state can be recovered from a few outputs, so
+/// never use it for keys, nonces or anything an adversary could predict;
+/// [`is_cryptographically_secure`](EntropySource::is_cryptographically_secure)
+/// returns `false`. Prefer it over [`MockEntropy`](super::MockEntropy),
+/// which is a weak LCG meant only as a test fixture.
+///
+/// The 64-bit seed is expanded into the 256-bit state with SplitMix64, as
+/// recommended by the xoshiro authors. See [`fast_rng`].
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, SeedableEntropy, XoshiroEntropy};
+///
+/// let mut a = XoshiroEntropy::from_seed_u64(7);
+/// let mut b = XoshiroEntropy::from_seed_u64(7);
+/// assert_eq!(a.next_u64(), b.next_u64());
+/// assert!(!a.is_cryptographically_secure());
+/// ```
+#[derive(Debug, Clone)]
+pub struct XoshiroEntropy {
+    state: [u64; 4],
+    initial_state: [u64; 4],
+}
+
+impl XoshiroEntropy {
+    /// Create a source from a raw 256-bit state
+    ///
+    /// The state must not be all zeros; an all-zero state is replaced by
+    /// the SplitMix64 expansion of seed 0.
+    pub fn from_state(state: [u64; 4]) -> Self {
+        if state == [0; 4] {
+            return Self::from_seed_u64(0);
+        }
+        Self { state, initial_state: state }
+    }
+
+    fn step(&mut self) -> u64 {
+        let [s0, s1, s2, s3] = &mut self.state;
+        let result = s0.wrapping_add(*s3).rotate_left(23).wrapping_add(*s0);
+        let t = *s1 << 17;
+
+        *s2 ^= *s0;
+        *s3 ^= *s1;
+        *s1 ^= *s2;
+        *s0 ^= *s3;
+        *s2 ^= t;
+        *s3 = s3.rotate_left(45);
+
+        result
+    }
+}
+
+impl SeedableEntropy for XoshiroEntropy {
+    fn from_seed_u64(seed: u64) -> Self {
+        // SplitMix64 never yields four zero words in a row
+        let mut x = seed;
+        let mut splitmix = || {
+            x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        let state = [splitmix(), splitmix(), splitmix(), splitmix()];
+        Self { state, initial_state: state }
+    }
+}
+
+impl EntropySource for XoshiroEntropy {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut chunks = dest.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.step().to_le_bytes());
+        }
+
+        let tail = chunks.into_remainder();
+        if !tail.is_empty() {
+            let len = tail.len();
+            tail.copy_from_slice(&self.step().to_le_bytes()[..len]);
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.step()
+    }
+
+    fn name(&self) -> &str {
+        "xoshiro256++ (fast, not secure)"
+    }
+
+    fn reset(&mut self) {
+        self.state = self.initial_state;
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    fn estimated_entropy_per_byte(&self) -> Option<f64> {
+        Some(8.0)
+    }
+
+    fn is_cryptographically_secure(&self) -> bool {
+        false
+    }
+}
+
+/// Fast, seeded, non-cryptographic source for simulations
+///
+/// The default choice when speed matters and security does not. Currently
+/// an [`XoshiroEntropy`]; the concrete type may change, so only the traits
+/// are promised.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{fast_rng, EntropySource};
+///
+/// let mut rng = fast_rng(42);
+/// let roll = rng.next_u32() % 6 + 1;
+/// assert!((1..=6).contains(&roll));
+/// ```
+pub fn fast_rng(seed: u64) -> impl EntropySource + SeedableEntropy {
+    XoshiroEntropy::from_seed_u64(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::assert_replays;
+    use crate::quality::QualityMetrics;
+
+    #[test]
+    fn test_xoshiro_reference_vector() {
+        // First outputs of xoshiro256++ from state [1, 2, 3, 4]
+        let mut rng = XoshiroEntropy::from_state([1, 2, 3, 4]);
+        for expected in [41943041, 58720359, 3588806011781223, 3591011842654386] {
+            assert_eq!(rng.next_u64(), expected);
+        }
+
+        // fill_bytes is the little-endian output stream, tail truncated
+        let mut rng = XoshiroEntropy::from_state([1, 2, 3, 4]);
+        let mut buf = [0u8; 11];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf[..8], 41943041u64.to_le_bytes());
+        assert_eq!(buf[8..], 58720359u64.to_le_bytes()[..3]);
+    }
+
+    #[test]
+    fn test_fast_rng_quality_and_flags() {
+        let mut rng = fast_rng(2024);
+        assert!(!rng.is_cryptographically_secure());
+        assert!(rng.is_deterministic());
+        assert_replays(&mut rng);
+
+        let metrics = QualityMetrics::analyze(&mut rng, 100_000);
+        assert!(metrics.chi_square_p_value > 0.01);
+        assert!(metrics.shannon_entropy > 7.99);
+    }
+
+    #[test]
+    fn test_zero_state_is_replaced() {
+        let mut rng = XoshiroEntropy::from_state([0; 4]);
+        assert_ne!(rng.next_u64(), 0);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::quality::{BitOrder, NistTests};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlockFreqStepType {
+    ConvertToBits,
+    SplitIntoBlocks,
+    CalculateProportions,
+    AccumulateChiSquare,
+    CalculatePValue,
+    Interpret,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlockFrequencyStep {
+    pub step_type: BlockFreqStepType,
+    pub bits: Vec<u8>, // 0 or 1
+    pub block_size: usize,
+    pub proportions: Vec<f64>, // ones-proportion per block
+    pub running_chi_square: Vec<f64>, // χ² after each block
+    pub chi_square: f64,
+    pub p_value: f64,
+    pub passed: bool,
+}
+
+pub struct BlockFrequencyProcess {
+    pub input_text: String,
+    pub block_size: usize,
+    pub steps: Vec<BlockFrequencyStep>,
+    pub current_step_index: usize,
+    pub is_playing: bool,
+    pub speed: f32,
+    pub last_update: f64,
+}
+
+impl Default for BlockFrequencyProcess {
+    fn default() -> Self {
+        Self {
+            input_text: String::new(),
+            block_size: 8,
+            steps: Vec::new(),
+            current_step_index: 0,
+            is_playing: false,
+            speed: 1.0,
+            last_update: 0.0,
+        }
+    }
+}
+
+impl BlockFrequencyProcess {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start(&mut self, text: &str) {
+        self.input_text = text.to_string();
+        self.steps.clear();
+        self.current_step_index = 0;
+        self.is_playing = false;
+
+        let data = text.as_bytes();
+        if data.is_empty() || self.block_size == 0 {
+            return;
+        }
+
+        let block_size = self.block_size;
+        let mut step = BlockFrequencyStep {
+            step_type: BlockFreqStepType::ConvertToBits,
+            bits: BitOrder::MsbFirst.extract(data),
+            block_size,
+            proportions: Vec::new(),
+            running_chi_square: Vec::new(),
+            chi_square: 0.0,
+            p_value: 0.0,
+            passed: false,
+        };
+
+        // --- Step 1: Convert to Bits ---
+        self.steps.push(step.clone());
+
+        // --- Step 2: Split into Blocks (leftover bits are dropped) ---
+        step.step_type = BlockFreqStepType::SplitIntoBlocks;
+        self.steps.push(step.clone());
+
+        // --- Step 3: Proportion of ones in each block ---
+        step.step_type = BlockFreqStepType::CalculateProportions;
+        step.proportions = step.bits
+            .chunks_exact(block_size)
+            .map(|block| block.iter().filter(|&&b| b == 1).count() as f64 / block_size as f64)
+            .collect();
+        self.steps.push(step.clone());
+
+        // --- Step 4: Accumulate χ² = 4M Σ (π_i - 1/2)² ---
+        step.step_type = BlockFreqStepType::AccumulateChiSquare;
+        let mut chi_square = 0.0;
+        for &pi in &step.proportions {
+            chi_square += 4.0 * block_size as f64 * (pi - 0.5) * (pi - 0.5);
+            step.running_chi_square.push(chi_square);
+        }
+        step.chi_square = chi_square;
+        self.steps.push(step.clone());
+
+        // --- Step 5: P-Value, from the library so the visual matches it ---
+        step.step_type = BlockFreqStepType::CalculatePValue;
+        // Text shorter than one block has no p-value; show it as a failure
+        step.p_value = NistTests::block_frequency_test_bits(&step.bits, block_size).unwrap_or(0.0);
+        self.steps.push(step.clone());
+
+        // --- Step 6: Interpretation ---
+        step.step_type = BlockFreqStepType::Interpret;
+        step.passed = step.p_value >= 0.01;
+        self.steps.push(step);
+    }
+
+    pub fn current_step(&self) -> Option<&BlockFrequencyStep> {
+        self.steps.get(self.current_step_index)
+    }
+
+    pub fn next_step(&mut self) {
+        if self.current_step_index + 1 < self.steps.len() {
+            self.current_step_index += 1;
+        } else {
+            self.is_playing = false;
+        }
+    }
+
+    pub fn prev_step(&mut self) {
+        if self.current_step_index > 0 {
+            self.current_step_index -= 1;
+        }
+    }
+
+    pub fn toggle_play(&mut self) {
+        self.is_playing = !self.is_playing;
+    }
+
+    pub fn update(&mut self, time: f64) {
+        if self.is_playing {
+            if time - self.last_update > (1.0 / self.speed as f64) {
+                self.next_step();
+                self.last_update = time;
+            }
+        } else {
+            self.last_update = time;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_cells() {
+        let mut process = BlockFrequencyProcess::new();
+
+        // "Hello" is 40 bits: five 8-bit blocks
+        process.start("Hello");
+        let last = process.steps.last().unwrap();
+        assert_eq!(last.proportions.len(), 5);
+        assert_eq!(last.running_chi_square.len(), 5);
+
+        // 16-bit blocks leave 8 bits over, which are dropped
+        process.block_size = 16;
+        process.start("Hello");
+        let last = process.steps.last().unwrap();
+        assert_eq!(last.proportions.len(), 2);
+        assert_eq!(Some(last.p_value), NistTests::block_frequency_test_bits(&last.bits, 16));
+        assert!((last.chi_square - last.running_chi_square[1]).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,124 @@
+//! "Spot the pattern": pick the biased sample out of a pair
+
+use crate::entropy::{BiasedEntropy, EntropySource};
+use crate::quality::{NistSummary, NistTests};
+
+/// One of the two byte grids in a round
+#[derive(Debug, Clone)]
+pub struct ChallengeSample {
+    /// The sample, one grid cell per byte
+    pub bytes: Vec<u8>,
+    /// Whether it came from the biased source
+    pub biased: bool,
+    /// NIST verdict, revealed after the guess
+    pub summary: NistSummary,
+}
+
+impl ChallengeSample {
+    fn new(bytes: Vec<u8>, biased: bool) -> Self {
+        let summary = NistSummary::from_results(NistTests::run_all_tests(&bytes));
+        Self { bytes, biased, summary }
+    }
+
+    /// Whether every NIST test passed
+    pub fn passed(&self) -> bool {
+        self.summary.all_passed()
+    }
+}
+
+/// A round: one random and one subtly biased sample, in random order
+///
+/// The bias is too small to see in a grid of gray cells but large enough
+/// that the NIST tests catch it, which is the point of the exercise. So
+/// that every round has a clear answer, a random sample that happens to
+/// fail a test (a few percent of rounds) is redrawn, as is a biased one
+/// that happens to pass.
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    /// Left and right grids
+    pub samples: [ChallengeSample; 2],
+}
+
+impl Challenge {
+    /// Bytes per sample, shown as a 32×32 grid
+    pub const SAMPLE_BYTES: usize = 1_024;
+
+    /// Probability of a 1 bit in the biased sample
+    pub const BIAS: f64 = 0.55;
+
+    /// Draws per sample before settling for an ambiguous round
+    const MAX_DRAWS: usize = 32;
+
+    /// Generate a round, drawing the random sample and the layout from `random`
+    ///
+    /// The GUI passes [`SystemEntropy`](crate::entropy::SystemEntropy);
+    /// tests pass a deterministic source.
+    pub fn generate<E: ?Sized + EntropySource>(random: &mut E) -> Self {
+        let mut fair = Self::draw(false, || {
+            let mut bytes = vec![0u8; Self::SAMPLE_BYTES];
+            random.fill_bytes(&mut bytes);
+            bytes
+        });
+
+        let mut biased_source = BiasedEntropy::new(random.next_u64(), Self::BIAS);
+        let mut biased = Self::draw(true, || {
+            let mut bytes = vec![0u8; Self::SAMPLE_BYTES];
+            biased_source.fill_bytes(&mut bytes);
+            bytes
+        });
+
+        if random.next_u32() & 1 == 0 {
+            std::mem::swap(&mut fair, &mut biased);
+        }
+        Self { samples: [fair, biased] }
+    }
+
+    // Redraw until the sample's verdict matches whether it is biased
+    fn draw(biased: bool, mut fill: impl FnMut() -> Vec<u8>) -> ChallengeSample {
+        let mut sample = ChallengeSample::new(fill(), biased);
+        for _ in 1..Self::MAX_DRAWS {
+            if sample.passed() != biased {
+                break;
+            }
+            sample = ChallengeSample::new(fill(), biased);
+        }
+        sample
+    }
+
+    /// Index (0 or 1) of the biased sample
+    pub fn biased_index(&self) -> usize {
+        if self.samples[0].biased { 0 } else { 1 }
+    }
+
+    /// Whether picking sample `guess` as the biased one is right
+    pub fn is_correct(&self, guess: usize) -> bool {
+        guess == self.biased_index()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{SystemBackend, SystemEntropy};
+
+    #[test]
+    fn test_each_round_has_one_pass_and_one_fail() {
+        let mut random = SystemEntropy::with_backend(SystemBackend::Deterministic(8));
+        let mut biased_positions = [0; 2];
+
+        for _ in 0..10 {
+            let challenge = Challenge::generate(&mut random);
+            let biased = challenge.biased_index();
+            let fair = 1 - biased;
+            biased_positions[biased] += 1;
+
+            assert!(challenge.samples[fair].passed(), "{:?}", challenge.samples[fair].summary);
+            assert!(!challenge.samples[biased].passed());
+            assert!(challenge.samples.iter().all(|s| s.bytes.len() == Challenge::SAMPLE_BYTES));
+            assert!(challenge.is_correct(biased) && !challenge.is_correct(fair));
+        }
+
+        // The biased sample is not always on the same side
+        assert!(biased_positions.iter().all(|&n| n > 0), "{:?}", biased_positions);
+    }
+}
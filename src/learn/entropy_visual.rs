@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use crate::entropy::{fill_from_alphabet, AlphabetError, EntropySource, PRINTABLE_ASCII};
 
 /// Represents a step in the entropy calculation process
 #[derive(Debug, Clone, PartialEq)]
@@ -29,6 +30,7 @@ pub struct EntropyProcess {
     pub is_playing: bool,
     pub speed: f32,
     pub last_update: f64,
+    pub random_len: usize,
 }
 
 impl Default for EntropyProcess {
@@ -40,6 +42,7 @@ impl Default for EntropyProcess {
             is_playing: false,
             speed: 1.0,
             last_update: 0.0,
+            random_len: 16,
         }
     }
 }
@@ -135,9 +138,9 @@ impl EntropyProcess {
         // --- Step 5: Interpretation ---
         self.steps.push(EntropyStep {
             step_type: EntropyStepType::Interpret,
-            byte_counts: byte_counts,
-            probabilities: probabilities,
-            entropy_contributions: entropy_contributions,
+            byte_counts,
+            probabilities,
+            entropy_contributions,
             current_entropy_sum: total_entropy,
             total_entropy,
             max_entropy: max_possible,
@@ -152,6 +155,20 @@ impl EntropyProcess {
         }
     }
 
+    /// Start with `count` random printable characters drawn from `source`
+    ///
+    /// Leaves the current input alone if `source` is too biased to draw
+    /// symbols from; see [`fill_from_alphabet`].
+    pub fn generate_random<E: ?Sized + EntropySource>(&mut self, source: &mut E, count: usize) -> Result<(), AlphabetError> {
+        let mut data = vec![0u8; count];
+        fill_from_alphabet(source, PRINTABLE_ASCII, &mut data)?;
+
+        // Printable ASCII is always valid UTF-8
+        let chars: String = data.iter().map(|&b| b as char).collect();
+        self.start(&chars);
+        Ok(())
+    }
+
     pub fn next_step(&mut self) {
         if self.current_step_index + 1 < self.steps.len() {
             self.current_step_index += 1;
@@ -181,3 +198,23 @@ impl EntropyProcess {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::MockEntropy;
+
+    #[test]
+    fn test_generate_random_length() {
+        let mut process = EntropyProcess::new();
+        let mut source = MockEntropy::new(3);
+
+        for n in [1, 2, 16, 100, 1_000] {
+            process.generate_random(&mut source, n).unwrap();
+            assert_eq!(process.input.chars().count(), n);
+
+            let counted: usize = process.steps[0].byte_counts.values().sum();
+            assert_eq!(counted, n);
+        }
+    }
+}
@@ -2,8 +2,12 @@ pub mod steps;
 pub mod xor_visual;
 pub mod entropy_visual;
 pub mod nist_visual;
+pub mod block_freq_visual;
+pub mod challenge;
 
 pub use xor_visual::EncryptionProcess;
 pub use steps::{EncryptionStep, BitOperation};
 pub use entropy_visual::EntropyProcess;
 pub use nist_visual::NistProcess;
+pub use block_freq_visual::BlockFrequencyProcess;
+pub use challenge::Challenge;
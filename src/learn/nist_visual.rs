@@ -1,4 +1,5 @@
-use crate::quality::NistTests;
+use crate::entropy::{fill_from_alphabet, AlphabetError, BiasedEntropy, EntropySource, PRINTABLE_ASCII};
+use crate::quality::{BitOrder, NistTests};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum NistStepType {
@@ -21,6 +22,37 @@ pub struct NistFrequencyStep {
     pub passed: bool,
 }
 
+impl NistFrequencyStep {
+    /// Why the sequence passed or failed, for the interpretation step
+    ///
+    /// `None` for the other steps.
+    pub fn explanation(&self) -> Option<String> {
+        if self.step_type != NistStepType::Interpret {
+            return None;
+        }
+        let (more, fewer, major, minor) = if self.ones_count >= self.zeros_count {
+            ("1s", "0s", self.ones_count, self.zeros_count)
+        } else {
+            ("0s", "1s", self.zeros_count, self.ones_count)
+        };
+        let n = self.bits.len();
+        Some(if self.passed {
+            format!(
+                "{} and {} differ by {}, which is {:.1} standard deviations (√{} ≈ {:.1}): \
+                 a fair coin is often this uneven.",
+                more, fewer, self.sum.abs(), self.s_obs, n, (n as f64).sqrt()
+            )
+        } else {
+            format!(
+                "The {} outnumber the {} {} to {}. For a fair coin the difference is usually \
+                 within √{} ≈ {:.1}, but here it is {}, {:.1} standard deviations. A fair coin \
+                 would be this uneven with probability {:.2e}, below the 0.01 threshold.",
+                more, fewer, major, minor, n, (n as f64).sqrt(), self.sum.abs(), self.s_obs, self.p_value
+            )
+        })
+    }
+}
+
 pub struct NistProcess {
     pub input_text: String,
     pub steps: Vec<NistFrequencyStep>,
@@ -28,6 +60,7 @@ pub struct NistProcess {
     pub is_playing: bool,
     pub speed: f32,
     pub last_update: f64,
+    pub random_len: usize,
 }
 
 impl Default for NistProcess {
@@ -39,12 +72,25 @@ impl Default for NistProcess {
             is_playing: false,
             speed: 1.0,
             last_update: 0.0,
+            random_len: 16,
         }
     }
 }
 
 
 impl NistProcess {
+    /// Probability of a 1 bit in [`generate_failing`](Self::generate_failing)'s source
+    pub const FAILING_P_ONE: f64 = 0.9;
+
+    /// Shortest input that can fail
+    ///
+    /// Printable ASCII has at most six 1s per byte, and three such
+    /// characters still give p ≈ 0.014.
+    pub const MIN_FAILING_CHARS: usize = 4;
+
+    /// Draws before falling back to a fixed failing input
+    const FAILING_DRAWS: usize = 16;
+
     pub fn new() -> Self {
         Self::default()
     }
@@ -61,12 +107,7 @@ impl NistProcess {
         }
 
         // --- Step 1: Convert to Bits ---
-        let mut bits = Vec::new();
-        for &byte in data {
-            for i in (0..8).rev() {
-                bits.push((byte >> i) & 1);
-            }
-        }
+        let bits = BitOrder::MsbFirst.extract(data);
 
         self.steps.push(NistFrequencyStep {
             step_type: NistStepType::ConvertToBits,
@@ -140,27 +181,53 @@ impl NistProcess {
         });
     }
 
-    pub fn generate_random(&mut self, count: usize) {
-        use crate::entropy::{EntropySource, SystemEntropy};
-        let mut entropy = SystemEntropy::new();
+    /// Start with `count` random printable characters drawn from `source`
+    ///
+    /// Leaves the current input alone if `source` is too biased to draw
+    /// symbols from; see [`fill_from_alphabet`].
+    pub fn generate_random<E: ?Sized + EntropySource>(&mut self, source: &mut E, count: usize) -> Result<(), AlphabetError> {
         let mut data = vec![0u8; count];
-        entropy.fill_bytes(&mut data);
+        fill_from_alphabet(source, PRINTABLE_ASCII, &mut data)?;
 
-        // Compromise: Generate a random string of alphanumeric characters.
-        // It's easy to understand and has high entropy.
+        // Printable ASCII is always valid UTF-8
+        let chars: String = data.iter().map(|&b| b as char).collect();
+        self.start(&chars);
+        Ok(())
+    }
 
-        // Re-using SystemEntropy is better as I already have it.
-        // SystemEntropy fills bytes. I can map them to chars.
+    /// Start with `count` characters from a source biased towards 1 bits
+    ///
+    /// The input always fails the test, for demonstrating what failure
+    /// looks like. `count` is raised to [`MIN_FAILING_CHARS`](Self::MIN_FAILING_CHARS).
+    /// The biased bytes are squeezed into printable ASCII, which costs the
+    /// top bit, so the text is about 80% ones rather than 90%. A draw that
+    /// happens to pass is redrawn, and if that keeps happening the input
+    /// falls back to a run of `~` (six 1s per character).
+    pub fn generate_failing<E: ?Sized + EntropySource>(&mut self, source: &mut E, count: usize) {
+        let count = count.max(Self::MIN_FAILING_CHARS);
+        let mut biased = BiasedEntropy::new(source.next_u64(), Self::FAILING_P_ONE);
+        let mut data = vec![0u8; count];
 
-        let chars: String = data.iter()
-            .map(|&b| {
-                // map to printable ascii range 33-126
-                let c = 33 + (b % (126 - 33));
-                c as char
-            })
-            .collect();
+        for _ in 0..Self::FAILING_DRAWS {
+            biased.fill_bytes(&mut data);
+            let chars: String = data.iter()
+                .map(|&b| match b & 0x7F {
+                    0x7F => '~',
+                    b if b <= 0x20 => (b | 0x40) as char,
+                    b => b as char,
+                })
+                .collect();
+            self.start(&chars);
+            if self.passed() == Some(false) {
+                return;
+            }
+        }
+        self.start(&"~".repeat(count));
+    }
 
-        self.start(&chars);
+    /// Outcome of the test, once an input has been analyzed
+    pub fn passed(&self) -> Option<bool> {
+        self.steps.last().map(|step| step.passed)
     }
 
     pub fn current_step(&self) -> Option<&NistFrequencyStep> {
@@ -200,3 +267,45 @@ impl NistProcess {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::MockEntropy;
+
+    #[test]
+    fn test_generate_random_length() {
+        let mut process = NistProcess::new();
+        let mut source = MockEntropy::new(3);
+
+        for n in [1, 2, 16, 100, 1_000] {
+            process.generate_random(&mut source, n).unwrap();
+            assert_eq!(process.input_text.chars().count(), n);
+            assert_eq!(process.steps[0].bits.len(), n * 8);
+        }
+    }
+
+    #[test]
+    fn test_failing_example_fails() {
+        let mut process = NistProcess::new();
+        let mut source = MockEntropy::new(5);
+
+        for n in [0, 1, 3, 4, 16, 200] {
+            process.generate_failing(&mut source, n);
+            assert_eq!(process.input_text.chars().count(), n.max(NistProcess::MIN_FAILING_CHARS));
+            assert!(process.input_text.bytes().all(|b| PRINTABLE_ASCII.contains(&b)));
+
+            let last = process.steps.last().unwrap();
+            assert_eq!(last.step_type, NistStepType::Interpret);
+            assert!(!last.passed, "{:?} passed", process.input_text);
+            assert!(last.explanation().unwrap().contains("1s outnumber the 0s"));
+        }
+
+        // Three of the most one-heavy characters are not enough
+        process.start("~~~");
+        assert_eq!(process.passed(), Some(true));
+        process.start("~~~~");
+        assert_eq!(process.passed(), Some(false));
+        assert!(process.steps[0].explanation().is_none());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::entropy::bits::{BitIter, BitOrder};
+
 /// Represents a single bit XOR operation
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BitOperation {
@@ -22,22 +24,19 @@ impl EncryptionStep {
         let input_byte = input_char as u8;
         let result_byte = input_byte ^ keystream_byte;
 
-        let mut bit_ops = Vec::with_capacity(8);
-
         // Process bits from MSB (7) to LSB (0) for display
-        for i in (0..8).rev() {
-            let mask = 1 << i;
-            let input_bit = (input_byte & mask) != 0;
-            let key_bit = (keystream_byte & mask) != 0;
-            let result_bit = (result_byte & mask) != 0;
-
-            bit_ops.push(BitOperation {
-                input_bit,
-                key_bit,
-                result_bit,
-                position: i,
-            });
-        }
+        let bits = |byte: u8| BitIter::new(&[byte], BitOrder::MsbFirst).collect::<Vec<u8>>();
+        let bit_ops = bits(input_byte).into_iter()
+            .zip(bits(keystream_byte))
+            .zip(bits(result_byte))
+            .enumerate()
+            .map(|(i, ((input_bit, key_bit), result_bit))| BitOperation {
+                input_bit: input_bit == 1,
+                key_bit: key_bit == 1,
+                result_bit: result_bit == 1,
+                position: 7 - i,
+            })
+            .collect();
 
         Self {
             character: input_char,
@@ -63,3 +62,23 @@ impl EncryptionStep {
         format!("{:08b}", self.result_byte)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_ops_msb_first() {
+        let step = EncryptionStep::new('A', 0b1100_0011);
+        assert_eq!(step.result_byte, 0b1000_0010);
+
+        let positions: Vec<usize> = step.bit_ops.iter().map(|op| op.position).collect();
+        assert_eq!(positions, [7, 6, 5, 4, 3, 2, 1, 0]);
+        for op in &step.bit_ops {
+            let mask = 1u8 << op.position;
+            assert_eq!(op.input_bit, b'A' & mask != 0);
+            assert_eq!(op.key_bit, 0b1100_0011 & mask != 0);
+            assert_eq!(op.result_bit, op.input_bit ^ op.key_bit);
+        }
+    }
+}
@@ -56,7 +56,41 @@ pub mod learn;
 #[cfg(feature = "gui")]
 pub mod viz;
 
+#[cfg(feature = "service")]
+pub mod service;
+
 // Re-exports for convenience
 pub use entropy::{EntropySource, SystemEntropy};
 pub use crypto::StreamCipher;
-pub use quality::{QualityMetrics, NistTests};
+pub use quality::{QualityMetrics, NistTests, QualityReport};
+
+/// Sample `size` bytes once and run the metrics and NIST suites on them
+///
+/// The one-shot front door for the common case; see
+/// [`QualityReport::generate`].
+///
+/// # Examples
+///
+/// ```
+/// let mut source = entropy_forge::SystemEntropy::new();
+/// let report = entropy_forge::quick_check(&mut source, 100_000);
+/// println!("{:?}: {}/{} NIST tests passed", report.verdict(), report.nist_passed(), report.nist_applicable());
+/// ```
+pub fn quick_check(source: &mut dyn EntropySource, size: usize) -> QualityReport {
+    QualityReport::generate(source, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::MockEntropy;
+
+    #[test]
+    fn test_quick_check_sample_size() {
+        let mut source = MockEntropy::new(9);
+        let report = quick_check(&mut source, 12_345);
+
+        assert_eq!(report.metrics.total_bytes, 12_345);
+        assert_eq!(report, QualityReport::generate(&mut MockEntropy::new(9), 12_345));
+    }
+}
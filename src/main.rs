@@ -1,6 +1,7 @@
 //! Entropy Forge - GUI Application
 
 use eframe::egui;
+use entropy_forge::viz::config::GuiConfig;
 use entropy_forge::viz::EntropyForgeApp;
 
 fn main() -> Result<(), eframe::Error> {
@@ -16,12 +17,25 @@ fn main() -> Result<(), eframe::Error> {
     eframe::run_native(
         "Entropy Forge",
         options,
-        Box::new(|_cc| {
-            Ok(Box::new(EntropyForgeApp::default()))
+        Box::new(|cc| {
+            let app = app_with_saved_settings();
+            app.apply_visuals(&cc.egui_ctx);
+            Ok(Box::new(app))
         }),
     )
 }
 
+/// Settings from the user's config file, saved back on exit
+fn app_with_saved_settings() -> EntropyForgeApp {
+    match GuiConfig::default_path() {
+        Some(path) => EntropyForgeApp::builder()
+            .config(GuiConfig::load_or_default(&path))
+            .save_config_to(path)
+            .build(),
+        None => EntropyForgeApp::default(),
+    }
+}
+
 fn load_icon() -> egui::IconData {
     // Simple 32x32 icon (can be replaced with actual icon)
     let icon_size = 32;
@@ -0,0 +1,115 @@
+//! Batch NIST testing over many samples
+
+use super::{NistSummary, NistTests};
+
+/// NIST results for one named sample
+#[derive(Debug, Clone)]
+pub struct BatchEntry {
+    /// Sample name (e.g. file name)
+    pub name: String,
+    
+    /// Size of the sample in bytes
+    pub bytes: usize,
+    
+    /// Number of tests with p ≥ 0.01
+    pub passed: usize,
+    
+    /// Number of tests run
+    pub total: usize,
+    
+    /// Test with the lowest p-value
+    pub weakest_test: &'static str,
+    
+    /// P-value of the weakest test
+    pub weakest_p_value: f64,
+}
+
+impl BatchEntry {
+    /// Whether every test passed
+    pub fn all_passed(&self) -> bool {
+        self.passed == self.total
+    }
+}
+
+/// Aggregated NIST results for a batch of samples
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::quality::BatchReport;
+///
+/// let zeros = vec![0u8; 1_000];
+/// let report = BatchReport::run([("zeros.bin", zeros.as_slice())]);
+///
+/// assert_eq!(report.entries.len(), 1);
+/// assert_eq!(report.samples_passed(), 0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BatchReport {
+    /// One entry per sample, in input order
+    pub entries: Vec<BatchEntry>,
+}
+
+impl BatchReport {
+    /// Run all NIST tests over each named sample
+    pub fn run<'a, S, I>(samples: I) -> Self
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = (S, &'a [u8])>,
+    {
+        let entries = samples.into_iter()
+            .map(|(name, data)| Self::evaluate(name.into(), data))
+            .collect();
+        
+        Self { entries }
+    }
+    
+    /// Number of samples where every test passed
+    pub fn samples_passed(&self) -> usize {
+        self.entries.iter().filter(|e| e.all_passed()).count()
+    }
+    
+    fn evaluate(name: String, data: &[u8]) -> BatchEntry {
+        let summary = NistSummary::from_results(NistTests::run_all_tests(data));
+        let (weakest_test, weakest_p_value) = summary.weakest().unwrap_or(("", 0.0));
+        
+        BatchEntry {
+            name,
+            bytes: data.len(),
+            passed: summary.passed,
+            total: summary.total(),
+            weakest_test,
+            weakest_p_value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{ChaChaEntropy, EntropySource};
+    
+    #[test]
+    fn test_batch_over_buffers() {
+        let mut random = vec![0u8; 100_000];
+        ChaChaEntropy::new([6; 32]).fill_bytes(&mut random);
+        let ones = vec![0xFFu8; 100_000];
+        
+        let report = BatchReport::run([
+            ("random.bin", random.as_slice()),
+            ("ones.bin", ones.as_slice()),
+        ]);
+        
+        assert_eq!(report.entries.len(), 2);
+        assert_eq!(report.entries[0].name, "random.bin");
+        assert_eq!(report.entries[0].bytes, 100_000);
+        assert!(report.entries[0].passed >= report.entries[0].total - 1);
+        
+        let ones_entry = &report.entries[1];
+        assert_eq!(ones_entry.passed, 0);
+        assert!(!ones_entry.all_passed());
+        assert!(ones_entry.weakest_p_value < 0.01);
+        
+        assert!(report.samples_passed() <= 1);
+    }
+}
@@ -0,0 +1,66 @@
+//! Dependence between two byte streams
+
+/// Fraction of bit positions where two equal-length streams agree
+///
+/// About 0.5 for independent sources. A value far from 0.5 either way
+/// means the streams are correlated, so XOR-combining them adds less
+/// entropy than it seems: at 1.0 (identical) the XOR is all zeros, and at
+/// 0.0 (complementary) all ones. On `n` bytes, independent streams stay
+/// within about `±0.53 / √n` of 0.5 (three standard deviations of the
+/// agreement over `8n` bits).
+///
+/// Returns `None` if the streams are empty or differ in length.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, SystemEntropy};
+/// use entropy_forge::quality::cross_correlation;
+///
+/// let mut a = vec![0u8; 10_000];
+/// let mut b = vec![0u8; 10_000];
+/// SystemEntropy::new().fill_bytes(&mut a);
+/// SystemEntropy::new().fill_bytes(&mut b);
+///
+/// let agreement = cross_correlation(&a, &b).unwrap();
+/// assert!((agreement - 0.5).abs() < 0.02);
+/// ```
+pub fn cross_correlation(a: &[u8], b: &[u8]) -> Option<f64> {
+    if a.is_empty() || a.len() != b.len() {
+        return None;
+    }
+
+    let differing: u64 = a.iter()
+        .zip(b)
+        .map(|(x, y)| u64::from((x ^ y).count_ones()))
+        .sum();
+    let bits = a.len() as u64 * 8;
+
+    Some((bits - differing) as f64 / bits as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{EntropySource, SystemEntropy};
+
+    #[test]
+    fn test_independent_and_identical_streams() {
+        let mut a = vec![0u8; 100_000];
+        let mut b = vec![0u8; 100_000];
+        SystemEntropy::new().fill_bytes(&mut a);
+        SystemEntropy::new().fill_bytes(&mut b);
+
+        // 3σ over 800,000 bits is about 0.0017
+        let agreement = cross_correlation(&a, &b).unwrap();
+        assert!((agreement - 0.5).abs() < 0.005, "{}", agreement);
+
+        assert_eq!(cross_correlation(&a, &a), Some(1.0));
+        let inverted: Vec<u8> = a.iter().map(|x| !x).collect();
+        assert_eq!(cross_correlation(&a, &inverted), Some(0.0));
+
+        assert_eq!(cross_correlation(&[0x0F], &[0x00]), Some(0.5));
+        assert_eq!(cross_correlation(&[], &[]), None);
+        assert_eq!(cross_correlation(&a, &b[1..]), None);
+    }
+}
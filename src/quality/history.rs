@@ -0,0 +1,260 @@
+//! Rolling history of quality reports, for spotting gradual degradation
+
+use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use super::QualityReport;
+
+/// A metric tracked by [`MetricsHistory`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MetricKind {
+    /// [`QualityMetrics::shannon_entropy`](super::QualityMetrics::shannon_entropy), bits per byte
+    ShannonEntropy,
+    /// [`QualityMetrics::min_entropy`](super::QualityMetrics::min_entropy), bits per byte
+    MinEntropy,
+    /// [`QualityMetrics::chi_square_p_value`](super::QualityMetrics::chi_square_p_value)
+    ChiSquarePValue,
+    /// [`QualityMetrics::overall_score`](super::QualityMetrics::overall_score), 0–100
+    OverallScore,
+    /// Fraction of NIST tests passed, 0–1
+    NistPassRate,
+}
+
+/// The key metrics of one report, as kept by [`MetricsHistory`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Shannon entropy in bits per byte
+    pub shannon_entropy: f64,
+    /// Min-entropy in bits per byte
+    pub min_entropy: f64,
+    /// Byte chi-square p-value
+    pub chi_square_p_value: f64,
+    /// Overall quality score, 0–100
+    pub overall_score: f64,
+    /// Fraction of the NIST tests that ran which passed (0 when none ran)
+    pub nist_pass_rate: f64,
+}
+
+impl HistoryEntry {
+    /// Extract the tracked metrics from a report
+    pub fn from_report(report: &QualityReport) -> Self {
+        let total = report.nist_applicable();
+        Self {
+            shannon_entropy: report.metrics.shannon_entropy,
+            min_entropy: report.metrics.min_entropy,
+            chi_square_p_value: report.metrics.chi_square_p_value,
+            overall_score: report.metrics.overall_score(),
+            nist_pass_rate: if total == 0 { 0.0 } else { report.nist_passed() as f64 / total as f64 },
+        }
+    }
+
+    /// Value of one metric
+    pub fn get(&self, metric: MetricKind) -> f64 {
+        match metric {
+            MetricKind::ShannonEntropy => self.shannon_entropy,
+            MetricKind::MinEntropy => self.min_entropy,
+            MetricKind::ChiSquarePValue => self.chi_square_p_value,
+            MetricKind::OverallScore => self.overall_score,
+            MetricKind::NistPassRate => self.nist_pass_rate,
+        }
+    }
+}
+
+/// The most recent runs' key metrics, oldest first
+///
+/// A bounded ring buffer: once full, each [`push`](Self::push) drops the
+/// oldest run. [`trend`](Self::trend) fits a line through what is kept, so
+/// a slow decline that no single run flags still shows up as a negative
+/// slope. The history serializes, so a monitor can persist it between
+/// checks.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::SystemEntropy;
+/// use entropy_forge::quality::{MetricKind, MetricsHistory, QualityReport};
+///
+/// let mut history = MetricsHistory::new(30);
+/// let mut source = SystemEntropy::new();
+/// for _ in 0..5 {
+///     history.push(&QualityReport::generate(&mut source, 10_000));
+/// }
+///
+/// if history.trend(MetricKind::ShannonEntropy) < -0.01 {
+///     println!("entropy is drifting down");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "StoredHistory")]
+pub struct MetricsHistory {
+    capacity: usize,
+    entries: VecDeque<HistoryEntry>,
+}
+
+/// Error for a saved [`MetricsHistory`] that breaks its invariants
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum InvalidHistory {
+    /// A history must keep at least one run
+    #[error("history capacity must be at least 1")]
+    ZeroCapacity,
+
+    /// More runs than the history can hold
+    #[error("history holds {entries} runs but its capacity is {capacity}")]
+    OverCapacity { entries: usize, capacity: usize },
+}
+
+/// Deserialized form of [`MetricsHistory`], checked before use
+#[derive(Deserialize)]
+struct StoredHistory {
+    capacity: usize,
+    entries: VecDeque<HistoryEntry>,
+}
+
+impl TryFrom<StoredHistory> for MetricsHistory {
+    type Error = InvalidHistory;
+
+    fn try_from(stored: StoredHistory) -> Result<Self, Self::Error> {
+        let StoredHistory { capacity, entries } = stored;
+        if capacity == 0 {
+            return Err(InvalidHistory::ZeroCapacity);
+        }
+        if entries.len() > capacity {
+            return Err(InvalidHistory::OverCapacity { entries: entries.len(), capacity });
+        }
+        Ok(Self { capacity, entries })
+    }
+}
+
+impl MetricsHistory {
+    /// Empty history keeping the last `capacity` runs (at least 1)
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Record a run, dropping the oldest if the history is full
+    pub fn push(&mut self, report: &QualityReport) {
+        self.push_entry(HistoryEntry::from_report(report));
+    }
+
+    /// Record already-extracted metrics
+    pub fn push_entry(&mut self, entry: HistoryEntry) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Maximum number of runs kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of runs kept
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no runs have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Kept runs, oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries.iter()
+    }
+
+    /// Least-squares slope of `metric` per run over the kept runs
+    ///
+    /// Negative means the metric is falling. 0.0 with fewer than two runs,
+    /// where there is no trend to fit.
+    pub fn trend(&self, metric: MetricKind) -> f64 {
+        let n = self.entries.len();
+        if n < 2 {
+            return 0.0;
+        }
+
+        let mean_x = (n - 1) as f64 / 2.0;
+        let mean_y = self.entries.iter().map(|e| e.get(metric)).sum::<f64>() / n as f64;
+
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for (i, entry) in self.entries.iter().enumerate() {
+            let dx = i as f64 - mean_x;
+            covariance += dx * (entry.get(metric) - mean_y);
+            variance += dx * dx;
+        }
+        covariance / variance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::BiasedEntropy;
+
+    fn entry(shannon_entropy: f64) -> HistoryEntry {
+        HistoryEntry {
+            shannon_entropy,
+            min_entropy: 0.0,
+            chi_square_p_value: 0.5,
+            overall_score: 50.0,
+            nist_pass_rate: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_declining_entropy_has_negative_trend() {
+        let mut history = MetricsHistory::new(10);
+        for i in 0..5 {
+            history.push_entry(entry(7.99 - 0.01 * i as f64));
+        }
+        assert!((history.trend(MetricKind::ShannonEntropy) + 0.01).abs() < 1e-9);
+        assert_eq!(history.trend(MetricKind::OverallScore), 0.0);
+
+        // Real reports from an increasingly biased source
+        let mut history = MetricsHistory::new(10);
+        for bias in [0.5, 0.55, 0.6, 0.65, 0.7] {
+            history.push(&QualityReport::generate(&mut BiasedEntropy::new(3, bias), 20_000));
+        }
+        assert!(history.trend(MetricKind::ShannonEntropy) < 0.0);
+        assert!(history.trend(MetricKind::MinEntropy) < 0.0);
+    }
+
+    #[test]
+    fn test_ring_buffer_keeps_recent_runs() {
+        let mut history = MetricsHistory::new(3);
+        assert_eq!(history.trend(MetricKind::ShannonEntropy), 0.0);
+
+        for value in [1.0, 2.0, 3.0, 5.0, 4.0, 3.0] {
+            history.push_entry(entry(value));
+        }
+        assert_eq!(history.len(), 3);
+        let kept: Vec<f64> = history.entries().map(|e| e.shannon_entropy).collect();
+        assert_eq!(kept, [5.0, 4.0, 3.0]);
+
+        // Only the kept runs count: falling, though the whole series rose
+        assert_eq!(history.trend(MetricKind::ShannonEntropy), -1.0);
+        assert_eq!(MetricsHistory::new(0).capacity(), 1);
+    }
+
+    #[test]
+    fn test_deserialize_checks_capacity() {
+        let mut history = MetricsHistory::new(2);
+        history.push_entry(entry(1.0));
+        let json = serde_json::to_string(&history).unwrap();
+        assert_eq!(serde_json::from_str::<MetricsHistory>(&json).unwrap(), history);
+
+        let entry_json = serde_json::to_string(&entry(1.0)).unwrap();
+        let zero = r#"{"capacity":0,"entries":[]}"#;
+        let err = serde_json::from_str::<MetricsHistory>(zero).unwrap_err();
+        assert!(err.to_string().contains("at least 1"), "{}", err);
+
+        let over = format!(r#"{{"capacity":1,"entries":[{0},{0}]}}"#, entry_json);
+        let err = serde_json::from_str::<MetricsHistory>(&over).unwrap_err();
+        assert!(err.to_string().contains("holds 2 runs"), "{}", err);
+    }
+}
@@ -1,13 +1,98 @@
 //! Entropy quality metrics
 
+use crate::entropy::bits::BitOrder;
 use crate::entropy::EntropySource;
+use crate::quality::NistTests;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ChiSquared, ContinuousCDF};
 use std::collections::HashMap;
+use std::ops::Range;
+use thiserror::Error;
+
+/// Errors from [`QualityMetrics::try_analyze`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AnalyzeError {
+    /// A finite source has fewer bytes left than the requested sample
+    #[error("requested {requested} bytes but the source has only {available} left")]
+    InsufficientData {
+        /// Requested sample size
+        requested: usize,
+        /// Bytes the source reported as remaining
+        available: u64,
+    },
+}
+
+/// Optional, costlier metrics for [`QualityMetrics::from_bytes_with`]
+///
+/// Everything is off by default, which is what
+/// [`from_bytes`](QualityMetrics::from_bytes) uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MetricsOptions {
+    /// Compute [`digraph_p_value`](QualityMetrics::digraph_p_value), which
+    /// builds a 256×256 transition table (256 KiB)
+    pub digraph: bool,
+    
+    /// Compute [`conditional_entropy`](QualityMetrics::conditional_entropy),
+    /// which builds a 256×256 pair table (512 KiB) and is only meaningful
+    /// from [`CONDITIONAL_ENTROPY_MIN_BYTES`](QualityMetrics::CONDITIONAL_ENTROPY_MIN_BYTES)
+    pub conditional: bool,
+}
+
+/// Summary verdict for an entropy source
+///
+/// Produced by [`QualityMetrics::classify`]. This is a heuristic over
+/// statistical results: passing statistical tests is necessary but never
+/// sufficient for cryptographic security.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SourceVerdict {
+    /// Indistinguishable from ideal randomness by every check we run
+    CryptographicQuality,
+    /// Good statistics with minor deviations (e.g. one NIST failure)
+    StatisticallyGood,
+    /// Noticeable bias or structure; investigate before relying on it
+    Suspicious,
+    /// Clearly non-random output
+    Broken,
+}
+
+impl SourceVerdict {
+    /// Short human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            SourceVerdict::CryptographicQuality => "Cryptographic quality",
+            SourceVerdict::StatisticallyGood => "Statistically good",
+            SourceVerdict::Suspicious => "Suspicious",
+            SourceVerdict::Broken => "Broken",
+        }
+    }
+}
+
+/// Quality metrics mapped to a common 0–1 scale
+///
+/// Produced by [`QualityMetrics::normalized`]. Every field is 1.0 for ideal
+/// random data and 0.0 for the worst case, which suits radar charts and
+/// dashboards. `longest_run` is not included because its expected value
+/// grows with the sample size rather than having a fixed ideal.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedMetrics {
+    /// Shannon entropy / 8
+    pub shannon_entropy: f64,
+    
+    /// Min-entropy / 8
+    pub min_entropy: f64,
+    
+    /// Chi-square p-value, passed through unchanged
+    pub chi_square: f64,
+    
+    /// 1 − |mean − 127.5| / 127.5
+    pub mean: f64,
+}
 
 /// Entropy quality metrics
 ///
 /// This struct contains various measurements of entropy quality, including
 /// Shannon entropy, min-entropy, and byte frequency distribution.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct QualityMetrics {
     /// Shannon entropy in bits per byte (max: 8.0)
     pub shannon_entropy: f64,
@@ -15,6 +100,25 @@ pub struct QualityMetrics {
     /// Min-entropy in bits per byte (conservative estimate)
     pub min_entropy: f64,
     
+    /// Entropy of each byte given the one before it, in bits per byte
+    ///
+    /// Well below [`shannon_entropy`](Self::shannon_entropy) when bytes are
+    /// predictable from their predecessor. See
+    /// [`conditional_entropy`](Self::conditional_entropy). Only computed
+    /// when requested through [`MetricsOptions::conditional`]; otherwise
+    /// `None`, as it is for fewer than two bytes. The estimate is biased
+    /// low on small samples (random data scores about 7.45 at 100 KB), so
+    /// compare it with Shannon entropy only from
+    /// [`CONDITIONAL_ENTROPY_MIN_BYTES`](Self::CONDITIONAL_ENTROPY_MIN_BYTES).
+    pub conditional_entropy: Option<f64>,
+    
+    /// P-value of the byte-transition independence test
+    ///
+    /// See [`digraph_chi_square`](Self::digraph_chi_square). Only computed
+    /// when requested through [`MetricsOptions::digraph`]; otherwise
+    /// `None`, as it is when the test is not applicable.
+    pub digraph_p_value: Option<f64>,
+    
     /// Frequency of each byte value (0-255)
     pub byte_frequency: HashMap<u8, usize>,
     
@@ -24,34 +128,89 @@ pub struct QualityMetrics {
     /// Chi-square statistic (for uniformity test)
     pub chi_square: f64,
     
+    /// P-value of the chi-square statistic (255 degrees of freedom)
+    ///
+    /// Values ≥ 0.01 indicate the bytes are consistent with a uniform
+    /// distribution.
+    pub chi_square_p_value: f64,
+    
     /// Mean value (should be ~127.5 for uniform distribution)
     pub mean: f64,
     
     /// Longest run of identical bits
     pub longest_run: usize,
+    
+    /// Smallest byte value seen (0 for empty input)
+    pub min_byte: u8,
+    
+    /// Largest byte value seen (0 for empty input)
+    pub max_byte: u8,
+    
+    /// Median byte value (lower median for even counts)
+    pub median_byte: u8,
+    
+    /// Frequency of 0x00 relative to the uniform 1/256 (ideal: 1.0)
+    ///
+    /// Values well above 1.0 point to a source that clamps to zero.
+    pub zero_byte_fraction: f64,
+    
+    /// Frequency of 0xFF relative to the uniform 1/256 (ideal: 1.0)
+    ///
+    /// Values well above 1.0 point to a source that saturates.
+    pub max_byte_fraction: f64,
+    
+    /// Largest spectral peak relative to white noise (ideal: about 1.0)
+    ///
+    /// See [`periodicity_score`](Self::periodicity_score). `None` without
+    /// the `fft` feature or for samples under 125 bytes.
+    pub periodicity_score: Option<f64>,
 }
 
 impl QualityMetrics {
+    /// Sample size from which order-1 [`conditional_entropy`](Self::conditional_entropy)
+    /// is trustworthy: random data scores within about 0.05 bits of 8.0
+    pub const CONDITIONAL_ENTROPY_MIN_BYTES: usize = 1_000_000;
+    
+    /// Sample size, in bytes, at which [`CALIBRATION_QUANTILES`](Self::CALIBRATION_QUANTILES) were measured
+    pub const CALIBRATION_SAMPLE_BYTES: usize = 100_000;
+    
+    /// Reference distribution for [`calibrated_score`](Self::calibrated_score)
+    ///
+    /// [`overall_score`](Self::overall_score) at the 0th, 5th, ..., 100th
+    /// percentile over 5,000 independent `SystemEntropy` samples of
+    /// [`CALIBRATION_SAMPLE_BYTES`](Self::CALIBRATION_SAMPLE_BYTES) each.
+    pub const CALIBRATION_QUANTILES: [f64; 21] = [
+        98.7388, 99.0440, 99.0939, 99.1238, 99.1478, 99.1659, 99.1813,
+        99.1967, 99.2101, 99.2230, 99.2335, 99.2453, 99.2571, 99.2688,
+        99.2817, 99.2932, 99.3074, 99.3242, 99.3427, 99.3664, 99.4572,
+    ];
+    
     /// Calculate Shannon entropy (in bits per byte)
     ///
     /// Shannon entropy measures the average information content.
     /// Higher is better, with 8.0 being perfect for byte-level entropy.
     ///
     /// Formula: H(X) = -Σ p(x) * log₂(p(x))
-    pub fn shannon_entropy(data: &[u8]) -> f64 {
+    ///
+    /// Returns `None` for empty input; `Some(0.0)` means constant data.
+    pub fn shannon_entropy(data: &[u8]) -> Option<f64> {
         if data.is_empty() {
-            return 0.0;
+            return None;
         }
         
-        let mut freq = HashMap::new();
-        for &byte in data {
-            *freq.entry(byte).or_insert(0) += 1;
+        Some(Self::shannon_from_histogram(&Self::byte_histogram_fast(data), data.len()))
+    }
+    
+    fn shannon_from_histogram(freq: &[usize; 256], total: usize) -> f64 {
+        if total == 0 {
+            return 0.0;
         }
         
-        let len = data.len() as f64;
+        // Fixed-order counts keep the floating-point sum reproducible
+        let len = total as f64;
         let mut entropy = 0.0;
         
-        for count in freq.values() {
+        for count in freq {
             let p = *count as f64 / len;
             if p > 0.0 {
                 entropy -= p * p.log2();
@@ -61,27 +220,307 @@ impl QualityMetrics {
         entropy
     }
     
+    /// Calculate Shannon entropy over symbols of arbitrary bit width
+    ///
+    /// Reinterprets the data as a bit stream (most significant bit first)
+    /// split into `symbol_bits`-wide symbols, e.g. 4 for nibbles or 12 for
+    /// ADC samples, and computes entropy over that alphabet. The result is in
+    /// bits per symbol, so the maximum is `symbol_bits`. Trailing bits that
+    /// do not fill a whole symbol are dropped.
+    ///
+    /// Returns `None` if `symbol_bits` is outside `2..=16` or no complete
+    /// symbol is available.
+    pub fn shannon_entropy_symbols(data: &[u8], symbol_bits: usize) -> Option<f64> {
+        if !(2..=16).contains(&symbol_bits) {
+            return None;
+        }
+        
+        let mut freq = vec![0usize; 1 << symbol_bits];
+        let mut symbol = 0usize;
+        let mut filled = 0;
+        let mut total = 0usize;
+        
+        for bit in BitOrder::MsbFirst.iter(data) {
+            symbol = (symbol << 1) | bit as usize;
+            filled += 1;
+            
+            if filled == symbol_bits {
+                freq[symbol] += 1;
+                total += 1;
+                symbol = 0;
+                filled = 0;
+            }
+        }
+        
+        if total == 0 {
+            return None;
+        }
+        
+        let n = total as f64;
+        let entropy = freq.iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 / n;
+                -p * p.log2()
+            })
+            .sum();
+        Some(entropy)
+    }
+    
+    /// Conditional entropy of a byte given the `order` bytes before it
+    ///
+    /// Computes H(Xₙ | Xₙ₋₁, …, Xₙ₋ₖ) for k = `order`, in bits per byte,
+    /// over every overlapping window of `order + 1` bytes: the entropy of
+    /// the windows minus the entropy of their leading contexts. It equals
+    /// the marginal [`shannon_entropy`](Self::shannon_entropy) for
+    /// independent bytes and drops below it when bytes are predictable
+    /// from their predecessors. Order 0 is the marginal entropy itself.
+    ///
+    /// The estimate is biased low when the sample is small next to the
+    /// 256^(order + 1) possible windows: order 1 wants a few megabytes
+    /// before random data scores near 8.0, order 2 far more.
+    ///
+    /// Returns `None` if `order` is above 2 (the counts get memory-heavy)
+    /// or the data holds no complete window.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// // Each byte determines the next, though all values are equally common
+    /// let counter: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+    /// assert!(QualityMetrics::shannon_entropy(&counter).unwrap() > 7.99);
+    /// assert!(QualityMetrics::conditional_entropy(&counter, 1).unwrap() < 0.01);
+    /// ```
+    pub fn conditional_entropy(data: &[u8], order: usize) -> Option<f64> {
+        if order > 2 || data.len() <= order {
+            return None;
+        }
+        if order == 0 {
+            return Self::shannon_entropy(data);
+        }
+        
+        // Windows packed big-endian into integers; the context drops the last byte
+        let (joint, context): (Vec<usize>, Vec<usize>) = if order == 1 {
+            let mut joint = vec![0usize; 1 << 16];
+            let mut context = vec![0usize; 256];
+            for pair in data.windows(2) {
+                joint[(pair[0] as usize) << 8 | pair[1] as usize] += 1;
+                context[pair[0] as usize] += 1;
+            }
+            (joint, context)
+        } else {
+            let mut joint: HashMap<u32, usize> = HashMap::new();
+            let mut context: HashMap<u32, usize> = HashMap::new();
+            for window in data.windows(3) {
+                let key = (window[0] as u32) << 16 | (window[1] as u32) << 8 | window[2] as u32;
+                *joint.entry(key).or_insert(0) += 1;
+                *context.entry(key >> 8).or_insert(0) += 1;
+            }
+            
+            // Sort so the floating-point sum doesn't depend on hash order
+            let sorted = |counts: HashMap<u32, usize>| {
+                let mut counts: Vec<usize> = counts.into_values().collect();
+                counts.sort_unstable();
+                counts
+            };
+            (sorted(joint), sorted(context))
+        };
+        
+        let total = (data.len() - order) as f64;
+        let entropy = |counts: &[usize]| -> f64 {
+            counts.iter()
+                .filter(|&&count| count > 0)
+                .map(|&count| {
+                    let p = count as f64 / total;
+                    -p * p.log2()
+                })
+                .sum()
+        };
+        Some((entropy(&joint) - entropy(&context)).max(0.0))
+    }
+    
+    /// Shannon entropy of non-overlapping `block_bytes`-byte blocks, per byte
+    ///
+    /// Treats each block as one symbol from an alphabet of 256^`block_bytes`
+    /// and divides the entropy by `block_bytes`, so the maximum stays 8.0
+    /// bits per byte. Independent bytes keep it near the single-byte
+    /// [`shannon_entropy`](Self::shannon_entropy); structure within blocks,
+    /// such as a byte that depends on its neighbour, pulls it below. Like
+    /// [`conditional_entropy`](Self::conditional_entropy) the estimate is
+    /// biased low unless the sample has many times more blocks than the
+    /// alphabet has symbols: a few megabytes for 2-byte blocks. A trailing
+    /// partial block is ignored.
+    ///
+    /// Returns `None` if `block_bytes` is 0 or the data holds no complete
+    /// block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// // Every byte value equally common, but only 256 of 65,536 pairs occur
+    /// let pairs: Vec<u8> = (0..=255u8).flat_map(|b| [b, b.rotate_left(3)]).cycle().take(20_000).collect();
+    /// assert!(QualityMetrics::shannon_entropy(&pairs).unwrap() > 7.99);
+    /// assert!(QualityMetrics::block_entropy(&pairs, 2).unwrap() < 4.01);
+    /// ```
+    pub fn block_entropy(data: &[u8], block_bytes: usize) -> Option<f64> {
+        if block_bytes == 0 || data.len() < block_bytes {
+            return None;
+        }
+        
+        let mut freq: HashMap<&[u8], usize> = HashMap::new();
+        for block in data.chunks_exact(block_bytes) {
+            *freq.entry(block).or_insert(0) += 1;
+        }
+        
+        // Sort so the floating-point sum doesn't depend on hash order
+        let mut counts: Vec<usize> = freq.into_values().collect();
+        counts.sort_unstable();
+        
+        let n = (data.len() / block_bytes) as f64;
+        let entropy: f64 = counts.iter()
+            .map(|&count| {
+                let p = count as f64 / n;
+                -p * p.log2()
+            })
+            .sum();
+        Some(entropy / block_bytes as f64)
+    }
+    
+    /// Count every byte transition (overlapping pair of adjacent bytes)
+    ///
+    /// Returns a flattened 256×256 table where entry `a << 8 | b` counts how
+    /// often byte `a` is directly followed by `b`; the counts sum to
+    /// `data.len() - 1`. Counts saturate at `u32::MAX`.
+    pub fn digraph_counts(data: &[u8]) -> Vec<u32> {
+        let mut counts = vec![0u32; 1 << 16];
+        for pair in data.windows(2) {
+            let cell = &mut counts[(pair[0] as usize) << 8 | pair[1] as usize];
+            *cell = cell.saturating_add(1);
+        }
+        counts
+    }
+    
+    /// P-value for byte transitions being independent of the previous byte
+    ///
+    /// Pearson's χ² test of independence on the
+    /// [`digraph_counts`](Self::digraph_counts) table: each count is
+    /// compared with `row × column / pairs`, its expectation if the next
+    /// byte did not depend on the current one, given the observed byte
+    /// frequencies. This catches sources whose bytes are individually
+    /// uniform but whose pairs are not, which the byte-level
+    /// [`chi_square`](Self::chi_square) cannot see. Degrees of freedom are
+    /// (r − 1)(c − 1) over the byte values that occur.
+    ///
+    /// The approximation wants about five expected pairs per cell, so use a
+    /// few hundred kilobytes for uniform data. Returns `None` for fewer than
+    /// two bytes or when only one value leads or follows (no degrees of
+    /// freedom).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// // Every value equally common, but each byte predicts the next
+    /// let counter: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+    /// assert!(QualityMetrics::chi_square(&counter).unwrap() < 1.0);
+    /// assert!(QualityMetrics::digraph_chi_square(&counter).unwrap() < 0.01);
+    /// ```
+    pub fn digraph_chi_square(data: &[u8]) -> Option<f64> {
+        if data.len() < 2 {
+            return None;
+        }
+        
+        let counts = Self::digraph_counts(data);
+        let mut rows = [0u64; 256];
+        let mut columns = [0u64; 256];
+        for (cell, &count) in counts.iter().enumerate() {
+            rows[cell >> 8] += count as u64;
+            columns[cell & 0xFF] += count as u64;
+        }
+        
+        let occupied = |totals: &[u64; 256]| totals.iter().filter(|&&t| t > 0).count();
+        let df = (occupied(&rows) - 1) * (occupied(&columns) - 1);
+        if df == 0 {
+            return None;
+        }
+        
+        let pairs = (data.len() - 1) as f64;
+        let mut chi_sq = 0.0;
+        for (a, &row) in rows.iter().enumerate().filter(|(_, &row)| row > 0) {
+            for (b, &column) in columns.iter().enumerate().filter(|(_, &column)| column > 0) {
+                let expected = row as f64 * column as f64 / pairs;
+                let diff = counts[a << 8 | b] as f64 - expected;
+                chi_sq += diff * diff / expected;
+            }
+        }
+        
+        let dist = ChiSquared::new(df as f64).ok()?;
+        Some(NistTests::finalize_pvalue(1.0 - dist.cdf(chi_sq)))
+    }
+    
+    /// Calculate Shannon entropy over sliding windows
+    ///
+    /// Returns one entropy value (bits per byte) per window, starting at
+    /// offsets `0, step, 2 * step, ...`. Useful for spotting a source that
+    /// degrades partway through a capture. The last window may be shorter
+    /// than `window` if the data does not divide evenly; windows stop once
+    /// one reaches the end of the data or the next would start past it. If
+    /// `window` is larger than the data, a single value covering all of it
+    /// is returned.
+    ///
+    /// Returns an empty vector if `data` is empty or `window`/`step` is zero.
+    pub fn windowed_entropy(data: &[u8], window: usize, step: usize) -> Vec<f64> {
+        if data.is_empty() || window == 0 || step == 0 {
+            return Vec::new();
+        }
+        
+        let mut result = Vec::new();
+        let mut start = 0;
+        
+        loop {
+            let end = (start + window).min(data.len());
+            let slice = &data[start..end];
+            result.push(Self::shannon_from_histogram(&Self::byte_histogram_fast(slice), slice.len()));
+            
+            start += step;
+            if end == data.len() || start >= data.len() {
+                break;
+            }
+        }
+        
+        result
+    }
+    
     /// Estimate min-entropy (conservative bound)
     ///
     /// Min-entropy is based on the most probable outcome.
     /// It provides a conservative estimate of entropy quality.
     ///
     /// Formula: H_∞(X) = -log₂(max_i p(x_i))
-    pub fn min_entropy(data: &[u8]) -> f64 {
+    ///
+    /// Returns `None` for empty input; `Some(0.0)` means constant data.
+    pub fn min_entropy(data: &[u8]) -> Option<f64> {
         if data.is_empty() {
-            return 0.0;
+            return None;
         }
         
-        let mut freq = HashMap::new();
-        for &byte in data {
-            *freq.entry(byte).or_insert(0) += 1;
+        Some(Self::min_entropy_from_histogram(&Self::byte_histogram_fast(data), data.len()))
+    }
+    
+    fn min_entropy_from_histogram(freq: &[usize; 256], total: usize) -> f64 {
+        if total == 0 {
+            return 0.0;
         }
         
-        let max_freq = freq.values().max().copied().unwrap_or(0);
-        let total = data.len() as f64;
+        let max_freq = freq.iter().max().copied().unwrap_or(0);
         
         if max_freq > 0 {
-            -(max_freq as f64 / total).log2()
+            -(max_freq as f64 / total as f64).log2()
         } else {
             0.0
         }
@@ -90,22 +529,25 @@ impl QualityMetrics {
     /// Calculate chi-square statistic for uniformity
     ///
     /// Tests how well the byte distribution matches a uniform distribution.
-    /// Lower values indicate better uniformity.
-    pub fn chi_square(data: &[u8]) -> f64 {
+    /// Lower values indicate better uniformity. Returns `None` for empty
+    /// input; `Some(0.0)` means every byte value occurs equally often.
+    pub fn chi_square(data: &[u8]) -> Option<f64> {
         if data.is_empty() {
-            return 0.0;
+            return None;
         }
         
-        let mut freq = vec![0usize; 256];
-        for &byte in data {
-            freq[byte as usize] += 1;
+        Some(Self::chi_square_from_histogram(&Self::byte_histogram_fast(data), data.len()))
+    }
+    
+    fn chi_square_from_histogram(freq: &[usize; 256], total: usize) -> f64 {
+        if total == 0 {
+            return 0.0;
         }
         
-        let n = data.len() as f64;
-        let expected = n / 256.0;
+        let expected = total as f64 / 256.0;
         
         let mut chi_sq = 0.0;
-        for &count in &freq {
+        for &count in freq {
             let diff = count as f64 - expected;
             chi_sq += diff * diff / expected;
         }
@@ -113,119 +555,756 @@ impl QualityMetrics {
         chi_sq
     }
     
+    /// Convert a chi-square statistic into a p-value
+    ///
+    /// Uses the chi-square distribution with 255 degrees of freedom (256
+    /// byte values minus one).
+    pub fn chi_square_p_value(chi_square: f64) -> f64 {
+        if let Ok(dist) = ChiSquared::new(255.0) {
+            NistTests::finalize_pvalue(1.0 - dist.cdf(chi_square))
+        } else {
+            0.0
+        }
+    }
+    
+    /// Chi-square statistic against an arbitrary byte distribution
+    ///
+    /// Like [`chi_square`](Self::chi_square), but compares the byte counts
+    /// with `expected[v]`, the target probability of value `v`, for
+    /// sources meant to be non-uniform (e.g. samplers). `expected` is
+    /// normalized, so relative weights work too. Values with zero expected
+    /// probability are skipped, unless they occur, which makes the
+    /// statistic infinite. Returns `None` for empty data or if `expected`
+    /// has a negative or non-finite entry or sums to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// // A fair coin over bytes 0 and 1
+    /// let mut expected = [0.0; 256];
+    /// expected[0] = 1.0;
+    /// expected[1] = 1.0;
+    ///
+    /// let flips = [0u8, 1, 1, 0, 1, 0, 0, 1];
+    /// assert_eq!(QualityMetrics::chi_square_against(&flips, &expected), Some(0.0));
+    /// assert!(QualityMetrics::chi_square_against_p_value(&flips, &expected).unwrap() > 0.99);
+    /// ```
+    pub fn chi_square_against(data: &[u8], expected: &[f64; 256]) -> Option<f64> {
+        Self::chi_square_against_histogram(&Self::byte_histogram_fast(data), data.len(), expected)
+            .map(|(chi_sq, _)| chi_sq)
+    }
+    
+    /// P-value of [`chi_square_against`](Self::chi_square_against)
+    ///
+    /// Degrees of freedom are the number of values with non-zero expected
+    /// probability minus one. Returns `None` where `chi_square_against`
+    /// does, and if fewer than two values are possible. A value the target
+    /// rules out scores `Some(0.0)`.
+    pub fn chi_square_against_p_value(data: &[u8], expected: &[f64; 256]) -> Option<f64> {
+        let (chi_sq, categories) =
+            Self::chi_square_against_histogram(&Self::byte_histogram_fast(data), data.len(), expected)?;
+        if chi_sq.is_infinite() {
+            return Some(0.0);
+        }
+        if categories < 2 {
+            return None;
+        }
+        
+        let dist = ChiSquared::new((categories as f64) - 1.0).ok()?;
+        Some(NistTests::finalize_pvalue(1.0 - dist.cdf(chi_sq)))
+    }
+    
+    // Statistic and number of possible values, or None for invalid input
+    fn chi_square_against_histogram(freq: &[usize; 256], total: usize, expected: &[f64; 256]) -> Option<(f64, usize)> {
+        let weight: f64 = expected.iter().sum();
+        if total == 0 || !weight.is_finite() || weight <= 0.0 || expected.iter().any(|&p| !p.is_finite() || p < 0.0) {
+            return None;
+        }
+        
+        let mut chi_sq = 0.0;
+        let mut categories = 0;
+        for (&count, &p) in freq.iter().zip(expected) {
+            if p == 0.0 {
+                if count > 0 {
+                    chi_sq = f64::INFINITY;
+                }
+                continue;
+            }
+            
+            categories += 1;
+            let expected_count = total as f64 * p / weight;
+            let diff = count as f64 - expected_count;
+            chi_sq += diff * diff / expected_count;
+        }
+        
+        Some((chi_sq, categories))
+    }
+    
     /// Calculate mean byte value
-    pub fn mean(data: &[u8]) -> f64 {
+    ///
+    /// Returns `None` for empty input.
+    pub fn mean(data: &[u8]) -> Option<f64> {
         if data.is_empty() {
-            return 0.0;
+            return None;
         }
         
         let sum: u64 = data.iter().map(|&b| b as u64).sum();
-        sum as f64 / data.len() as f64
+        Some(sum as f64 / data.len() as f64)
     }
     
-    /// Find longest run of identical bits
-    pub fn longest_run(data: &[u8]) -> usize {
+    /// Median byte value
+    ///
+    /// Returns the lower median for an even number of bytes, and `None`
+    /// for empty input.
+    pub fn median_byte(data: &[u8]) -> Option<u8> {
         if data.is_empty() {
-            return 0;
+            return None;
         }
         
-        let mut max_run = 0;
-        let mut current_run = 1;
-        let mut last_bit = (data[0] >> 7) & 1;
+        Some(Self::median_from_histogram(&Self::byte_histogram_fast(data), data.len()))
+    }
+    
+    fn median_from_histogram(freq: &[usize; 256], total: usize) -> u8 {
+        if total == 0 {
+            return 0;
+        }
         
-        for &byte in data {
-            for i in (0..8).rev() {
-                let bit = (byte >> i) & 1;
-                if bit == last_bit {
-                    current_run += 1;
-                    max_run = max_run.max(current_run);
-                } else {
-                    current_run = 1;
-                    last_bit = bit;
-                }
+        // Walk the counts up to the middle element of the sorted data
+        let target = (total - 1) / 2;
+        let mut seen = 0;
+        for (value, &count) in freq.iter().enumerate() {
+            seen += count;
+            if seen > target {
+                return value as u8;
             }
         }
         
-        max_run
+        255
     }
     
-    /// Analyze entropy source quality
+    /// Frequency of one byte value relative to the uniform 1/256
     ///
-    /// Generates a full quality report by sampling the entropy source.
+    /// 1.0 means the value appears exactly as often as expected for
+    /// uniform data, 0.0 that it never appears. Returns `None` for empty
+    /// input.
+    pub fn byte_fraction(data: &[u8], value: u8) -> Option<f64> {
+        if data.is_empty() {
+            return None;
+        }
+        
+        let count = data.iter().filter(|&&b| b == value).count();
+        Some(count as f64 / data.len() as f64 * 256.0)
+    }
+    
+    /// Count how often each byte value occurs
     ///
-    /// # Arguments
+    /// Straightforward single-pass count; see
+    /// [`byte_histogram_fast`](Self::byte_histogram_fast) for large samples.
+    pub fn byte_histogram(data: &[u8]) -> [usize; 256] {
+        let mut freq = [0usize; 256];
+        for &byte in data {
+            freq[byte as usize] += 1;
+        }
+        freq
+    }
+    
+    /// Count how often each byte value occurs, optimized for large samples
     ///
-    /// * `source` - Entropy source to analyze
-    /// * `sample_size` - Number of bytes to sample
+    /// Same result as [`byte_histogram`](Self::byte_histogram). Runs of a
+    /// repeated byte make the scalar loop stall on increments of a single
+    /// counter; reading eight bytes at a time and spreading them over four
+    /// independent counter tables breaks that dependency chain. On uniform
+    /// data both paths run at about the same speed. Inputs below a few KiB
+    /// take the scalar path, where merging the tables would dominate.
     ///
     /// # Examples
     ///
     /// ```
-    /// use entropy_forge::entropy::SystemEntropy;
     /// use entropy_forge::quality::QualityMetrics;
     ///
-    /// let mut source = SystemEntropy::new();
-    /// let metrics = QualityMetrics::analyze(&mut source, 100_000);
-    ///
-    /// println!("Shannon Entropy: {:.4} bits/byte", metrics.shannon_entropy);
-    /// println!("Min-Entropy: {:.4} bits/byte", metrics.min_entropy);
+    /// let data = vec![7u8; 1_000_000];
+    /// assert_eq!(QualityMetrics::byte_histogram_fast(&data)[7], 1_000_000);
     /// ```
-    pub fn analyze<E: ?Sized + EntropySource>(source: &mut E, sample_size: usize) -> Self {
-        let mut data = vec![0u8; sample_size];
-        source.fill_bytes(&mut data);
+    pub fn byte_histogram_fast(data: &[u8]) -> [usize; 256] {
+        const SCALAR_BELOW: usize = 4 * 1024;
+        
+        if data.len() < SCALAR_BELOW {
+            return Self::byte_histogram(data);
+        }
         
-        let shannon = Self::shannon_entropy(&data);
-        let min_ent = Self::min_entropy(&data);
-        let chi_sq = Self::chi_square(&data);
-        let mean_val = Self::mean(&data);
-        let longest = Self::longest_run(&data);
+        // u32 tables stay in L1; a block can't overflow them
+        const BLOCK: usize = 1 << 30;
+        let mut freq = [0usize; 256];
         
-        let mut freq = HashMap::new();
-        for &byte in &data {
-            *freq.entry(byte).or_insert(0) += 1;
+        for block in data.chunks(BLOCK) {
+            let mut tables = [[0u32; 256]; 4];
+            let mut words = block.chunks_exact(8);
+            for word in &mut words {
+                let word = u64::from_le_bytes(word.try_into().unwrap());
+                for i in 0..8 {
+                    tables[i % 4][(word >> (8 * i)) as u8 as usize] += 1;
+                }
+            }
+            for &byte in words.remainder() {
+                tables[0][byte as usize] += 1;
+            }
+            
+            for table in &tables {
+                for (total, &count) in freq.iter_mut().zip(table) {
+                    *total += count as usize;
+                }
+            }
         }
+        freq
+    }
+    
+    /// Find longest run of identical bits
+    ///
+    /// This is the largest run length with a non-zero count in
+    /// [`run_length_histogram`](Self::run_length_histogram).
+    pub fn longest_run(data: &[u8]) -> usize {
+        Self::run_length_histogram(data).len().saturating_sub(1)
+    }
+    
+    /// Count runs of identical bits by length, for zeros and ones combined
+    ///
+    /// Index `n` holds the number of maximal runs of exactly `n` identical
+    /// bits (most significant bit first, runs continue across byte
+    /// boundaries). Index 0 is always 0 and the vector ends at the longest
+    /// run, so it is empty for empty input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// // 11001100: runs of 2, 2, 2, 2
+    /// let histogram = QualityMetrics::run_length_histogram(&[0b1100_1100]);
+    /// assert_eq!(histogram, vec![0, 0, 4]);
+    /// ```
+    pub fn run_length_histogram(data: &[u8]) -> Vec<usize> {
+        let [zeros, ones] = Self::run_length_histogram_by_bit(data);
+        let mut combined = vec![0; zeros.len().max(ones.len())];
         
-        Self {
-            shannon_entropy: shannon,
-            min_entropy: min_ent,
-            byte_frequency: freq,
-            total_bytes: sample_size,
-            chi_square: chi_sq,
-            mean: mean_val,
-            longest_run: longest,
+        for (len, count) in zeros.iter().enumerate().chain(ones.iter().enumerate()) {
+            combined[len] += count;
         }
+        
+        combined
     }
     
-    /// Get a quality score (0-100)
+    /// Count runs of identical bits by length, separately for each bit value
     ///
-    /// Combines multiple metrics into a single score.
-    /// 100 is perfect, 0 is worst.
-    pub fn overall_score(&self) -> f64 {
-        // Shannon entropy score (0-100)
-        let shannon_score = (self.shannon_entropy / 8.0) * 100.0;
+    /// Returns `[zeros, ones]`, each laid out like
+    /// [`run_length_histogram`](Self::run_length_histogram). A source that
+    /// sticks at one value shows up as long runs on only one side.
+    pub fn run_length_histogram_by_bit(data: &[u8]) -> [Vec<usize>; 2] {
+        let mut histograms = [Vec::new(), Vec::new()];
+        let mut bits = BitOrder::MsbFirst.iter(data);
+        let Some(mut current_bit) = bits.next() else {
+            return histograms;
+        };
         
-        // Min-entropy score (0-100)
-        let min_entropy_score = (self.min_entropy / 8.0) * 100.0;
+        let mut record = |bit: u8, len: usize| {
+            let histogram: &mut Vec<usize> = &mut histograms[bit as usize];
+            if histogram.len() <= len {
+                histogram.resize(len + 1, 0);
+            }
+            histogram[len] += 1;
+        };
         
-        // Mean score (how close to 127.5)
-        let mean_diff = (self.mean - 127.5).abs();
-        let mean_score = ((127.5 - mean_diff) / 127.5) * 100.0;
+        let mut current_run = 1;
         
-        // Weighted average
-        shannon_score * 0.5 + min_entropy_score * 0.3 + mean_score * 0.2
+        for bit in bits {
+            if bit == current_bit {
+                current_run += 1;
+            } else {
+                record(current_bit, current_run);
+                current_bit = bit;
+                current_run = 1;
+            }
+        }
+        record(current_bit, current_run);
+        
+        histograms
     }
-}
+    
+    /// Bit-level autocorrelation at lags `1..=max_lag`
+    ///
+    /// Entry `k - 1` is the sample autocorrelation between bit `i` and bit
+    /// `i + k` (most significant bit first), in [-1, 1]. Near zero for
+    /// random data; a peak at lag `k` reveals a period-`k` structure, the
+    /// time-domain counterpart of the DFT test. Lags with no overlapping
+    /// pairs, and all lags of constant data, are 0.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// // 0x0F repeats every 8 bits and is inverted at a 4-bit shift
+    /// let profile = QualityMetrics::autocorrelation_profile(&[0x0F; 100], 8);
+    /// assert!(profile[3] < -0.9);
+    /// assert!(profile[7] > 0.9);
+    /// ```
+    pub fn autocorrelation_profile(data: &[u8], max_lag: usize) -> Vec<f64> {
+        let bits = BitOrder::MsbFirst.extract(data);
+        let n = bits.len();
+        if n == 0 {
+            return vec![0.0; max_lag];
+        }
+        
+        let mean = bits.iter().map(|&b| b as f64).sum::<f64>() / n as f64;
+        let centered: Vec<f64> = bits.iter().map(|&b| b as f64 - mean).collect();
+        let variance: f64 = centered.iter().map(|x| x * x).sum();
+        
+        (1..=max_lag)
+            .map(|lag| {
+                if variance == 0.0 || lag >= n {
+                    return 0.0;
+                }
+                let covariance: f64 = centered.iter()
+                    .zip(&centered[lag..])
+                    .map(|(a, b)| a * b)
+                    .sum();
+                covariance / variance
+            })
+            .collect()
+    }
+    
+    /// Continuous periodicity measure from the bit spectrum
+    ///
+    /// Maps the bits (most significant first) to ±1, takes their discrete
+    /// Fourier transform, and divides the highest power among the non-zero
+    /// frequencies by the mean power. For noise that ratio still grows
+    /// with the sample, so it is normalized by its expected value for
+    /// random bits. Near 1.0 for random data; much larger when one period
+    /// dominates. Unlike the pass/fail DFT test, this ranks sources.
+    ///
+    /// Uses at most the first 2^20 bits. Returns `None` for fewer than
+    /// 1,000 bits or constant bits, which have no spectrum to compare.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// // A 16-bit period concentrates the spectrum in a few peaks
+    /// let pattern: Vec<u8> = [0x3C, 0xA5].repeat(500);
+    /// assert!(QualityMetrics::periodicity_score(&pattern).unwrap() > 10.0);
+    /// ```
+    #[cfg(feature = "fft")]
+    pub fn periodicity_score(data: &[u8]) -> Option<f64> {
+        use rustfft::{num_complex::Complex, FftPlanner};
+        
+        const MAX_BITS: usize = 1 << 20;
+        let bits = BitOrder::MsbFirst.extract(&data[..data.len().min(MAX_BITS / 8)]);
+        let n = bits.len();
+        if n < 1_000 || bits.iter().all(|&b| b == bits[0]) {
+            return None;
+        }
+        
+        let mut spectrum: Vec<Complex<f64>> = bits.iter()
+            .map(|&b| Complex::new(if b == 1 { 1.0 } else { -1.0 }, 0.0))
+            .collect();
+        FftPlanner::new().plan_fft_forward(n).process(&mut spectrum);
+        
+        // Skip the DC term; the upper half mirrors the lower for real input
+        let powers: Vec<f64> = spectrum[1..=n / 2].iter().map(|c| c.norm_sqr()).collect();
+        let mean = powers.iter().sum::<f64>() / powers.len() as f64;
+        let peak = powers.iter().copied().fold(0.0, f64::max);
+        
+        // Noise powers are roughly exponential, so the expected peak-to-mean
+        // ratio over m frequencies is the harmonic number H(m) ≈ ln m + γ
+        let m = powers.len() as f64;
+        let expected_ratio = m.ln() + 0.577_215_664_901_532_9 + 0.5 / m;
+        Some(peak / mean / expected_ratio)
+    }
+    
+    /// Analyze entropy source quality
+    ///
+    /// Generates a full quality report by sampling the entropy source.
+    /// For finite sources, [`try_analyze`](Self::try_analyze) refuses
+    /// samples larger than the data left.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - Entropy source to analyze
+    /// * `sample_size` - Number of bytes to sample
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::SystemEntropy;
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// let mut source = SystemEntropy::new();
+    /// let metrics = QualityMetrics::analyze(&mut source, 100_000);
+    ///
+    /// println!("Shannon Entropy: {:.4} bits/byte", metrics.shannon_entropy);
+    /// println!("Min-Entropy: {:.4} bits/byte", metrics.min_entropy);
+    /// ```
+    pub fn analyze<E: ?Sized + EntropySource>(source: &mut E, sample_size: usize) -> Self {
+        Self::analyze_capturing(source, sample_size).0
+    }
+    
+    /// Analyze a source and also return the sampled bytes
+    ///
+    /// Same as [`analyze`](Self::analyze), but keeps the sample so a
+    /// failing run can be inspected, saved, or re-checked with
+    /// [`from_bytes`](Self::from_bytes).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::SystemEntropy;
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// let (metrics, sample) = QualityMetrics::analyze_capturing(&mut SystemEntropy::new(), 10_000);
+    ///
+    /// if metrics.chi_square_p_value < 0.01 {
+    ///     println!("failing sample starts with {:02x?}", &sample[..16]);
+    /// }
+    /// ```
+    pub fn analyze_capturing<E: ?Sized + EntropySource>(source: &mut E, sample_size: usize) -> (Self, Vec<u8>) {
+        let mut data = vec![0u8; sample_size];
+        source.fill_bytes(&mut data);
+        
+        (Self::from_bytes(&data), data)
+    }
+    
+    /// Analyze a source, sampling into a caller-provided buffer
+    ///
+    /// Same as [`analyze`](Self::analyze), but reuses `buffer` instead of
+    /// allocating a new sample on every call, which matters in monitoring
+    /// loops. The buffer is resized to `sample_size` and only reallocates
+    /// when its capacity is too small. Afterwards it holds the sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::SystemEntropy;
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// let mut source = SystemEntropy::new();
+    /// let mut buffer = Vec::new();
+    /// for _ in 0..3 {
+    ///     let metrics = QualityMetrics::analyze_into(&mut source, &mut buffer, 10_000);
+    ///     assert_eq!(metrics.total_bytes, buffer.len());
+    /// }
+    /// ```
+    pub fn analyze_into<E: ?Sized + EntropySource>(source: &mut E, buffer: &mut Vec<u8>, sample_size: usize) -> Self {
+        buffer.resize(sample_size, 0);
+        source.fill_bytes(buffer);
+        
+        Self::from_bytes(buffer)
+    }
+    
+    /// Analyze a source, refusing samples larger than it can supply
+    ///
+    /// Same as [`analyze`](Self::analyze), but first checks
+    /// [`remaining_bytes`](EntropySource::remaining_bytes) and returns
+    /// [`AnalyzeError::InsufficientData`] without reading anything if a
+    /// finite source would run dry. Unbounded sources always succeed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::RecordedEntropy;
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// let mut capture = RecordedEntropy::new(vec![0x5A; 1_000]);
+    /// assert!(QualityMetrics::try_analyze(&mut capture, 10_000).is_err());
+    /// assert!(QualityMetrics::try_analyze(&mut capture, 1_000).is_ok());
+    /// ```
+    pub fn try_analyze<E: ?Sized + EntropySource>(source: &mut E, sample_size: usize) -> Result<Self, AnalyzeError> {
+        if let Some(available) = source.remaining_bytes() {
+            if (sample_size as u64) > available {
+                return Err(AnalyzeError::InsufficientData { requested: sample_size, available });
+            }
+        }
+        
+        Ok(Self::analyze(source, sample_size))
+    }
+    
+    /// Analyze a trait-object entropy source
+    ///
+    /// Same as [`analyze`](Self::analyze), for callers holding
+    /// `Box<dyn EntropySource>` or `&mut dyn EntropySource`.
+    pub fn analyze_dyn(source: &mut dyn EntropySource, sample_size: usize) -> Self {
+        Self::analyze(source, sample_size)
+    }
+    
+    /// Compute all quality metrics over an existing sample
+    ///
+    /// Use this when the same bytes also feed other checks (e.g. the NIST
+    /// suite), so every result describes one sample. Empty input yields
+    /// 0.0 for every metric, including the chi-square p-value; check
+    /// [`total_bytes`](Self::total_bytes) to tell it apart from a real zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// let data: Vec<u8> = (0..=255).collect();
+    /// let metrics = QualityMetrics::from_bytes(&data);
+    ///
+    /// assert_eq!(metrics.total_bytes, 256);
+    /// assert_eq!(metrics.shannon_entropy, 8.0);
+    /// ```
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Self::from_bytes_with(data, MetricsOptions::default())
+    }
+    
+    /// Compute all quality metrics over the noise bytes of fixed-size frames
+    ///
+    /// For sources that wrap their randomness in structure, such as a
+    /// sequence counter or header in every record: `data` is cut into
+    /// `frame_len`-byte frames, bytes `noise_range` of each are
+    /// concatenated, and the result is analyzed like
+    /// [`from_bytes`](Self::from_bytes). The structural bytes would
+    /// otherwise drag the entropy down. A trailing partial frame is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `noise_range` is empty or does not fit in a frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// // 6-byte frames: a counter, then 5 noise bytes
+    /// let data = [[0u8, 0x3A, 0x91, 0x07, 0xC4, 0x5E], [1, 0xF2, 0x18, 0x6B, 0xA0, 0x2D]].concat();
+    /// let metrics = QualityMetrics::analyze_framed(&data, 6, 1..6);
+    ///
+    /// assert_eq!(metrics.total_bytes, 10);
+    /// ```
+    pub fn analyze_framed(data: &[u8], frame_len: usize, noise_range: Range<usize>) -> Self {
+        assert!(
+            noise_range.start < noise_range.end && noise_range.end <= frame_len,
+            "noise range {:?} must be non-empty and fit in a {}-byte frame", noise_range, frame_len
+        );
+        
+        let noise: Vec<u8> = data.chunks_exact(frame_len)
+            .flat_map(|frame| &frame[noise_range.clone()])
+            .copied()
+            .collect();
+        Self::from_bytes(&noise)
+    }
+    
+    /// Compute all quality metrics, plus the optional ones in `options`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::quality::{MetricsOptions, QualityMetrics};
+    ///
+    /// let data: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+    /// let metrics = QualityMetrics::from_bytes_with(&data, MetricsOptions { digraph: true, ..Default::default() });
+    ///
+    /// assert!(metrics.digraph_p_value.unwrap() < 0.01);
+    /// assert_eq!(QualityMetrics::from_bytes(&data).digraph_p_value, None);
+    /// ```
+    pub fn from_bytes_with(data: &[u8], options: MetricsOptions) -> Self {
+        // One histogram feeds every byte-level metric
+        let histogram = Self::byte_histogram_fast(data);
+        let total = data.len();
+        
+        let chi_sq = Self::chi_square_from_histogram(&histogram, total);
+        let fraction = |value: u8| {
+            if total == 0 {
+                0.0
+            } else {
+                histogram[value as usize] as f64 / total as f64 * 256.0
+            }
+        };
+        
+        let freq: HashMap<u8, usize> = histogram.iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(value, &count)| (value as u8, count))
+            .collect();
+        let min_byte = freq.keys().copied().min().unwrap_or(0);
+        let max_byte = freq.keys().copied().max().unwrap_or(0);
+        
+        Self {
+            shannon_entropy: Self::shannon_from_histogram(&histogram, total),
+            min_entropy: Self::min_entropy_from_histogram(&histogram, total),
+            conditional_entropy: if options.conditional { Self::conditional_entropy(data, 1) } else { None },
+            digraph_p_value: if options.digraph { Self::digraph_chi_square(data) } else { None },
+            byte_frequency: freq,
+            total_bytes: total,
+            chi_square: chi_sq,
+            chi_square_p_value: if total == 0 { 0.0 } else { Self::chi_square_p_value(chi_sq) },
+            mean: Self::mean(data).unwrap_or(0.0),
+            longest_run: Self::longest_run(data),
+            min_byte,
+            max_byte,
+            median_byte: Self::median_from_histogram(&histogram, total),
+            zero_byte_fraction: fraction(0x00),
+            max_byte_fraction: fraction(0xFF),
+            #[cfg(feature = "fft")]
+            periodicity_score: Self::periodicity_score(data),
+            #[cfg(not(feature = "fft"))]
+            periodicity_score: None,
+        }
+    }
+    
+    /// Classify the source into a [`SourceVerdict`]
+    ///
+    /// `nist_passed` and `nist_total` are the number of NIST tests that
+    /// passed (p ≥ 0.01) and that were run, e.g. from a
+    /// [`NistSummary`](crate::quality::NistSummary).
+    ///
+    /// Thresholds, checked from best to worst:
+    ///
+    /// | Verdict                | Shannon | Min-entropy | χ² p-value | NIST failures |
+    /// |------------------------|---------|-------------|------------|---------------|
+    /// | `CryptographicQuality` | ≥ 7.9   | ≥ 7.0       | ≥ 0.01     | 0             |
+    /// | `StatisticallyGood`    | ≥ 7.5   | ≥ 6.0       | ≥ 0.001    | ≤ 1           |
+    /// | `Suspicious`           | ≥ 6.0   | ≥ 3.0       | any        | ≤ half        |
+    /// | `Broken`               | otherwise                                        |
+    ///
+    /// Entropy values are in bits per byte. Small samples bias Shannon and
+    /// min-entropy downwards, so use at least 100,000 bytes for the top
+    /// verdict to be reachable.
+    pub fn classify(&self, nist_passed: usize, nist_total: usize) -> SourceVerdict {
+        let nist_failed = nist_total.saturating_sub(nist_passed);
+        
+        if self.shannon_entropy >= 7.9
+            && self.min_entropy >= 7.0
+            && self.chi_square_p_value >= 0.01
+            && nist_failed == 0
+        {
+            SourceVerdict::CryptographicQuality
+        } else if self.shannon_entropy >= 7.5
+            && self.min_entropy >= 6.0
+            && self.chi_square_p_value >= 0.001
+            && nist_failed <= 1
+        {
+            SourceVerdict::StatisticallyGood
+        } else if self.shannon_entropy >= 6.0
+            && self.min_entropy >= 3.0
+            && nist_failed * 2 <= nist_total
+        {
+            SourceVerdict::Suspicious
+        } else {
+            SourceVerdict::Broken
+        }
+    }
+    
+    /// Map each metric to 0–1, where 1 is ideal
+    ///
+    /// The raw metrics are left untouched; see [`NormalizedMetrics`] for
+    /// the mapping of each field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// let data: Vec<u8> = (0..=255).collect();
+    /// let normalized = QualityMetrics::from_bytes(&data).normalized();
+    ///
+    /// assert_eq!(normalized.shannon_entropy, 1.0);
+    /// ```
+    pub fn normalized(&self) -> NormalizedMetrics {
+        let unit = |x: f64| if x.is_finite() { x.clamp(0.0, 1.0) } else { 0.0 };
+        
+        NormalizedMetrics {
+            shannon_entropy: unit(self.shannon_entropy / 8.0),
+            min_entropy: unit(self.min_entropy / 8.0),
+            chi_square: unit(self.chi_square_p_value),
+            mean: unit(1.0 - (self.mean - 127.5).abs() / 127.5),
+        }
+    }
+    
+    /// Get a quality score (0-100)
+    ///
+    /// Combines multiple metrics into a single score.
+    /// 100 is perfect, 0 is worst.
+    pub fn overall_score(&self) -> f64 {
+        // Shannon entropy score (0-100)
+        let shannon_score = (self.shannon_entropy / 8.0) * 100.0;
+        
+        // Min-entropy score (0-100)
+        let min_entropy_score = (self.min_entropy / 8.0) * 100.0;
+        
+        // Mean score (how close to 127.5)
+        let mean_diff = (self.mean - 127.5).abs();
+        let mean_score = ((127.5 - mean_diff) / 127.5) * 100.0;
+        
+        // Weighted average
+        shannon_score * 0.5 + min_entropy_score * 0.3 + mean_score * 0.2
+    }
+    
+    /// Percentile rank (0-100) of this sample among known-good samples
+    ///
+    /// Places [`overall_score`](Self::overall_score) within
+    /// [`CALIBRATION_QUANTILES`](Self::CALIBRATION_QUANTILES), interpolating
+    /// linearly between them, so 87 means "scores better than 87% of
+    /// `SystemEntropy` samples". Scores below the worst reference sample
+    /// give 0 and above the best give 100.
+    ///
+    /// Only meaningful for samples of
+    /// [`CALIBRATION_SAMPLE_BYTES`](Self::CALIBRATION_SAMPLE_BYTES): smaller
+    /// samples have lower min-entropy and rank near 0 even from a perfect
+    /// source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use entropy_forge::entropy::SystemEntropy;
+    /// use entropy_forge::quality::QualityMetrics;
+    ///
+    /// let mut source = SystemEntropy::new();
+    /// let metrics = QualityMetrics::analyze(&mut source, QualityMetrics::CALIBRATION_SAMPLE_BYTES);
+    ///
+    /// println!("Better than {:.0}% of good samples", metrics.calibrated_score());
+    /// ```
+    pub fn calibrated_score(&self) -> f64 {
+        let quantiles = &Self::CALIBRATION_QUANTILES;
+        let score = self.overall_score();
+        
+        if !score.is_finite() || score <= quantiles[0] {
+            return 0.0;
+        }
+        if score >= quantiles[quantiles.len() - 1] {
+            return 100.0;
+        }
+        
+        // First quantile above the score; 1 ≤ upper < len
+        let upper = quantiles.partition_point(|&q| q <= score);
+        let (low, high) = (quantiles[upper - 1], quantiles[upper]);
+        let step = 100.0 / (quantiles.len() - 1) as f64;
+        
+        step * ((upper - 1) as f64 + (score - low) / (high - low))
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::entropy::{SystemEntropy, MockEntropy};
+    use crate::entropy::{SystemBackend, SystemEntropy};
+    use crate::quality::NistSummary;
+    
+    struct ZeroSource;
+    
+    impl EntropySource for ZeroSource {
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.fill(0);
+        }
+    }
     
     #[test]
     fn test_shannon_entropy_perfect() {
         // All different bytes -> high entropy
         let data: Vec<u8> = (0..=255).collect();
-        let entropy = QualityMetrics::shannon_entropy(&data);
+        let entropy = QualityMetrics::shannon_entropy(&data).unwrap();
         assert!(entropy > 7.9); // Should be close to 8.0
     }
     
@@ -234,23 +1313,227 @@ mod tests {
         // All same bytes -> zero entropy
         let data = vec![0u8; 256];
         let entropy = QualityMetrics::shannon_entropy(&data);
-        assert_eq!(entropy, 0.0);
+        assert_eq!(entropy, Some(0.0));
+    }
+    
+    #[test]
+    fn test_conditional_entropy() {
+        // Random walk: each byte is the previous plus 0..=3
+        let mut source = crate::entropy::ChaChaEntropy::new([9; 32]);
+        let mut steps = vec![0u8; 200_000];
+        source.fill_bytes(&mut steps);
+        let walk: Vec<u8> = steps.iter()
+            .scan(0u8, |byte, &step| {
+                *byte = byte.wrapping_add(step % 4);
+                Some(*byte)
+            })
+            .collect();
+        
+        let marginal = QualityMetrics::shannon_entropy(&walk).unwrap();
+        let conditional = QualityMetrics::conditional_entropy(&walk, 1).unwrap();
+        assert!(marginal > 7.9, "marginal {}", marginal);
+        assert!((conditional - 2.0).abs() < 0.05, "conditional {}", conditional);
+        assert!(QualityMetrics::conditional_entropy(&walk, 2).unwrap() <= conditional + 1e-9);
+        let options = MetricsOptions { conditional: true, ..Default::default() };
+        assert_eq!(QualityMetrics::from_bytes_with(&walk, options).conditional_entropy, Some(conditional));
+        assert_eq!(QualityMetrics::from_bytes(&walk).conditional_entropy, None);
+        
+        // Independent bytes lose little beyond the small-sample bias
+        let random = &steps[..];
+        assert!(QualityMetrics::conditional_entropy(random, 1).unwrap() > 7.5);
+        assert_eq!(QualityMetrics::conditional_entropy(random, 0), QualityMetrics::shannon_entropy(random));
+        
+        assert_eq!(QualityMetrics::conditional_entropy(random, 3), None);
+        assert_eq!(QualityMetrics::conditional_entropy(&[5], 1), None);
+        assert_eq!(QualityMetrics::conditional_entropy(&[5, 5], 1), Some(0.0));
+    }
+    
+    #[test]
+    fn test_block_entropy_sees_pair_structure() {
+        let mut random = vec![0u8; 2_000_000];
+        crate::entropy::ChaChaEntropy::new([8; 32]).fill_bytes(&mut random);
+        let single = QualityMetrics::shannon_entropy(&random).unwrap();
+        assert!((QualityMetrics::block_entropy(&random, 1).unwrap() - single).abs() < 1e-9);
+        assert!(QualityMetrics::block_entropy(&random, 2).unwrap() > 7.95);
+        
+        // A random byte followed by a fixed function of it
+        let motif: Vec<u8> = random[..500_000].iter().flat_map(|&b| [b, b ^ 0x5A]).collect();
+        assert!(QualityMetrics::shannon_entropy(&motif).unwrap() > 7.99);
+        let paired = QualityMetrics::block_entropy(&motif, 2).unwrap();
+        assert!((paired - 4.0).abs() < 0.01, "{}", paired);
+        
+        assert_eq!(QualityMetrics::block_entropy(&[1, 2, 3], 0), None);
+        assert_eq!(QualityMetrics::block_entropy(&[1, 2, 3], 4), None);
+        assert_eq!(QualityMetrics::block_entropy(&[7, 7, 7, 7, 9], 4), Some(0.0));
+    }
+    
+    #[test]
+    fn test_analyze_framed_skips_counter() {
+        // 6-byte frames: a 4-bit sequence number, then 5 random bytes
+        let mut entropy = crate::entropy::ChaChaEntropy::new([6; 32]);
+        let mut data = Vec::new();
+        for counter in 0..20_000u32 {
+            data.push((counter % 16) as u8);
+            let mut noise = [0u8; 5];
+            entropy.fill_bytes(&mut noise);
+            data.extend_from_slice(&noise);
+        }
+        data.extend_from_slice(&[0xEE; 3]); // partial frame
+        
+        let framed = QualityMetrics::analyze_framed(&data, 6, 1..6);
+        assert_eq!(framed.total_bytes, 100_000);
+        assert!(framed.shannon_entropy > 7.99, "{}", framed.shannon_entropy);
+        assert!(framed.chi_square_p_value >= 0.01);
+        
+        // A sixth of the stream only takes 16 values
+        let whole = QualityMetrics::from_bytes(&data);
+        assert!(whole.shannon_entropy < 7.9, "{}", whole.shannon_entropy);
+        assert!(whole.chi_square_p_value < 0.01);
+        let counter_only = QualityMetrics::analyze_framed(&data, 6, 0..1);
+        assert_eq!(counter_only.total_bytes, 20_000);
+        assert!(counter_only.min_entropy < framed.min_entropy);
+    }
+    
+    #[test]
+    #[should_panic(expected = "noise range")]
+    fn test_analyze_framed_rejects_bad_range() {
+        QualityMetrics::analyze_framed(&[0u8; 12], 6, 4..7);
+    }
+    
+    #[test]
+    fn test_digraph_catches_pair_structure() {
+        // Uniform bytes, but always an even byte followed by an odd one
+        let mut source = SystemEntropy::with_backend(SystemBackend::Deterministic(6));
+        let mut data = vec![0u8; 500_000];
+        source.fill_bytes(&mut data);
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = (*byte & 0xFE) | (i % 2) as u8;
+        }
+        
+        assert!(QualityMetrics::from_bytes(&data).chi_square_p_value >= 0.01);
+        assert!(QualityMetrics::digraph_chi_square(&data).unwrap() < 0.01);
+        
+        let counts = QualityMetrics::digraph_counts(&data);
+        assert_eq!(counts.iter().map(|&c| c as usize).sum::<usize>(), data.len() - 1);
+        assert_eq!(counts[0x02 << 8 | 0x04], 0);
+        
+        // Independent bytes pass
+        source.fill_bytes(&mut data);
+        let metrics = QualityMetrics::from_bytes_with(&data, MetricsOptions { digraph: true, ..Default::default() });
+        assert!(metrics.digraph_p_value.unwrap() >= 0.01, "{:?}", metrics.digraph_p_value);
+        assert_eq!(QualityMetrics::from_bytes(&data).digraph_p_value, None);
+        
+        assert_eq!(QualityMetrics::digraph_chi_square(&[7]), None);
+        assert_eq!(QualityMetrics::digraph_chi_square(&[7; 100]), None);
     }
     
     #[test]
     fn test_min_entropy() {
         let data = vec![1, 2, 3, 4, 5, 1, 1, 1];
-        let min_ent = QualityMetrics::min_entropy(&data);
+        let min_ent = QualityMetrics::min_entropy(&data).unwrap();
         // 4 out of 8 are '1', so max prob is 0.5
         // min_entropy = -log2(0.5) = 1.0
         assert!((min_ent - 1.0).abs() < 0.01);
     }
     
+    #[test]
+    fn test_run_length_histogram() {
+        let data = vec![0b1100_1100; 4];
+        let histogram = QualityMetrics::run_length_histogram(&data);
+        assert_eq!(histogram, vec![0, 0, 16]);
+        assert_eq!(QualityMetrics::longest_run(&data), 2);
+        
+        // Runs continue across byte boundaries: 8 ones, then 8 zeros
+        let [zeros, ones] = QualityMetrics::run_length_histogram_by_bit(&[0xFF, 0x00]);
+        assert_eq!(zeros, vec![0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(ones, zeros);
+        assert_eq!(QualityMetrics::longest_run(&[0xFF, 0x00]), 8);
+        
+        // 1 followed by seven 0s
+        assert_eq!(QualityMetrics::run_length_histogram(&[0x80]), vec![0, 1, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(QualityMetrics::longest_run(&[0x80]), 7);
+        
+        assert!(QualityMetrics::run_length_histogram(&[]).is_empty());
+        assert_eq!(QualityMetrics::longest_run(&[]), 0);
+    }
+    
+    #[test]
+    fn test_normalized() {
+        // Every byte value exactly 64 times: ideal on every axis
+        let perfect: Vec<u8> = (0..256 * 64).map(|i| (i % 256) as u8).collect();
+        let normalized = QualityMetrics::from_bytes(&perfect).normalized();
+        
+        for value in [normalized.shannon_entropy, normalized.min_entropy, normalized.chi_square, normalized.mean] {
+            assert!((value - 1.0).abs() < 1e-9, "{:?}", normalized);
+        }
+        
+        let zeros = QualityMetrics::from_bytes(&[0u8; 1_000]).normalized();
+        assert_eq!(zeros.shannon_entropy, 0.0);
+        assert_eq!(zeros.min_entropy, 0.0);
+        assert!(zeros.chi_square < 1e-6);
+        assert_eq!(zeros.mean, 0.0);
+    }
+    
+    #[test]
+    fn test_order_statistics() {
+        // A source that never emits 0xFF
+        struct NoMaxSource(SystemEntropy);
+        
+        impl EntropySource for NoMaxSource {
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.0.fill_bytes(dest);
+                for byte in dest.iter_mut() {
+                    *byte = (*byte).min(0xFE);
+                }
+            }
+        }
+        
+        let metrics = QualityMetrics::analyze(&mut NoMaxSource(SystemEntropy::new()), 10_000);
+        assert!(metrics.max_byte < 255);
+        assert_eq!(metrics.max_byte_fraction, 0.0);
+        
+        // 257 of 512 bytes are 0x00: ~128x the uniform share
+        let mut data = vec![0u8; 256];
+        data.extend(0..=255u8);
+        let metrics = QualityMetrics::from_bytes(&data);
+        assert_eq!(metrics.min_byte, 0);
+        assert_eq!(metrics.max_byte, 255);
+        assert_eq!(metrics.median_byte, 0);
+        assert!((metrics.zero_byte_fraction - 257.0 / 512.0 * 256.0).abs() < 1e-9);
+        assert!((metrics.max_byte_fraction - 0.5).abs() < 1e-9);
+        
+        assert_eq!(QualityMetrics::median_byte(&[1, 9, 3, 7]), Some(3));
+        assert_eq!(QualityMetrics::median_byte(&[5, 1, 9]), Some(5));
+        assert_eq!(QualityMetrics::from_bytes(&[]).median_byte, 0);
+    }
+    
     #[test]
     fn test_mean() {
         let data = vec![0, 128, 255];
         let mean = QualityMetrics::mean(&data);
-        assert_eq!(mean, (0.0 + 128.0 + 255.0) / 3.0);
+        assert_eq!(mean, Some((0.0 + 128.0 + 255.0) / 3.0));
+    }
+    
+    #[test]
+    fn test_no_data_is_distinct_from_zero() {
+        // Empty input has no answer
+        assert_eq!(QualityMetrics::shannon_entropy(&[]), None);
+        assert_eq!(QualityMetrics::min_entropy(&[]), None);
+        assert_eq!(QualityMetrics::chi_square(&[]), None);
+        assert_eq!(QualityMetrics::mean(&[]), None);
+        assert_eq!(QualityMetrics::median_byte(&[]), None);
+        assert_eq!(QualityMetrics::byte_fraction(&[], 0), None);
+        assert_eq!(QualityMetrics::shannon_entropy_symbols(&[], 4), None);
+        
+        // Real data can legitimately score zero
+        let all_values: Vec<u8> = (0..=255).collect();
+        assert_eq!(QualityMetrics::shannon_entropy(&[7; 100]), Some(0.0));
+        assert_eq!(QualityMetrics::min_entropy(&[7; 100]), Some(0.0));
+        assert_eq!(QualityMetrics::chi_square(&all_values), Some(0.0));
+        assert_eq!(QualityMetrics::mean(&[0, 0]), Some(0.0));
+        assert_eq!(QualityMetrics::median_byte(&[0]), Some(0));
+        assert_eq!(QualityMetrics::byte_fraction(&[1, 2], 0), Some(0.0));
+        assert_eq!(QualityMetrics::shannon_entropy_symbols(&[0x00], 4), Some(0.0));
     }
     
     #[test]
@@ -264,6 +1547,25 @@ mod tests {
         assert!(metrics.mean > 100.0 && metrics.mean < 155.0);
     }
     
+    #[test]
+    fn test_analyze_dyn_over_boxed_sources() {
+        use crate::entropy::{ChaChaEntropy, MockEntropy};
+        
+        let mut sources: Vec<Box<dyn EntropySource>> = vec![
+            Box::new(MockEntropy::new(1)),
+            Box::new(ChaChaEntropy::new([0; 32])),
+            Box::new(ZeroSource),
+        ];
+        
+        let metrics: Vec<QualityMetrics> = sources.iter_mut()
+            .map(|source| QualityMetrics::analyze_dyn(source.as_mut(), 10_000))
+            .collect();
+        
+        assert!(metrics.iter().all(|m| m.total_bytes == 10_000));
+        assert!(metrics[1].shannon_entropy > 7.9);
+        assert_eq!(metrics[2].shannon_entropy, 0.0);
+    }
+    
     #[test]
     fn test_overall_score() {
         let mut source = SystemEntropy::new();
@@ -273,4 +1575,267 @@ mod tests {
         assert!(score > 80.0); // System RNG should score well
         assert!(score <= 100.0);
     }
+    
+    #[test]
+    fn test_analyze_capturing_reproduces() {
+        let (metrics, sample) = QualityMetrics::analyze_capturing(&mut SystemEntropy::new(), 20_000);
+        
+        assert_eq!(sample.len(), 20_000);
+        assert_eq!(QualityMetrics::from_bytes(&sample), metrics);
+    }
+    
+    #[test]
+    fn test_analyze_into_reuses_buffer() {
+        let mut source = crate::entropy::ChaChaEntropy::new([8; 32]);
+        let mut buffer = Vec::new();
+        
+        let first = QualityMetrics::analyze_into(&mut source, &mut buffer, 20_000);
+        assert_eq!(first, QualityMetrics::from_bytes(&buffer));
+        let capacity = buffer.capacity();
+        let pointer = buffer.as_ptr();
+        
+        // The second sample continues the stream in the same allocation
+        let second = QualityMetrics::analyze_into(&mut source, &mut buffer, 20_000);
+        assert_eq!(second, QualityMetrics::from_bytes(&buffer));
+        assert_ne!(second, first);
+        assert_eq!((buffer.capacity(), buffer.as_ptr()), (capacity, pointer));
+        
+        source.reset();
+        let mut expected = vec![0u8; 40_000];
+        source.fill_bytes(&mut expected);
+        assert_eq!(buffer, expected[20_000..]);
+        
+        // Smaller samples shrink the length but keep the allocation
+        let small = QualityMetrics::analyze_into(&mut source, &mut buffer, 1_000);
+        assert_eq!(small.total_bytes, 1_000);
+        assert_eq!(buffer.capacity(), capacity);
+    }
+    
+    #[test]
+    fn test_fast_histogram_matches_scalar() {
+        let mut random = vec![0u8; 100_003];
+        SystemEntropy::new().fill_bytes(&mut random);
+        
+        let constant = vec![0xABu8; 50_001];
+        let ramp: Vec<u8> = (0..70_000u32).map(|i| (i / 4) as u8).collect();
+        let stride: Vec<u8> = (0..40_000u32).map(|i| if i % 4 == 3 { 0xFF } else { 0 }).collect();
+        
+        for data in [&random[..], &random[..5], &constant, &ramp, &stride, &[]] {
+            let fast = QualityMetrics::byte_histogram_fast(data);
+            assert_eq!(fast, QualityMetrics::byte_histogram(data));
+            assert_eq!(fast.iter().sum::<usize>(), data.len());
+        }
+    }
+    
+    #[test]
+    fn test_calibrated_score() {
+        let broken = QualityMetrics::analyze(&mut ZeroSource, QualityMetrics::CALIBRATION_SAMPLE_BYTES);
+        assert_eq!(broken.calibrated_score(), 0.0);
+        
+        let mut source = crate::entropy::ChaChaEntropy::new([4; 32]);
+        let good = QualityMetrics::analyze(&mut source, QualityMetrics::CALIBRATION_SAMPLE_BYTES);
+        let rank = good.calibrated_score();
+        assert!(rank > 0.0 && rank < 100.0, "rank {}", rank);
+        
+        // The reference median ranks at the 50th percentile
+        let median = QualityMetrics {
+            shannon_entropy: 8.0,
+            min_entropy: 0.0,
+            mean: 127.5,
+            ..QualityMetrics::from_bytes(&[])
+        };
+        let needed = (QualityMetrics::CALIBRATION_QUANTILES[10] - median.overall_score()) / 30.0 * 8.0;
+        let median = QualityMetrics { min_entropy: needed, ..median };
+        assert!((median.calibrated_score() - 50.0).abs() < 1e-6);
+    }
+    
+    #[test]
+    fn test_chi_square_p_value() {
+        let mut source = SystemEntropy::with_backend(SystemBackend::Deterministic(1));
+        let metrics = QualityMetrics::analyze(&mut source, 10_000);
+        assert!(metrics.chi_square_p_value > 0.01);
+        
+        let degenerate = QualityMetrics::analyze(&mut ZeroSource, 10_000);
+        assert!(degenerate.chi_square_p_value < 1e-6);
+    }
+    
+    #[test]
+    fn test_chi_square_against_custom_target() {
+        // Triangular target: value v is (v + 1) times as likely as value 0
+        let mut expected = [0.0; 256];
+        for (v, p) in expected.iter_mut().enumerate() {
+            *p = (v + 1) as f64;
+        }
+        
+        // Sample it by inverting the CDF over uniform draws
+        let total: f64 = expected.iter().sum();
+        let mut cdf = [0.0; 256];
+        let mut acc = 0.0;
+        for (c, p) in cdf.iter_mut().zip(&expected) {
+            acc += p / total;
+            *c = acc;
+        }
+        let mut source = SystemEntropy::with_backend(SystemBackend::Deterministic(3));
+        let sampled: Vec<u8> = (0..100_000)
+            .map(|_| {
+                let u = source.next_u64() as f64 / u64::MAX as f64;
+                cdf.iter().position(|&c| u <= c).unwrap_or(255) as u8
+            })
+            .collect();
+        
+        assert!(QualityMetrics::chi_square_against_p_value(&sampled, &expected).unwrap() > 0.01);
+        assert!(QualityMetrics::chi_square_p_value(QualityMetrics::chi_square(&sampled).unwrap()) < 1e-6);
+        
+        // Uniform data fails the triangular target
+        let mut uniform = vec![0u8; 100_000];
+        source.fill_bytes(&mut uniform);
+        assert!(QualityMetrics::chi_square_against_p_value(&uniform, &expected).unwrap() < 1e-6);
+        
+        // A flat target reproduces the uniform statistic
+        let flat = [1.0; 256];
+        let diff = QualityMetrics::chi_square_against(&uniform, &flat).unwrap() - QualityMetrics::chi_square(&uniform).unwrap();
+        assert!(diff.abs() < 1e-6);
+        
+        // Impossible values and invalid targets
+        let mut only_zero = [0.0; 256];
+        only_zero[0] = 1.0;
+        assert_eq!(QualityMetrics::chi_square_against(&[0, 1], &only_zero), Some(f64::INFINITY));
+        assert_eq!(QualityMetrics::chi_square_against_p_value(&[0, 1], &only_zero), Some(0.0));
+        assert_eq!(QualityMetrics::chi_square_against_p_value(&[0, 0], &only_zero), None);
+        assert_eq!(QualityMetrics::chi_square_against(&uniform, &[0.0; 256]), None);
+        assert_eq!(QualityMetrics::chi_square_against(&[], &flat), None);
+    }
+    
+    #[test]
+    fn test_classify() {
+        let mut source = SystemEntropy::with_backend(SystemBackend::Deterministic(1));
+        let metrics = QualityMetrics::analyze(&mut source, 100_000);
+        
+        let mut data = vec![0u8; 100_000];
+        source.fill_bytes(&mut data);
+        let summary = NistSummary::from_results(NistTests::run_all_tests(&data));
+        
+        assert!(matches!(
+            metrics.classify(summary.passed, summary.total()),
+            SourceVerdict::CryptographicQuality | SourceVerdict::StatisticallyGood
+        ));
+        
+        let zeros = vec![0u8; 100_000];
+        let degenerate = QualityMetrics::analyze(&mut ZeroSource, 100_000);
+        let summary = NistSummary::from_results(NistTests::run_all_tests(&zeros));
+        
+        assert_eq!(degenerate.classify(summary.passed, summary.total()), SourceVerdict::Broken);
+    }
+    
+    #[test]
+    fn test_windowed_entropy_detects_drift() {
+        let mut source = SystemEntropy::new();
+        let mut data = vec![0u8; 16_384];
+        source.fill_bytes(&mut data[..8_192]);
+        
+        let series = QualityMetrics::windowed_entropy(&data, 1_024, 1_024);
+        assert_eq!(series.len(), 16);
+        
+        // Random first half, all zeros second half
+        assert!(series[..8].iter().all(|&h| h > 7.5));
+        assert!(series[8..].iter().all(|&h| h == 0.0));
+    }
+    
+    #[test]
+    fn test_windowed_entropy_edges() {
+        let data: Vec<u8> = (0..=255).collect();
+        
+        assert!(QualityMetrics::windowed_entropy(&[], 16, 16).is_empty());
+        assert!(QualityMetrics::windowed_entropy(&data, 0, 16).is_empty());
+        assert_eq!(QualityMetrics::windowed_entropy(&data, 1_000, 16).len(), 1);
+        
+        // 256 bytes in windows of 100: [0..100], [100..200], [200..256]
+        let series = QualityMetrics::windowed_entropy(&data, 100, 100);
+        assert_eq!(series.len(), 3);
+        assert!((series[2] - 56f64.log2()).abs() < 1e-9);
+        
+        // Steps longer than the window skip bytes and stop before the end
+        assert_eq!(QualityMetrics::windowed_entropy(&data, 10, 200).len(), 2);
+    }
+    
+    #[test]
+    fn test_autocorrelation_profile() {
+        let mut source = SystemEntropy::with_backend(SystemBackend::Deterministic(5));
+        let mut data = vec![0u8; 20_000];
+        source.fill_bytes(&mut data);
+        
+        let random = QualityMetrics::autocorrelation_profile(&data, 32);
+        assert_eq!(random.len(), 32);
+        assert!(random.iter().all(|r| r.abs() < 0.02));
+        
+        // Copy the first 16 bits of every 32 into the next 16: period-16 structure
+        for group in data.chunks_exact_mut(4) {
+            group[2] = group[0];
+            group[3] = group[1];
+        }
+        let periodic = QualityMetrics::autocorrelation_profile(&data, 32);
+        for (i, r) in periodic.iter().enumerate() {
+            if i + 1 == 16 {
+                assert!(*r > 0.4, "lag 16: {}", r);
+            } else {
+                assert!(r.abs() < 0.02, "lag {}: {}", i + 1, r);
+            }
+        }
+        
+        assert_eq!(QualityMetrics::autocorrelation_profile(&[], 3), vec![0.0; 3]);
+        assert_eq!(QualityMetrics::autocorrelation_profile(&[0xFF; 10], 3), vec![0.0; 3]);
+        assert_eq!(QualityMetrics::autocorrelation_profile(&[0x0F], 9)[8], 0.0);
+    }
+    
+    #[cfg(feature = "fft")]
+    #[test]
+    fn test_periodicity_score() {
+        let mut source = crate::entropy::ChaChaEntropy::new([6; 32]);
+        let mut random = vec![0u8; 50_000];
+        source.fill_bytes(&mut random);
+        let flat = QualityMetrics::periodicity_score(&random).unwrap();
+        assert!(flat > 0.5 && flat < 2.0, "random scored {}", flat);
+        
+        // Bias each bit's odds with a sinusoid of period 37 bits
+        let mut periodic = vec![0u8; random.len()];
+        for i in 0..periodic.len() * 8 {
+            let phase = 2.0 * std::f64::consts::PI * i as f64 / 37.0;
+            let u = source.next_u32() as f64 / u32::MAX as f64;
+            if u < 0.5 + 0.3 * phase.sin() {
+                periodic[i / 8] |= 0x80 >> (i % 8);
+            }
+        }
+        let peaked = QualityMetrics::periodicity_score(&periodic).unwrap();
+        assert!(peaked > 20.0, "sinusoid scored {}", peaked);
+        assert_eq!(QualityMetrics::from_bytes(&periodic).periodicity_score, Some(peaked));
+        
+        assert_eq!(QualityMetrics::periodicity_score(&random[..100]), None);
+        assert_eq!(QualityMetrics::periodicity_score(&[0xFF; 1_000]), None);
+    }
+    
+    #[test]
+    fn test_shannon_entropy_symbols() {
+        let mut source = SystemEntropy::new();
+        let mut data = vec![0u8; 50_000];
+        source.fill_bytes(&mut data);
+        
+        // Byte-wide symbols match the byte-level metric
+        let bytes = QualityMetrics::shannon_entropy_symbols(&data, 8).unwrap();
+        assert!((bytes - QualityMetrics::shannon_entropy(&data).unwrap()).abs() < 1e-9);
+        
+        let random_nibbles = QualityMetrics::shannon_entropy_symbols(&data, 4).unwrap();
+        assert!(random_nibbles > 3.99);
+        
+        // Only even nibbles: the low bit of each nibble is always 0
+        let even: Vec<u8> = data.iter().map(|&b| b & 0xEE).collect();
+        let even_nibbles = QualityMetrics::shannon_entropy_symbols(&even, 4).unwrap();
+        assert!(even_nibbles < 3.01);
+        
+        // 12-bit symbols drop the trailing partial symbol
+        assert_eq!(QualityMetrics::shannon_entropy_symbols(&[0xFF], 12), None);
+        assert_eq!(QualityMetrics::shannon_entropy_symbols(&data, 17), None);
+        assert_eq!(QualityMetrics::shannon_entropy_symbols(&data, 1), None);
+        assert_eq!(QualityMetrics::shannon_entropy_symbols(&data, 0), None);
+        assert!(QualityMetrics::shannon_entropy_symbols(&data, 2).is_some());
+    }
 }
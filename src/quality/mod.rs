@@ -2,9 +2,32 @@
 //!
 //! This module provides statistical tests and quality metrics for entropy
 //! sources, including NIST SP 800-22 tests.
+//!
+//! Individual metrics and tests return `None` when there is nothing to
+//! measure: empty input, or a sample too short for the test to apply.
+//! `Some(0.0)` is always a real result, such as zero entropy or a failing
+//! p-value. [`QualityMetrics::from_bytes`] scores missing metrics as 0.0;
+//! [`NistTests::run_all_tests`] and the other NIST aggregates keep missing
+//! p-values as `None` and leave them out of pass counts.
 
 mod metrics;
 mod nist;
+mod batch;
+mod report;
+mod uniform;
+mod suite;
+mod history;
+mod correlation;
+mod stream;
+mod preset;
 
-pub use metrics::QualityMetrics;
-pub use nist::NistTests;
+pub use metrics::{QualityMetrics, MetricsOptions, NormalizedMetrics, SourceVerdict, AnalyzeError};
+pub use nist::{NistTests, NistTestEntry, NistSummary, TestDescription, FrequencyAccumulator, BitOrder};
+pub use batch::{BatchReport, BatchEntry};
+pub use report::{compare_sources, QualityReport};
+pub use uniform::uniform_chi_square;
+pub use correlation::cross_correlation;
+pub use stream::NistStreamRunner;
+pub use preset::{Preset, UnknownPreset};
+pub use suite::{TestSuite, SuiteTestFn};
+pub use history::{MetricsHistory, MetricKind, HistoryEntry, InvalidHistory};
@@ -0,0 +1,175 @@
+//! Named analysis configurations: a sample size and a set of tests
+
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+use super::{NistTests, TestSuite};
+
+/// Error from parsing an unknown [`Preset`] name
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("unknown preset {0:?} (expected one of quick-smoke, fips-quick, standard-audit, full-nist)")]
+pub struct UnknownPreset(pub String);
+
+/// A ready-made analysis configuration
+///
+/// Each preset pairs a sample size with the tests worth running at that
+/// size, so new users get a sensible setup without knowing every test.
+/// From fastest to most thorough:
+///
+/// | Preset          | Sample        | Tests                                           |
+/// |-----------------|---------------|-------------------------------------------------|
+/// | `QuickSmoke`    | 10,000 bytes  | Frequency, Runs, Chi-Square                     |
+/// | `FipsQuick`     | 2,500 bytes   | Frequency, Runs, Longest Run, Serial            |
+/// | `StandardAudit` | 100,000 bytes | every registered test                           |
+/// | `FullNist`      | 1,000,000 bytes | every registered test, Block Runs and Run Length Distribution |
+///
+/// `FipsQuick` follows the 20,000-bit sample of the FIPS 140-2 power-up
+/// tests, with the Serial Test standing in for the poker test.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, SystemEntropy};
+/// use entropy_forge::quality::Preset;
+///
+/// let preset: Preset = "quick-smoke".parse().unwrap();
+/// let (sample_size, suite) = preset.configure();
+///
+/// let mut data = vec![0u8; sample_size];
+/// SystemEntropy::new().fill_bytes(&mut data);
+/// for (name, p_value) in suite.run(&data) {
+///     match p_value {
+///         Some(p) => println!("{}: {:.4}", name, p),
+///         None => println!("{}: not applicable", name),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// A few fast tests on a small sample
+    QuickSmoke,
+    /// FIPS 140-2 style start-up check
+    FipsQuick,
+    /// Every registered test at a size they all apply to
+    StandardAudit,
+    /// Every test the crate has, on a large sample
+    FullNist,
+}
+
+impl Preset {
+    /// All presets, from fastest to most thorough
+    pub const ALL: [Preset; 4] = [
+        Preset::QuickSmoke,
+        Preset::FipsQuick,
+        Preset::StandardAudit,
+        Preset::FullNist,
+    ];
+
+    /// Name used on the command line, e.g. `quick-smoke`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Preset::QuickSmoke => "quick-smoke",
+            Preset::FipsQuick => "fips-quick",
+            Preset::StandardAudit => "standard-audit",
+            Preset::FullNist => "full-nist",
+        }
+    }
+
+    /// Short human-readable label
+    pub fn label(&self) -> &'static str {
+        match self {
+            Preset::QuickSmoke => "Quick smoke test",
+            Preset::FipsQuick => "FIPS quick check",
+            Preset::StandardAudit => "Standard audit",
+            Preset::FullNist => "Full NIST",
+        }
+    }
+
+    /// Sample size in bytes
+    pub fn sample_size(&self) -> usize {
+        match self {
+            Preset::QuickSmoke => 10_000,
+            Preset::FipsQuick => 2_500,
+            Preset::StandardAudit => 100_000,
+            Preset::FullNist => 1_000_000,
+        }
+    }
+
+    /// Tests to run
+    pub fn suite(&self) -> TestSuite {
+        match self {
+            Preset::QuickSmoke => TestSuite::builtins(&["Frequency Test", "Runs Test", "Chi-Square Test"]),
+            Preset::FipsQuick => {
+                TestSuite::builtins(&["Frequency Test", "Runs Test", "Longest Run Test", "Serial Test"])
+            }
+            Preset::StandardAudit => TestSuite::default(),
+            Preset::FullNist => {
+                let mut suite = TestSuite::default();
+                suite
+                    .add_optional("Block Runs Test", NistTests::BLOCK_FREQUENCY_BITS / 8, |data| {
+                        NistTests::block_runs_test(data, NistTests::BLOCK_FREQUENCY_BITS)
+                    })
+                    .add_optional(
+                        "Run Length Distribution Test",
+                        NistTests::RUN_LENGTH_MIN_BYTES,
+                        NistTests::run_length_distribution_test,
+                    );
+                suite
+            }
+        }
+    }
+
+    /// Sample size and tests together
+    pub fn configure(&self) -> (usize, TestSuite) {
+        (self.sample_size(), self.suite())
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Preset {
+    type Err = UnknownPreset;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.name() == s)
+            .ok_or_else(|| UnknownPreset(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{ChaChaEntropy, EntropySource};
+
+    #[test]
+    fn test_presets_are_runnable() {
+        for preset in Preset::ALL {
+            let (sample_size, suite) = preset.configure();
+            assert!(!suite.is_empty(), "{}", preset);
+
+            // Every test applies at the preset's sample size
+            let mut data = vec![0u8; sample_size];
+            ChaChaEntropy::new([5; 32]).fill_bytes(&mut data);
+            let results = suite.run(&data);
+            assert_eq!(results.len(), suite.len());
+            assert!(results.iter().all(|(_, p)| p.is_some()), "{}: {:?}", preset, results);
+            let failed = results.iter().filter(|(_, p)| p.is_some_and(|p| p < 0.01)).count();
+            assert!(failed <= 1, "{}: {:?}", preset, results);
+
+            assert_eq!(preset.name().parse::<Preset>(), Ok(preset));
+        }
+
+        let quick: Vec<String> = Preset::QuickSmoke.suite().names().map(String::from).collect();
+        let full: Vec<String> = Preset::FullNist.suite().names().map(String::from).collect();
+        assert!(full.len() > quick.len());
+        assert!(quick.iter().all(|name| full.contains(name)));
+
+        assert_eq!("everything".parse::<Preset>(), Err(UnknownPreset("everything".to_string())));
+    }
+}
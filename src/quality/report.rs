@@ -0,0 +1,174 @@
+//! Combined metrics and NIST results over a single sample
+
+use serde::{Deserialize, Serialize};
+use crate::entropy::{EntropySource, SourceConfig, SourceConfigError};
+use super::{QualityMetrics, SourceVerdict, TestSuite};
+
+/// Entropy metrics and NIST results computed from the same bytes
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::SystemEntropy;
+/// use entropy_forge::quality::QualityReport;
+///
+/// let mut source = SystemEntropy::new();
+/// let report = QualityReport::generate(&mut source, 10_000);
+///
+/// assert_eq!(report.metrics.total_bytes, 10_000);
+/// println!("NIST passed: {}/{}", report.nist_passed(), report.nist_applicable());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualityReport {
+    /// Configuration of the source that produced the sample, if known
+    ///
+    /// For deterministic sources, building this config and sampling the
+    /// same number of bytes reproduces the sample exactly, provided the
+    /// report was generated from a fresh or freshly reset source. `None`
+    /// for pasted data or sources that can't describe themselves. Keyed
+    /// sources record only a key fingerprint unless they embed their key.
+    pub source: Option<SourceConfig>,
+
+    /// Entropy metrics for the sample
+    pub metrics: QualityMetrics,
+
+    /// Test name and p-value, in suite order (registry order by default);
+    /// `None` for tests that didn't apply to the sample
+    pub nist_results: Vec<(String, Option<f64>)>,
+}
+
+impl QualityReport {
+    /// Sample `sample_size` bytes once and run every check on them
+    pub fn generate<E: ?Sized + EntropySource>(source: &mut E, sample_size: usize) -> Self {
+        let mut data = vec![0u8; sample_size];
+        source.fill_bytes(&mut data);
+
+        Self {
+            source: SourceConfig::describe(source),
+            ..Self::from_bytes(&data)
+        }
+    }
+
+    /// Build a fresh source from `config` and generate a report from it
+    ///
+    /// The result is reproducible whenever the configured source is
+    /// deterministic. Fails if the config can't be built; see
+    /// [`SourceConfig::build`].
+    pub fn from_config(config: &SourceConfig, sample_size: usize) -> Result<Self, SourceConfigError> {
+        Ok(Self::generate(&mut config.build()?, sample_size))
+    }
+
+    /// Run every check on an existing sample
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Self::from_bytes_with_suite(data, &TestSuite::default())
+    }
+
+    /// Run the metrics and every test in `suite` on an existing sample
+    ///
+    /// Custom tests added to the suite appear in
+    /// [`nist_results`](Self::nist_results) after the built-ins.
+    pub fn from_bytes_with_suite(data: &[u8], suite: &TestSuite) -> Self {
+        Self {
+            source: None,
+            metrics: QualityMetrics::from_bytes(data),
+            nist_results: suite.run(data),
+        }
+    }
+
+    /// Number of NIST tests with p ≥ 0.01
+    pub fn nist_passed(&self) -> usize {
+        self.nist_results.iter().filter(|(_, p)| p.is_some_and(|p| p >= 0.01)).count()
+    }
+
+    /// Number of NIST tests that applied to the sample and ran
+    pub fn nist_applicable(&self) -> usize {
+        self.nist_results.iter().filter(|(_, p)| p.is_some()).count()
+    }
+
+    /// Verdict from [`QualityMetrics::classify`] using this report's NIST results
+    pub fn verdict(&self) -> SourceVerdict {
+        self.metrics.classify(self.nist_passed(), self.nist_applicable())
+    }
+}
+
+/// Generate a [`QualityReport`] for each source from equal-sized samples
+///
+/// Reports come back in the same order as `sources`, ready to be compared
+/// side by side. Each source is sampled once, like
+/// [`QualityReport::generate`].
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{BiasedEntropy, EntropySource, SystemEntropy};
+/// use entropy_forge::quality::compare_sources;
+///
+/// let mut sources: Vec<Box<dyn EntropySource>> = vec![
+///     Box::new(SystemEntropy::new()),
+///     Box::new(BiasedEntropy::new(1, 0.7)),
+/// ];
+/// for report in compare_sources(&mut sources, 10_000) {
+///     println!("{:.3} bits/byte", report.metrics.shannon_entropy);
+/// }
+/// ```
+pub fn compare_sources<E: EntropySource>(sources: &mut [E], sample_size: usize) -> Vec<QualityReport> {
+    sources.iter_mut()
+        .map(|source| QualityReport::generate(source, sample_size))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{BiasedEntropy, ChaChaEntropy, SeedableEntropy, SystemBackend, SystemEntropy};
+    use crate::quality::NistTests;
+
+    #[test]
+    fn test_generate_uses_one_sample() {
+        let mut source = ChaChaEntropy::from_seed_u64(7);
+        let report = QualityReport::generate(&mut source, 20_000);
+
+        // Replay the exact sample the report drew
+        source.reset();
+        let mut data = vec![0u8; 20_000];
+        source.fill_bytes(&mut data);
+
+        assert_eq!(report.metrics, QualityMetrics::from_bytes(&data));
+
+        let expected: Vec<(String, Option<f64>)> = NistTests::run_all_tests(&data)
+            .into_iter()
+            .map(|(name, p)| (name.to_string(), p))
+            .collect();
+        assert_eq!(report.nist_results, expected);
+    }
+
+    #[test]
+    fn test_report_reproduces_from_config() {
+        let config = SourceConfig::Mock { seed: 42 };
+        let report = QualityReport::from_config(&config, 5_000).unwrap();
+        assert_eq!(report.source, Some(config));
+
+        // The serialized report carries enough to regenerate itself
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: QualityReport = serde_json::from_str(&json).unwrap();
+        let replayed = QualityReport::from_config(parsed.source.as_ref().unwrap(), 5_000).unwrap();
+        assert_eq!(replayed, report);
+
+        assert_eq!(QualityReport::from_bytes(&[1, 2, 3]).source, None);
+    }
+
+    #[test]
+    fn test_compare_ranks_biased_source_lower() {
+        let mut sources: Vec<Box<dyn EntropySource>> = vec![
+            Box::new(SystemEntropy::with_backend(SystemBackend::Deterministic(5))),
+            Box::new(BiasedEntropy::new(5, 0.6)),
+        ];
+        let reports = compare_sources(&mut sources, 20_000);
+        
+        assert_eq!(reports.len(), 2);
+        let (system, biased) = (&reports[0].metrics, &reports[1].metrics);
+        assert!(biased.overall_score() < system.overall_score());
+        assert!(biased.min_entropy < system.min_entropy);
+        assert!(reports[1].nist_passed() < reports[0].nist_passed());
+    }
+}
@@ -0,0 +1,235 @@
+//! NIST tests over data too large to hold in memory
+
+use super::{BitOrder, FrequencyAccumulator, NistTests};
+
+/// Runs the streamable NIST tests chunk by chunk
+///
+/// Feed any number of chunks to [`update`](Self::update), then call
+/// [`finalize`](Self::finalize). Memory use is constant: the runner keeps
+/// counts, not data, so gigabyte files can be tested by reading them a
+/// buffer at a time. The results are identical to running each test on
+/// the concatenated chunks, however the data was split.
+///
+/// Streamed tests, in registry order: Frequency, Block Frequency, Runs,
+/// Chi-Square and Serial. The Longest Run Test is skipped, because its
+/// block size depends on the total length, which is only known at the end;
+/// run it on a prefix if you need it.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::entropy::{EntropySource, SystemEntropy};
+/// use entropy_forge::quality::NistStreamRunner;
+///
+/// let mut runner = NistStreamRunner::new();
+/// let mut source = SystemEntropy::new();
+/// let mut buffer = vec![0u8; 65_536];
+/// for _ in 0..16 {
+///     source.fill_bytes(&mut buffer);
+///     runner.update(&buffer);
+/// }
+///
+/// println!("{} MiB tested", runner.bytes_seen() >> 20);
+/// for (name, p_value) in runner.finalize() {
+///     match p_value {
+///         Some(p) => println!("{}: {:.4}", name, p),
+///         None => println!("{}: not applicable", name),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct NistStreamRunner {
+    bytes: u64,
+    frequency: FrequencyAccumulator,
+    byte_counts: [u64; 256],
+    // Runs and Serial: transitions, adjacent bit pairs and the last bit seen
+    runs: u64,
+    pairs: [u64; 4],
+    last_bit: Option<u8>,
+    // Block Frequency: completed blocks, their summed terms, and the ones
+    // and bits of the block in progress
+    blocks: usize,
+    block_sum: f64,
+    block_ones: usize,
+    block_bits: usize,
+}
+
+impl Default for NistStreamRunner {
+    fn default() -> Self {
+        Self {
+            bytes: 0,
+            frequency: FrequencyAccumulator::new(),
+            byte_counts: [0; 256],
+            runs: 0,
+            pairs: [0; 4],
+            last_bit: None,
+            blocks: 0,
+            block_sum: 0.0,
+            block_ones: 0,
+            block_bits: 0,
+        }
+    }
+}
+
+impl NistStreamRunner {
+    /// Names of the tests [`finalize`](Self::finalize) reports, in order
+    pub const TESTS: [&'static str; 5] = [
+        "Frequency Test",
+        "Block Frequency Test",
+        "Runs Test",
+        "Chi-Square Test",
+        "Serial Test",
+    ];
+
+    /// Create a runner that has seen no data
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the next chunk of the stream
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.bytes += chunk.len() as u64;
+        self.frequency.update(chunk);
+
+        for &byte in chunk {
+            self.byte_counts[byte as usize] += 1;
+        }
+
+        // Bits in the default (MSB-first) order, as the in-memory tests read them
+        for bit in BitOrder::default().iter(chunk) {
+            match self.last_bit {
+                Some(last) => {
+                    self.pairs[usize::from(last << 1 | bit)] += 1;
+                    if last != bit {
+                        self.runs += 1;
+                    }
+                }
+                None => self.runs = 1,
+            }
+            self.last_bit = Some(bit);
+
+            self.block_ones += usize::from(bit);
+            self.block_bits += 1;
+            if self.block_bits == NistTests::BLOCK_FREQUENCY_BITS {
+                self.block_sum += NistTests::block_frequency_term(self.block_ones, self.block_bits);
+                self.blocks += 1;
+                self.block_ones = 0;
+                self.block_bits = 0;
+            }
+        }
+    }
+
+    /// Bytes seen so far, for progress reporting
+    pub fn bytes_seen(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Test name and p-value for everything seen so far
+    ///
+    /// Like [`NistTests::run_all_tests`], a test that is not applicable
+    /// yet (too little data) has `None`. The runner is left as it was, so
+    /// it can report intermediate results and keep going.
+    pub fn finalize(&self) -> Vec<(&'static str, Option<f64>)> {
+        Self::TESTS.into_iter().zip(self.p_values()).collect()
+    }
+
+    /// Like [`finalize`](Self::finalize), without the test names
+    pub fn p_values(&self) -> [Option<f64>; 5] {
+        [
+            self.frequency_p_value(),
+            self.block_frequency_p_value(),
+            self.runs_p_value(),
+            self.chi_square_p_value(),
+            self.serial_p_value(),
+        ]
+    }
+
+    fn frequency_p_value(&self) -> Option<f64> {
+        if self.bytes == 0 {
+            return NistTests::not_applicable("Frequency Test", "empty input");
+        }
+        self.frequency.p_value()
+    }
+
+    fn block_frequency_p_value(&self) -> Option<f64> {
+        if self.blocks == 0 {
+            return NistTests::not_applicable("Block Frequency Test", "shorter than one block");
+        }
+        NistTests::block_frequency_p_value(self.block_sum, NistTests::BLOCK_FREQUENCY_BITS, self.blocks)
+    }
+
+    fn runs_p_value(&self) -> Option<f64> {
+        let bits = self.frequency.bit_count();
+        if bits < 16 {
+            return NistTests::not_applicable("Runs Test", "fewer than 16 bits");
+        }
+        let ones = (self.frequency.sum() + bits as i64) as u64 / 2;
+        Some(NistTests::runs_p_value(bits, ones, self.runs))
+    }
+
+    fn chi_square_p_value(&self) -> Option<f64> {
+        if self.bytes == 0 {
+            return NistTests::not_applicable("Chi-Square Test", "empty input");
+        }
+        NistTests::byte_chi_square_p_value(&self.byte_counts, self.bytes)
+    }
+
+    fn serial_p_value(&self) -> Option<f64> {
+        if self.bytes < 2 {
+            return NistTests::not_applicable("Serial Test", "fewer than 2 bytes");
+        }
+        NistTests::serial_p_value(&self.pairs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{BiasedEntropy, ChaChaEntropy, EntropySource};
+
+    fn in_memory(data: &[u8]) -> [Option<f64>; 5] {
+        [
+            NistTests::frequency_test(data),
+            NistTests::block_frequency_test(data),
+            NistTests::runs_test(data),
+            NistTests::chi_square_test(data),
+            NistTests::serial_test(data),
+        ]
+    }
+
+    #[test]
+    fn test_streamed_matches_in_memory() {
+        let mut random = vec![0u8; 100_003];
+        ChaChaEntropy::new([9; 32]).fill_bytes(&mut random);
+        let mut biased = vec![0u8; 50_000];
+        BiasedEntropy::new(2, 0.52).fill_bytes(&mut biased);
+
+        for data in [&random[..], &biased[..], &[0x5A; 40][..], &random[..17], &random[..1]] {
+            for chunk in [1, 7, 4_096, data.len()] {
+                let mut runner = NistStreamRunner::new();
+                for part in data.chunks(chunk) {
+                    runner.update(part);
+                }
+                assert_eq!(runner.bytes_seen(), data.len() as u64);
+                assert_eq!(runner.p_values(), in_memory(data), "{} bytes in chunks of {}", data.len(), chunk);
+            }
+        }
+    }
+
+    #[test]
+    fn test_finalize_matches_registry() {
+        let mut data = vec![0u8; 10_000];
+        ChaChaEntropy::new([1; 32]).fill_bytes(&mut data);
+
+        let mut runner = NistStreamRunner::new();
+        assert!(runner.finalize().iter().all(|&(_, p)| p.is_none()));
+        runner.update(&data);
+
+        // Everything in the registry except the Longest Run Test
+        let expected: Vec<_> = NistTests::run_all_tests(&data)
+            .into_iter()
+            .filter(|(name, _)| *name != "Longest Run Test")
+            .collect();
+        assert_eq!(runner.finalize(), expected);
+    }
+}
@@ -0,0 +1,189 @@
+//! Extensible test suite: the built-in NIST tests plus user tests
+
+use std::time::{Duration, Instant};
+use super::{NistTestEntry, NistTests};
+
+/// A p-value test over a byte sample, `None` if it doesn't apply
+pub type SuiteTestFn = Box<dyn Fn(&[u8]) -> Option<f64> + Send + Sync>;
+
+struct SuiteTest {
+    name: String,
+    min_bytes: usize,
+    run: SuiteTestFn,
+}
+
+/// An ordered set of named p-value tests run over one sample
+///
+/// [`TestSuite::default`] holds the built-in tests from
+/// [`NistTests::registry`]; [`add`](Self::add) appends custom ones so
+/// they appear in the same report. Like the built-ins, a test returns a
+/// p-value where ≥ 0.01 passes, and `None` on samples smaller than its
+/// `min_bytes`.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::quality::TestSuite;
+///
+/// let mut suite = TestSuite::default();
+/// suite.add("No Zero Bytes", 1, |data| if data.contains(&0) { 0.0 } else { 1.0 });
+///
+/// for (name, p_value) in suite.run(&[0x12; 200]) {
+///     match p_value {
+///         Some(p) => println!("{}: {:.4}", name, p),
+///         None => println!("{}: not applicable", name),
+///     }
+/// }
+/// ```
+pub struct TestSuite {
+    tests: Vec<SuiteTest>,
+}
+
+impl TestSuite {
+    /// Create a suite with no tests
+    pub fn empty() -> Self {
+        Self { tests: Vec::new() }
+    }
+
+    /// Create a suite with the named built-in tests, in registry order
+    ///
+    /// Names not in [`NistTests::registry`] are ignored.
+    pub fn builtins(names: &[&str]) -> Self {
+        let mut suite = Self::empty();
+        for entry in NistTests::registry().into_iter().filter(|entry| names.contains(&entry.name)) {
+            suite.add_builtin(entry);
+        }
+        suite
+    }
+
+    fn add_builtin(&mut self, entry: NistTestEntry) {
+        self.add_optional(entry.name, entry.min_bytes, entry.run);
+    }
+
+    /// Append a test, run after the ones already in the suite
+    pub fn add<F>(&mut self, name: impl Into<String>, min_bytes: usize, test: F) -> &mut Self
+    where
+        F: Fn(&[u8]) -> f64 + Send + Sync + 'static,
+    {
+        self.add_optional(name, min_bytes, move |data| Some(test(data)))
+    }
+
+    /// Like [`add`](Self::add), for a test that can itself decline to run
+    ///
+    /// Returning `None` marks the test not applicable, as the built-ins
+    /// do for inputs they can't judge.
+    pub fn add_optional<F>(&mut self, name: impl Into<String>, min_bytes: usize, test: F) -> &mut Self
+    where
+        F: Fn(&[u8]) -> Option<f64> + Send + Sync + 'static,
+    {
+        self.tests.push(SuiteTest {
+            name: name.into(),
+            min_bytes,
+            run: Box::new(test),
+        });
+        self
+    }
+
+    /// Test names, in run order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.tests.iter().map(|test| test.name.as_str())
+    }
+
+    /// Number of tests in the suite
+    pub fn len(&self) -> usize {
+        self.tests.len()
+    }
+
+    /// Whether the suite has no tests
+    pub fn is_empty(&self) -> bool {
+        self.tests.is_empty()
+    }
+
+    /// Run every test on `data`, returning name and p-value in order
+    ///
+    /// Tests that don't apply to `data` have `None`.
+    pub fn run(&self, data: &[u8]) -> Vec<(String, Option<f64>)> {
+        self.tests
+            .iter()
+            .map(|test| (test.name.clone(), Self::run_one(test, data)))
+            .collect()
+    }
+
+    /// Like [`run`](Self::run), also recording how long each test took
+    pub fn run_timed(&self, data: &[u8]) -> Vec<(String, Option<f64>, Duration)> {
+        self.tests
+            .iter()
+            .map(|test| {
+                let start = Instant::now();
+                let p_value = Self::run_one(test, data);
+                (test.name.clone(), p_value, start.elapsed())
+            })
+            .collect()
+    }
+
+    fn run_one(test: &SuiteTest, data: &[u8]) -> Option<f64> {
+        if data.len() < test.min_bytes {
+            return None;
+        }
+        (test.run)(data).map(NistTests::finalize_pvalue)
+    }
+}
+
+impl Default for TestSuite {
+    /// The built-in tests from [`NistTests::registry`]
+    fn default() -> Self {
+        let mut suite = Self::empty();
+        for entry in NistTests::registry() {
+            suite.add_builtin(entry);
+        }
+        suite
+    }
+}
+
+impl std::fmt::Debug for TestSuite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{ChaChaEntropy, EntropySource};
+
+    #[test]
+    fn test_custom_test_runs_with_builtins() {
+        let mut data = vec![0u8; 10_000];
+        ChaChaEntropy::new([4; 32]).fill_bytes(&mut data);
+
+        let mut suite = TestSuite::default();
+        suite.add("Always Pass", 1, |_| 1.0);
+
+        let results = suite.run(&data);
+        let builtins: Vec<(String, Option<f64>)> = NistTests::run_all_tests(&data)
+            .into_iter()
+            .map(|(name, p)| (name.to_string(), p))
+            .collect();
+
+        assert_eq!(results.len(), builtins.len() + 1);
+        assert_eq!(results[..builtins.len()], builtins[..]);
+        assert_eq!(results.last().unwrap(), &("Always Pass".to_string(), Some(1.0)));
+    }
+
+    #[test]
+    fn test_min_bytes_and_clamping() {
+        let mut suite = TestSuite::empty();
+        assert!(suite.is_empty());
+
+        suite
+            .add("Needs 100", 100, |_| 0.5)
+            .add("Out Of Range", 0, |_| 7.0)
+            .add("NaN", 0, |_| f64::NAN)
+            .add_optional("Declines", 0, |_| None);
+        assert_eq!(suite.names().collect::<Vec<_>>(), ["Needs 100", "Out Of Range", "NaN", "Declines"]);
+
+        let p_values: Vec<Option<f64>> = suite.run(&[1, 2, 3]).into_iter().map(|(_, p)| p).collect();
+        assert_eq!(p_values, [None, Some(1.0), Some(0.0), None]);
+        assert_eq!(suite.run_timed(&[0; 100])[0].1, Some(0.5));
+    }
+}
@@ -0,0 +1,102 @@
+//! Goodness-of-fit tests over arbitrary symbol streams
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use statrs::distribution::{ChiSquared, ContinuousCDF};
+use super::NistTests;
+
+/// Chi-square goodness-of-fit against a uniform distribution
+///
+/// Generalizes [`NistTests::chi_square_test`] beyond bytes: `samples` can
+/// be dice rolls, card indices or any other symbols, drawn from a known
+/// number of equally likely `categories`. Categories that never appear in
+/// `samples` count as observed zero times.
+///
+/// # Returns
+///
+/// P-value (0.0 to 1.0). Values ≥ 0.01 indicate the samples are consistent
+/// with a uniform distribution. Returns `None` if `samples` is empty or
+/// `categories` is less than 2. More distinct symbols than categories is a
+/// definite failure and scores `Some(0.0)`.
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::quality::uniform_chi_square;
+///
+/// let rolls = [1, 2, 3, 4, 5, 6, 6, 5, 4, 3, 2, 1];
+/// assert!(uniform_chi_square(&rolls, 6).unwrap() >= 0.01);
+/// ```
+pub fn uniform_chi_square<T: Eq + Hash>(samples: &[T], categories: usize) -> Option<f64> {
+    if samples.is_empty() || categories < 2 {
+        return None;
+    }
+
+    let mut freq: HashMap<&T, usize> = HashMap::new();
+    for sample in samples {
+        *freq.entry(sample).or_insert(0) += 1;
+    }
+
+    if freq.len() > categories {
+        return Some(0.0);
+    }
+
+    let expected = samples.len() as f64 / categories as f64;
+
+    // Sort the counts so the floating-point sum doesn't depend on hash order
+    let mut counts: Vec<usize> = freq.into_values().collect();
+    counts.sort_unstable();
+
+    let unseen = (categories - counts.len()) as f64;
+    let mut chi_square = unseen * expected;
+    for count in counts {
+        let diff = count as f64 - expected;
+        chi_square += diff * diff / expected;
+    }
+
+    let dist = ChiSquared::new((categories - 1) as f64).ok()?;
+    Some(NistTests::finalize_pvalue(1.0 - dist.cdf(chi_square)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{EntropySource, SystemEntropy};
+
+    #[test]
+    fn test_dice() {
+        // 100 of each face
+        let fair: Vec<u8> = (0..600).map(|i| (i % 6) as u8 + 1).collect();
+        assert!(uniform_chi_square(&fair, 6).unwrap() >= 0.01);
+
+        // Half the rolls are sixes
+        let biased: Vec<u8> = (0..600)
+            .map(|i| if i % 2 == 0 { 6 } else { (i % 5) as u8 + 1 })
+            .collect();
+        assert!(uniform_chi_square(&biased, 6).unwrap() < 0.01);
+
+        // A face that never comes up is as suspicious as an over-represented one
+        let no_sixes: Vec<u8> = (0..600).map(|i| (i % 5) as u8 + 1).collect();
+        assert!(uniform_chi_square(&no_sixes, 6).unwrap() < 0.01);
+    }
+
+    #[test]
+    fn test_matches_byte_chi_square() {
+        let mut source = SystemEntropy::new();
+        let mut data = vec![0u8; 10_000];
+        source.fill_bytes(&mut data);
+
+        let generic = uniform_chi_square(&data, 256).unwrap();
+        assert!((generic - NistTests::chi_square_test(&data).unwrap()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        assert_eq!(uniform_chi_square::<u8>(&[], 6), None);
+        assert_eq!(uniform_chi_square(&[1, 2, 3], 1), None);
+        assert_eq!(uniform_chi_square(&[1, 2, 3], 2), Some(0.0));
+
+        // A perfectly even spread is a legitimate result, not "no data"
+        assert_eq!(uniform_chi_square(&[1, 2, 1, 2], 2), Some(1.0));
+    }
+}
@@ -0,0 +1,204 @@
+//! Newline-delimited JSON service for driving the crate from other languages
+//!
+//! Each input line is one [`Request`]; each output line is the matching
+//! [`Response`]. Sources are described by [`SourceConfig`], so a request
+//! against a deterministic source is reproducible. Reports describe keyed
+//! sources by key fingerprint only:
+//!
+//! ```json
+//! {"op":"analyze","source":{"kind":"mock","seed":42},"size":100000}
+//! {"op":"nist","source":{"kind":"system"},"size":10000}
+//! {"op":"benchmark","source":{"kind":"system"},"size":1000000}
+//! {"op":"generate","source":{"kind":"mock","seed":1},"size":32}
+//! ```
+//!
+//! Requires the `service` feature; the `entropy-forge-service` binary runs
+//! [`serve`] over stdin and stdout.
+
+use std::io::{self, BufRead, Write};
+use serde::{Deserialize, Serialize};
+use crate::bench::PerformanceBench;
+use crate::entropy::SourceConfig;
+use crate::quality::{NistTests, QualityReport};
+
+/// Largest `size` a single request may ask for
+pub const MAX_REQUEST_BYTES: usize = 64 * 1024 * 1024;
+
+/// One operation, tagged by `op`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Request {
+    /// Sample `size` bytes and return a full [`QualityReport`]
+    Analyze {
+        /// Source to sample
+        source: SourceConfig,
+        /// Sample size in bytes
+        size: usize,
+    },
+
+    /// Sample `size` bytes and run the NIST tests only
+    Nist {
+        /// Source to sample
+        source: SourceConfig,
+        /// Sample size in bytes
+        size: usize,
+    },
+
+    /// Measure throughput generating `size` bytes
+    Benchmark {
+        /// Source to benchmark
+        source: SourceConfig,
+        /// Bytes to generate
+        size: usize,
+    },
+
+    /// Return `size` bytes from the source, hex-encoded
+    Generate {
+        /// Source to draw from
+        source: SourceConfig,
+        /// Bytes to generate
+        size: usize,
+    },
+}
+
+impl Request {
+    fn source(&self) -> &SourceConfig {
+        match self {
+            Request::Analyze { source, .. }
+            | Request::Nist { source, .. }
+            | Request::Benchmark { source, .. }
+            | Request::Generate { source, .. } => source,
+        }
+    }
+
+    fn size(&self) -> usize {
+        match *self {
+            Request::Analyze { size, .. }
+            | Request::Nist { size, .. }
+            | Request::Benchmark { size, .. }
+            | Request::Generate { size, .. } => size,
+        }
+    }
+}
+
+/// Result of one request, tagged by `op` (`"error"` on failure)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Response {
+    /// Report for an [`Request::Analyze`]
+    Analyze {
+        /// Metrics and NIST results over one sample
+        report: Box<QualityReport>,
+    },
+
+    /// Results for a [`Request::Nist`]
+    Nist {
+        /// Test name and p-value, in registry order; `null` for tests that
+        /// need more data than the sample has
+        results: Vec<(String, Option<f64>)>,
+        /// Number of tests with p ≥ 0.01
+        passed: usize,
+    },
+
+    /// Measurements for a [`Request::Benchmark`]
+    Benchmark {
+        /// Throughput in megabytes per second
+        throughput_mbps: f64,
+        /// Average latency per byte in microseconds
+        latency_us: f64,
+        /// Total bytes generated
+        bytes_generated: usize,
+        /// Total time taken in seconds
+        duration_secs: f64,
+    },
+
+    /// Bytes for a [`Request::Generate`]
+    Generate {
+        /// Generated bytes, hex-encoded
+        hex: String,
+    },
+
+    /// The request could not be parsed or was rejected
+    Error {
+        /// Human-readable reason
+        message: String,
+    },
+}
+
+/// Run one request
+pub fn handle(request: &Request) -> Response {
+    if request.size() > MAX_REQUEST_BYTES {
+        return Response::Error {
+            message: format!("size {} exceeds the limit of {} bytes", request.size(), MAX_REQUEST_BYTES),
+        };
+    }
+
+    let mut source = match request.source().build() {
+        Ok(source) => source,
+        Err(e) => return Response::Error { message: e.to_string() },
+    };
+
+    match request {
+        Request::Analyze { size, .. } => {
+            // Don't echo keys back into responses that may be logged
+            let mut report = QualityReport::generate(&mut source, *size);
+            report.source = report.source.map(SourceConfig::redacted);
+            Response::Analyze { report: Box::new(report) }
+        }
+        Request::Nist { size, .. } => {
+            let mut data = vec![0u8; *size];
+            source.fill_bytes(&mut data);
+
+            let results: Vec<(String, Option<f64>)> = NistTests::run_all_tests(&data)
+                .into_iter()
+                .map(|(name, p_value)| (name.to_string(), p_value))
+                .collect();
+            let passed = results.iter().filter(|(_, p)| p.is_some_and(|p| p >= 0.01)).count();
+            Response::Nist { results, passed }
+        }
+        Request::Benchmark { size, .. } => {
+            let result = PerformanceBench::benchmark_dyn(source.as_mut(), *size);
+            Response::Benchmark {
+                throughput_mbps: result.throughput_mbps,
+                latency_us: result.latency_us,
+                bytes_generated: result.bytes_generated,
+                duration_secs: result.duration.as_secs_f64(),
+            }
+        }
+        Request::Generate { size, .. } => {
+            let mut data = vec![0u8; *size];
+            source.fill_bytes(&mut data);
+            Response::Generate { hex: hex::encode(data) }
+        }
+    }
+}
+
+/// Parse one JSON line, run it, and serialize the response
+///
+/// Malformed input yields an `"error"` response rather than failing, so a
+/// client can keep the process running across bad requests.
+pub fn handle_line(line: &str) -> String {
+    let response = match serde_json::from_str::<Request>(line) {
+        Ok(request) => handle(&request),
+        Err(e) => Response::Error { message: format!("invalid request: {}", e) },
+    };
+
+    serde_json::to_string(&response).expect("responses always serialize")
+}
+
+/// Answer requests from `input` line by line until end of input
+///
+/// Blank lines are skipped. Each response is flushed immediately so a
+/// client can wait for it before sending the next request.
+pub fn serve<R: BufRead, W: Write>(input: R, mut output: W) -> io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        writeln!(output, "{}", handle_line(&line))?;
+        output.flush()?;
+    }
+    Ok(())
+}
@@ -1,11 +1,16 @@
 //! Main GUI application
 
+use std::path::PathBuf;
+use std::time::Duration;
 use eframe::egui;
-use crate::entropy::{EntropySource, SystemEntropy};
-use crate::crypto::StreamCipher;
-use crate::quality::{QualityMetrics, NistTests};
+use serde::{Deserialize, Serialize};
+use crate::entropy::{BiasedEntropy, ChaChaEntropy, EntropySource, PassphraseEntropy, SeedableEntropy, SystemEntropy, XoshiroEntropy};
+use crate::crypto::{self, StreamCipher, OutputFormat};
+use crate::quality::{compare_sources, MetricsOptions, QualityMetrics, QualityReport, NistSummary, NistTests, Preset, TestSuite};
 use crate::bench::{PerformanceBench, BenchmarkResult};
-use crate::learn::{EncryptionProcess, EntropyProcess, NistProcess};
+use crate::learn::{BlockFrequencyProcess, Challenge, EncryptionProcess, EntropyProcess, NistProcess};
+use super::config::GuiConfig;
+use super::theme::Palette;
 
 /// Main application state
 pub struct EntropyForgeApp {
@@ -15,30 +20,62 @@ pub struct EntropyForgeApp {
     // Current tab
     current_tab: Tab,
     
+    // Settings as loaded, and where to save them on exit
+    config: GuiConfig,
+    config_path: Option<PathBuf>,
+    
+    // Settings
+    show_settings: bool,
+    dark_mode: bool,
+    palette: Palette,
+    
     // Use tab state
     cipher_input: String,
-    cipher_output: String,
-    cipher_hex: bool,
+    cipher_key: String,
+    cipher_decrypted: bool,
+    cipher_escape: bool,
+    cipher_output: Vec<u8>,
+    cipher_format: OutputFormat,
     cipher_state: Vec<u8>,
+    cipher_warning: Option<String>,
+    cipher_export_status: Option<String>,
     
     // Test tab state
     quality_metrics: Option<QualityMetrics>,
-    nist_results: Vec<(String, f64)>,
+    nist_results: Vec<(String, Option<f64>)>,
+    nist_durations: Vec<Duration>,
+    nist_suite: TestSuite,
+    preset: Option<Preset>,
+    quality_sample: Vec<u8>,
+    rerun_matched: Option<bool>,
     quality_sample_size: usize,
-    is_testing: bool,
+    test_input: TestInput,
+    paste_input: String,
+    paste_format: OutputFormat,
+    paste_error: Option<String>,
+    test_estimate: Option<(usize, Option<Duration>)>,
+    compare_selected: Vec<bool>,
+    compare_results: Vec<(&'static str, QualityReport)>,
     
     // Benchmark tab state
     bench_result: Option<BenchmarkResult>,
     bench_size: usize,
-    is_benchmarking: bool,
+    bench_estimate: Option<(usize, Option<Duration>)>,
+    bench_baseline: Option<BenchmarkResult>,
 
     // Learn tab state
     learn_mode: LearnMode,
     learn_process: EncryptionProcess,
     learn_input: String,
+    // Why the last Generate Random click drew nothing
+    learn_random_error: Option<String>,
 
     entropy_process: EntropyProcess,
     nist_process: NistProcess,
+    block_freq_process: BlockFrequencyProcess,
+    challenge: Option<Challenge>,
+    challenge_guess: Option<usize>,
+    challenge_score: (usize, usize),
 }
 
 #[derive(PartialEq, Clone, Copy)]
@@ -46,13 +83,28 @@ enum LearnMode {
     XorCipher,
     ShannonEntropy,
     NistFrequency,
+    BlockFrequency,
+    SpotThePattern,
 }
 
 #[derive(PartialEq, Clone, Copy)]
-enum Tab {
+enum TestInput {
+    Generated,
+    Pasted,
+}
+
+/// Top-level tab of the GUI
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tab {
+    /// Stream cipher
+    #[default]
     Use,
+    /// Quality metrics and NIST tests
     Test,
+    /// Throughput benchmark
     Benchmark,
+    /// Step-by-step explanations
     Learn,
 }
 
@@ -61,22 +113,135 @@ impl Default for EntropyForgeApp {
         Self {
             entropy: Box::new(SystemEntropy::new()),
             current_tab: Tab::Use,
+            config: GuiConfig::default(),
+            config_path: None,
+            show_settings: false,
+            dark_mode: true,
+            palette: Palette::Standard,
             cipher_input: String::from("Hello, World!"),
-            cipher_output: String::new(),
-            cipher_hex: true,
+            cipher_key: String::new(),
+            cipher_decrypted: false,
+            cipher_escape: false,
+            cipher_output: Vec::new(),
+            cipher_format: OutputFormat::Hex,
             cipher_state: Vec::new(),
+            cipher_warning: None,
+            cipher_export_status: None,
             quality_metrics: None,
             nist_results: Vec::new(),
+            nist_durations: Vec::new(),
+            nist_suite: TestSuite::default(),
+            preset: None,
+            quality_sample: Vec::new(),
+            rerun_matched: None,
             quality_sample_size: 100_000,
-            is_testing: false,
+            test_input: TestInput::Generated,
+            paste_input: String::new(),
+            paste_format: OutputFormat::Hex,
+            paste_error: None,
+            test_estimate: None,
+            compare_selected: vec![true, false, false, true],
+            compare_results: Vec::new(),
             bench_result: None,
             bench_size: 1_000_000,
-            is_benchmarking: false,
+            bench_estimate: None,
+            bench_baseline: None,
             learn_mode: LearnMode::XorCipher,
             learn_process: EncryptionProcess::new(),
             learn_input: String::from("Hello"),
+            learn_random_error: None,
             entropy_process: EntropyProcess::new(),
             nist_process: NistProcess::new(),
+            block_freq_process: BlockFrequencyProcess::new(),
+            challenge: None,
+            challenge_guess: None,
+            challenge_score: (0, 0),
+        }
+    }
+}
+
+impl EntropyForgeApp {
+    /// Start configuring an app; unset options keep their defaults
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use entropy_forge::entropy::ChaChaEntropy;
+    /// use entropy_forge::viz::{EntropyForgeApp, Tab};
+    ///
+    /// let app = EntropyForgeApp::builder()
+    ///     .source(Box::new(ChaChaEntropy::new([0; 32])))
+    ///     .sample_size(50_000)
+    ///     .start_tab(Tab::Test)
+    ///     .build();
+    /// ```
+    pub fn builder() -> EntropyForgeAppBuilder {
+        EntropyForgeAppBuilder::default()
+    }
+}
+
+/// Builder for a preconfigured [`EntropyForgeApp`]
+///
+/// Created with [`EntropyForgeApp::builder`]. Useful for embedding the GUI
+/// or launching it straight into a given tab.
+#[derive(Default)]
+pub struct EntropyForgeAppBuilder {
+    config: GuiConfig,
+    config_path: Option<PathBuf>,
+    source: Option<Box<dyn EntropySource>>,
+    sample_size: Option<usize>,
+    start_tab: Option<Tab>,
+}
+
+impl EntropyForgeAppBuilder {
+    /// Start from saved settings; the other builder methods override them
+    pub fn config(mut self, config: GuiConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Save the settings to `path` when the app exits
+    pub fn save_config_to(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_path = Some(path.into());
+        self
+    }
+
+    /// Entropy source to use instead of the configured one
+    pub fn source(mut self, source: Box<dyn EntropySource>) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Sample size for the Test tab, in bytes (default from the config)
+    pub fn sample_size(mut self, bytes: usize) -> Self {
+        self.sample_size = Some(bytes);
+        self
+    }
+
+    /// Tab shown on launch (default from the config)
+    pub fn start_tab(mut self, tab: Tab) -> Self {
+        self.start_tab = Some(tab);
+        self
+    }
+
+    /// Build the app
+    pub fn build(self) -> EntropyForgeApp {
+        let config = self.config;
+        EntropyForgeApp {
+            entropy: self.source.unwrap_or_else(|| {
+                config.source.build().unwrap_or_else(|e| {
+                    log::warn!("using the system RNG instead: {}", e);
+                    Box::new(SystemEntropy::new())
+                })
+            }),
+            current_tab: self.start_tab.unwrap_or(config.start_tab),
+            dark_mode: config.dark_mode,
+            palette: config.palette,
+            quality_sample_size: self.sample_size.unwrap_or(config.sample_size),
+            bench_size: config.bench_size,
+            config_path: self.config_path,
+            config,
+            ..EntropyForgeApp::default()
         }
     }
 }
@@ -85,8 +250,18 @@ impl eframe::App for EntropyForgeApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::CentralPanel::default().show(ctx, |ui| {
             // Header
-            ui.heading("🔐 Entropy Forge");
+            ui.horizontal(|ui| {
+                ui.heading("🔐 Entropy Forge");
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.toggle_value(&mut self.show_settings, "⚙").on_hover_text("Settings");
+                });
+            });
             ui.label(format!("Source: {}", self.entropy.name()));
+            
+            if self.show_settings {
+                self.render_settings(ui);
+            }
+            
             ui.add_space(5.0);
             ui.separator();
             
@@ -112,9 +287,77 @@ impl eframe::App for EntropyForgeApp {
             });
         });
     }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        if let Some(path) = &self.config_path {
+            if let Err(e) = self.current_config().save(path) {
+                log::warn!("could not save settings to {}: {}", path.display(), e);
+            }
+        }
+    }
 }
 
+/// Builds a fresh source for the comparison table
+type SourceBuilder = fn() -> Box<dyn EntropySource>;
+
 impl EntropyForgeApp {
+    /// Bytes generated when measuring the SystemEntropy baseline
+    const BENCH_BASELINE_BYTES: usize = 1_000_000;
+    
+    /// Share of total NIST runtime above which a test is marked slow
+    const SLOW_TEST_SHARE: f64 = 0.25;
+    
+    /// Keystream bytes kept for the Use tab's grid
+    const CIPHER_STATE_BYTES: usize = 1_024;
+    
+    /// Random PBKDF2 salt drawn per encryption and prepended to the
+    /// ciphertext, so one key never yields the same keystream twice
+    const CIPHER_SALT_BYTES: usize = 16;
+    
+    /// File the keystream grid is saved to, in the working directory
+    const KEYSTREAM_PNG: &str = "keystream.png";
+    
+    /// Time to generate `size` bytes, re-calibrating only when the size
+    /// changes rather than every frame
+    ///
+    /// Calibrates on a fresh source built from `entropy`'s config, so the
+    /// live source is neither advanced nor drained. `None` for finite or
+    /// throttled sources and for sources without a buildable config, such
+    /// as hardware and jitter sources that are slow to draw from.
+    fn cached_estimate(entropy: &dyn EntropySource, cache: &mut Option<(usize, Option<Duration>)>, size: usize) -> Option<Duration> {
+        match *cache {
+            Some((cached_size, estimate)) if cached_size == size => estimate,
+            _ => {
+                let estimate = Self::fresh_calibration_source(entropy)
+                    .map(|mut fresh| PerformanceBench::estimate_duration(&mut fresh, size));
+                *cache = Some((size, estimate));
+                estimate
+            }
+        }
+    }
+    
+    /// A new source like `entropy` that is safe to draw calibration bytes from
+    fn fresh_calibration_source(entropy: &dyn EntropySource) -> Option<Box<dyn EntropySource>> {
+        if entropy.remaining_bytes().is_some() || entropy.is_throttled() {
+            return None;
+        }
+        entropy.source_config()?.build().ok()
+    }
+    
+    /// Show a generation time estimate from [`cached_estimate`](Self::cached_estimate)
+    fn render_estimate(ui: &mut egui::Ui, estimate: Option<Duration>) {
+        match estimate {
+            Some(estimate) => {
+                ui.label(format!("≈{:.2} s", estimate.as_secs_f64()))
+                    .on_hover_text("Time to generate the bytes, estimated from a short calibration run");
+            }
+            None => {
+                ui.label("≈? s")
+                    .on_hover_text("No estimate: this source can't be sampled without slowing down or using it up");
+            }
+        }
+    }
+    
     /// Helper to render consistent educational tooltips
     fn render_explanation_tooltip(ui: &mut egui::Ui, label: &str, text: &str) {
         ui.horizontal(|ui| {
@@ -123,10 +366,55 @@ impl EntropyForgeApp {
         });
     }
 
+    /// Apply the dark or light theme; call once the egui context exists
+    pub fn apply_visuals(&self, ctx: &egui::Context) {
+        let visuals = if self.dark_mode {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        };
+        ctx.set_visuals(visuals);
+    }
+    
+    /// Current settings, as saved on exit
+    ///
+    /// The tab open at exit becomes the next start tab. Sources that cannot
+    /// describe themselves keep the source from the loaded config.
+    pub fn current_config(&self) -> GuiConfig {
+        GuiConfig {
+            source: self.entropy.source_config().unwrap_or_else(|| self.config.source.clone()),
+            sample_size: self.quality_sample_size,
+            bench_size: self.bench_size,
+            dark_mode: self.dark_mode,
+            palette: self.palette,
+            start_tab: self.current_tab,
+        }
+    }
+    
+    /// Render the settings panel (theme and palette)
+    fn render_settings(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                if ui.checkbox(&mut self.dark_mode, "Dark mode").changed() {
+                    self.apply_visuals(ui.ctx());
+                }
+                
+                ui.add_space(20.0);
+                ui.label("Result colors:");
+                ui.radio_value(&mut self.palette, Palette::Standard, "Standard");
+                ui.radio_value(&mut self.palette, Palette::ColorblindSafe, "Colorblind-safe");
+            });
+        });
+    }
+    
     /// Render the "Use" tab (stream cipher)
     fn render_use_tab(&mut self, ui: &mut egui::Ui) {
         ui.heading("Stream Cipher");
         ui.label("Encrypt or decrypt data using the entropy source as keystream.");
+        ui.colored_label(
+            self.palette.fail_color(),
+            "⚠ For learning only: there is no authentication tag, so tampering with the ciphertext goes undetected.",
+        );
         ui.add_space(10.0);
         
         // Input
@@ -140,55 +428,97 @@ impl EntropyForgeApp {
             }
         });
         
-        ui.checkbox(&mut self.cipher_hex, "Display output as hex");
+        ui.horizontal(|ui| {
+            ui.label("Key:");
+            ui.text_edit_singleline(&mut self.cipher_key);
+            ui.label("ℹ").on_hover_text(
+                "Each encryption mixes the key with a fresh random salt, stored in the first 16 bytes of the output.\nDecrypting reads the salt back, so only the key is needed."
+            );
+        });
+        
+        ui.horizontal(|ui| {
+            ui.label("Format:");
+            egui::ComboBox::from_id_salt("cipher_format")
+                .selected_text(self.cipher_format.label())
+                .show_ui(ui, |ui| {
+                    for format in OutputFormat::ALL {
+                        ui.selectable_value(&mut self.cipher_format, format, format.label());
+                    }
+                });
+        });
         
         ui.add_space(10.0);
         
-        // Encrypt button
-        if ui.button("🔒 Encrypt / Decrypt").clicked() {
-            // Create a temporary entropy source for the cipher
-            let temp_entropy = SystemEntropy::new();
-            let mut cipher = StreamCipher::new(temp_entropy);
-            let output = cipher.process(self.cipher_input.as_bytes());
-
-            self.cipher_output = if self.cipher_hex {
-                hex::encode(&output)
-            } else {
-                String::from_utf8_lossy(&output).to_string()
-            };
-
-            self.cipher_state = cipher.state().to_vec();
+        ui.horizontal(|ui| {
+            if ui.button("🔒 Encrypt").on_hover_text("Encrypt the input text").clicked() {
+                self.run_cipher(false);
+            }
+            if ui.button("🔓 Decrypt").on_hover_text("Decrypt the input, given in the selected format").clicked() {
+                self.run_cipher(true);
+            }
+        });
+        
+        if let Some(ref warning) = self.cipher_warning {
+            ui.colored_label(self.palette.fail_color(), format!("⚠ {}", warning));
         }
         
         ui.add_space(10.0);
         
-        // Output
-        ui.label("Output:");
+        // Output: plaintext after decrypting, encoded ciphertext otherwise.
+        // Text that isn't valid UTF-8 would be mangled, so offer escapes.
+        let as_text = self.cipher_decrypted || self.cipher_format == OutputFormat::Text;
+        let valid_utf8 = std::str::from_utf8(&self.cipher_output).is_ok();
+        let output = if as_text && (self.cipher_escape || !valid_utf8) {
+            ui.horizontal(|ui| {
+                ui.label("Output (text):");
+                ui.checkbox(&mut self.cipher_escape, "Show as escaped bytes");
+            });
+            if !valid_utf8 && !self.cipher_escape {
+                ui.colored_label(self.palette.fail_color(),
+                    "⚠ Output is not valid UTF-8; � marks bytes that were replaced and cannot be recovered");
+            }
+            if self.cipher_escape {
+                crypto::escape_bytes(&self.cipher_output)
+            } else {
+                String::from_utf8_lossy(&self.cipher_output).into_owned()
+            }
+        } else if as_text {
+            ui.label("Output (text):");
+            String::from_utf8_lossy(&self.cipher_output).into_owned()
+        } else {
+            ui.label(format!("Output ({}):", self.cipher_format.label()));
+            crypto::encode(&self.cipher_output, self.cipher_format)
+        };
         ui.add(
-            egui::TextEdit::multiline(&mut self.cipher_output.as_str())
+            egui::TextEdit::multiline(&mut output.as_str())
                 .desired_width(f32::INFINITY)
                 .desired_rows(3)
         );
+        if !self.cipher_output.is_empty() && ui.button("⬆ Use as input").clicked() {
+            self.cipher_input = output;
+        }
         
         ui.add_space(20.0);
         
         // Cipher state visualization
         if !self.cipher_state.is_empty() {
-            ui.heading("Cipher State (first 64 bytes of keystream)");
+            ui.heading(format!("Cipher State (last {} bytes of keystream)", self.cipher_state.len()));
             ui.add_space(5.0);
             
-            // Display as 8x8 grid
+            // Near-square grid, cells shrinking as the keystream grows
+            let (columns, cell) = Self::keystream_grid_layout(self.cipher_state.len());
+            let spacing = (cell / 8.0).max(1.0);
             egui::Grid::new("cipher_state_grid")
-                .spacing([4.0, 4.0])
+                .spacing([spacing, spacing])
                 .show(ui, |ui| {
-                    for (i, &byte) in self.cipher_state.iter().take(64).enumerate() {
-                        if i % 8 == 0 && i > 0 {
+                    for (i, &byte) in self.cipher_state.iter().enumerate() {
+                        if i % columns == 0 && i > 0 {
                             ui.end_row();
                         }
                         
                         let color = egui::Color32::from_rgb(byte, byte, byte);
                         let (rect, response) = ui.allocate_exact_size(
-                            egui::vec2(30.0, 30.0),
+                            egui::vec2(cell, cell),
                             egui::Sense::hover()
                         );
                         ui.painter().rect_filled(rect, 2.0, color);
@@ -197,7 +527,161 @@ impl EntropyForgeApp {
                         response.on_hover_text(format!("Byte {}: 0x{:02X} ({})", i, byte, byte));
                     }
                 });
+            
+            ui.horizontal(|ui| {
+                if ui.button("💾 Save as PNG").clicked() {
+                    // Same layout as the grid above
+                    let saved = crypto::keystream_image_scaled(&self.cipher_state, columns, cell as usize)
+                        .map_err(|e| e.to_string())
+                        .and_then(|png| std::fs::write(Self::KEYSTREAM_PNG, png).map_err(|e| e.to_string()));
+                    self.cipher_export_status = Some(match saved {
+                        Ok(()) => format!("Saved {}", Self::KEYSTREAM_PNG),
+                        Err(e) => format!("Could not save {}: {}", Self::KEYSTREAM_PNG, e),
+                    });
+                }
+                if let Some(ref status) = self.cipher_export_status {
+                    ui.label(status);
+                }
+            });
+            
+            ui.add_space(5.0);
+            ui.label(format!("Keystream ({}):", self.cipher_format.label()));
+            let keystream = crypto::encode(&self.cipher_state, self.cipher_format);
+            ui.add(
+                egui::TextEdit::multiline(&mut keystream.as_str())
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(2)
+            );
+        }
+    }
+    
+    /// Encrypt or decrypt the Use tab input under the current key
+    fn run_cipher(&mut self, decrypt: bool) {
+        self.cipher_output.clear();
+        self.cipher_state.clear();
+        self.cipher_decrypted = decrypt;
+        
+        if self.cipher_key.is_empty() {
+            self.cipher_warning = Some("Enter a key first".to_string());
+            return;
         }
+        
+        // Ciphertext is salt ‖ body; encrypting draws a fresh salt
+        let (salt, input) = if decrypt {
+            let mut bytes = match crypto::decode(&self.cipher_input, self.cipher_format) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    self.cipher_warning = Some(format!("Input is not valid {}: {}", self.cipher_format.label(), e));
+                    return;
+                }
+            };
+            if bytes.len() < Self::CIPHER_SALT_BYTES {
+                self.cipher_warning = Some(format!(
+                    "Input is {} bytes, shorter than its {}-byte salt",
+                    bytes.len(),
+                    Self::CIPHER_SALT_BYTES
+                ));
+                return;
+            }
+            let body = bytes.split_off(Self::CIPHER_SALT_BYTES);
+            (bytes, body)
+        } else {
+            let mut salt = vec![0u8; Self::CIPHER_SALT_BYTES];
+            SystemEntropy::new().fill_bytes(&mut salt);
+            (salt, self.cipher_input.as_bytes().to_vec())
+        };
+        
+        let entropy = PassphraseEntropy::new(&self.cipher_key, &salt);
+        let result = StreamCipher::new_secure(entropy).and_then(|mut cipher| {
+            cipher.set_state_capacity(Self::CIPHER_STATE_BYTES);
+            let output = if decrypt {
+                cipher.decrypt(&input)?
+            } else {
+                let mut output = salt.clone();
+                output.extend(cipher.encrypt(&input)?);
+                output
+            };
+            Ok((output, cipher.state().to_vec()))
+        });
+        
+        match result {
+            Ok((output, state)) => {
+                self.cipher_output = output;
+                self.cipher_state = state;
+                self.cipher_warning = None;
+            }
+            Err(e) => self.cipher_warning = Some(format!("Refusing to run: {}", e)),
+        }
+    }
+    
+    /// Draw or decode a sample and run the metrics and NIST suite on it
+    fn run_quality_tests(&mut self) {
+        // Metrics and NIST results share one sample; generated samples
+        // reuse the buffer across runs, which keeps it for re-runs
+        let sampled = match self.test_input {
+            TestInput::Generated => {
+                self.quality_sample.resize(self.quality_sample_size, 0);
+                self.entropy.fill_bytes(&mut self.quality_sample);
+                true
+            }
+            TestInput::Pasted => match crypto::decode(&self.paste_input, self.paste_format) {
+                Ok(bytes) if bytes.is_empty() => {
+                    self.paste_error = Some("no data".to_string());
+                    false
+                }
+                Ok(bytes) => {
+                    self.paste_error = None;
+                    self.quality_sample = bytes;
+                    true
+                }
+                Err(e) => {
+                    self.paste_error = Some(e.to_string());
+                    false
+                }
+            },
+        };
+        
+        if sampled {
+            self.quality_metrics = Some(self.sample_metrics());
+            self.run_nist_suite();
+            self.rerun_matched = None;
+        }
+    }
+    
+    /// Switch the Test tab to a preset's sample size and tests
+    fn apply_preset(&mut self, preset: Preset) {
+        let (sample_size, suite) = preset.configure();
+        self.quality_sample_size = sample_size;
+        self.nist_suite = suite;
+        self.preset = Some(preset);
+    }
+    
+    /// Repeat the last run on the bytes it analyzed
+    ///
+    /// The tests are deterministic, so anything but a match means a bug.
+    fn rerun_quality_tests(&mut self) {
+        let previous = (self.quality_metrics.take(), std::mem::take(&mut self.nist_results));
+        self.quality_metrics = Some(self.sample_metrics());
+        self.run_nist_suite();
+        self.rerun_matched = Some(previous == (self.quality_metrics.clone(), self.nist_results.clone()));
+    }
+    
+    /// Metrics over the current sample
+    ///
+    /// Conditional entropy is left out below the size where it is too
+    /// biased to compare with Shannon entropy.
+    fn sample_metrics(&self) -> QualityMetrics {
+        let options = MetricsOptions {
+            conditional: self.quality_sample.len() >= QualityMetrics::CONDITIONAL_ENTROPY_MIN_BYTES,
+            ..Default::default()
+        };
+        QualityMetrics::from_bytes_with(&self.quality_sample, options)
+    }
+    
+    fn run_nist_suite(&mut self) {
+        let timed = self.nist_suite.run_timed(&self.quality_sample);
+        self.nist_results = timed.iter().map(|(name, p, _)| (name.clone(), *p)).collect();
+        self.nist_durations = timed.into_iter().map(|(_, _, elapsed)| elapsed).collect();
     }
     
     /// Render the "Test" tab (quality metrics)
@@ -206,41 +690,99 @@ impl EntropyForgeApp {
         ui.label("Analyze entropy quality with statistical tests.");
         ui.add_space(10.0);
         
-        // Sample size selector
+        // Input selector
         ui.horizontal(|ui| {
-            ui.label("Sample size:");
-            ui.add(egui::Slider::new(&mut self.quality_sample_size, 1_000..=1_000_000)
-                .logarithmic(true)
-                .suffix(" bytes"));
+            ui.label("Input:");
+            ui.selectable_value(&mut self.test_input, TestInput::Generated, "Sample from source");
+            ui.selectable_value(&mut self.test_input, TestInput::Pasted, "Paste hex / Base64");
         });
         
+        ui.add_space(5.0);
+        
+        match self.test_input {
+            TestInput::Generated => {
+                ui.horizontal(|ui| {
+                    ui.label("Preset:");
+                    egui::ComboBox::from_id_salt("test_preset")
+                        .selected_text(self.preset.map_or("Custom", |preset| preset.label()))
+                        .show_ui(ui, |ui| {
+                            for preset in Preset::ALL {
+                                if ui.selectable_label(self.preset == Some(preset), preset.label()).clicked() {
+                                    self.apply_preset(preset);
+                                }
+                            }
+                        });
+                    ui.label(format!("{} tests", self.nist_suite.len()));
+                });
+                
+                // Sample size selector
+                let estimate = Self::cached_estimate(&*self.entropy, &mut self.test_estimate, self.quality_sample_size);
+                ui.horizontal(|ui| {
+                    ui.label("Sample size:");
+                    ui.add(egui::Slider::new(&mut self.quality_sample_size, 1_000..=1_000_000)
+                        .logarithmic(true)
+                        .suffix(" bytes"));
+                    Self::render_estimate(ui, estimate);
+                });
+                
+                let checks = NistTests::applicable_tests(self.quality_sample_size);
+                let skipped = checks.iter().filter(|(_, applies)| !applies).count();
+                let names: Vec<_> = self.nist_suite.names().collect();
+                let recommended = NistTests::recommended_sample_size(&names);
+                ui.horizontal(|ui| {
+                    ui.label(format!("Recommended minimum: {} bytes", recommended));
+                    if self.quality_sample_size < recommended && ui.button("Set to recommended").clicked() {
+                        self.quality_sample_size = recommended;
+                    }
+                });
+                if skipped > 0 {
+                    ui.colored_label(self.palette.fail_color(), format!(
+                        "⚠ At {} bytes, {} of {} tests will be skipped",
+                        self.quality_sample_size, skipped, checks.len()
+                    ));
+                }
+            }
+            TestInput::Pasted => {
+                ui.horizontal(|ui| {
+                    ui.label("Format:");
+                    for format in [OutputFormat::Hex, OutputFormat::Base64, OutputFormat::Base64Url] {
+                        ui.selectable_value(&mut self.paste_format, format, format.label());
+                    }
+                });
+                egui::ScrollArea::vertical()
+                    .id_salt("paste_input")
+                    .max_height(120.0)
+                    .show(ui, |ui| {
+                        ui.add(egui::TextEdit::multiline(&mut self.paste_input)
+                            .hint_text("Paste captured bytes here")
+                            .desired_width(f32::INFINITY)
+                            .font(egui::TextStyle::Monospace));
+                    });
+                if let Some(ref error) = self.paste_error {
+                    ui.colored_label(self.palette.fail_color(), format!("Could not decode input: {}", error));
+                }
+            }
+        }
+        
         ui.add_space(10.0);
         
         // Run tests button
         ui.horizontal(|ui| {
-            if ui.button("🔬 Run All Tests").clicked() && !self.is_testing {
-                self.is_testing = true;
-                
-                // Run quality metrics
-                self.quality_metrics = Some(
-                    QualityMetrics::analyze(&mut *self.entropy, self.quality_sample_size)
-                );
-                
-                // Run NIST tests
-                let mut data = vec![0u8; self.quality_sample_size];
-                self.entropy.fill_bytes(&mut data);
-                
-                self.nist_results = NistTests::run_all_tests(&data)
-                    .into_iter()
-                    .map(|(name, p_value)| (name.to_string(), p_value))
-                    .collect();
-                
-                self.is_testing = false;
+            if ui.button("🔬 Run All Tests").clicked() {
+                self.run_quality_tests();
             }
             
-            if self.is_testing {
-                ui.spinner();
-                ui.label("Testing...");
+            let has_sample = self.quality_metrics.is_some() && !self.quality_sample.is_empty();
+            if ui.add_enabled(has_sample, egui::Button::new("🔁 Re-run on same data"))
+                .on_hover_text("Test the last sample again, to check a failure comes from the data rather than chance in sampling")
+                .clicked()
+            {
+                self.rerun_quality_tests();
+            }
+            match self.rerun_matched {
+                Some(true) => { ui.label("Same bytes, same results"); }
+                Some(false) => { ui.colored_label(self.palette.fail_color(), "⚠ Results changed on the same bytes"); }
+                None => {}
             }
         });
         
@@ -249,6 +791,7 @@ impl EntropyForgeApp {
         // Display results
         if let Some(ref metrics) = self.quality_metrics {
             ui.heading("Entropy Metrics");
+            ui.label(format!("{} bytes analyzed", metrics.total_bytes));
             ui.add_space(5.0);
             
             // Shannon entropy
@@ -279,9 +822,29 @@ impl EntropyForgeApp {
             ui.add_space(10.0);
             
             // Other metrics
+            if let Some(conditional) = metrics.conditional_entropy {
+                ui.label(format!("Conditional entropy: {:.4} bits/byte given the previous byte", conditional))
+                    .on_hover_text("Much lower than Shannon entropy means each byte is predictable from the one before it.\nOnly shown for samples of 1 MB or more; smaller samples make even perfect data score low.");
+            }
             ui.label(format!("Mean byte value: {:.2} (ideal: 127.5)", metrics.mean));
-            ui.label(format!("Chi-square: {:.2}", metrics.chi_square));
+            ui.label(format!("χ² = {:.1} (p = {:.2})", metrics.chi_square, metrics.chi_square_p_value));
             ui.label(format!("Longest run: {} bits", metrics.longest_run));
+            ui.label(format!(
+                "Byte range: {}–{} (median {})",
+                metrics.min_byte, metrics.max_byte, metrics.median_byte
+            ));
+            Self::render_explanation_tooltip(
+                ui,
+                "Stuck values:",
+                "How often 0x00 and 0xFF appear compared to uniform data (1.0 = as expected).\nMuch higher values suggest a source that clamps or saturates."
+            );
+            ui.label(format!(
+                "0x00 × {:.2}, 0xFF × {:.2}",
+                metrics.zero_byte_fraction, metrics.max_byte_fraction
+            ));
+            if let Some(periodicity) = metrics.periodicity_score {
+                ui.label(format!("Periodicity: {:.2} (random: ~1.0)", periodicity));
+            }
             
             ui.add_space(10.0);
             
@@ -292,6 +855,27 @@ impl EntropyForgeApp {
                 ui.label(format!("{:.1}/100", score));
             });
             
+            // The calibration only holds at its reference sample size
+            if metrics.total_bytes == QualityMetrics::CALIBRATION_SAMPLE_BYTES {
+                ui.horizontal(|ui| {
+                    Self::render_explanation_tooltip(
+                        ui,
+                        "Percentile:",
+                        "Share of known-good (SystemEntropy) samples of the same size that this sample beats on the overall score."
+                    );
+                    ui.label(format!("{:.0}", metrics.calibrated_score()));
+                });
+            }
+            
+            let nist_passed = self.nist_results.iter().filter(|(_, p)| p.is_some_and(|p| p >= 0.01)).count();
+            let nist_ran = self.nist_results.iter().filter(|(_, p)| p.is_some()).count();
+            let verdict = metrics.classify(nist_passed, nist_ran);
+            Self::render_explanation_tooltip(
+                ui,
+                &format!("Verdict: {}", verdict.label()),
+                "A summary of all metrics and NIST results.\nPassing statistical tests is necessary, but does not prove a source is secure."
+            );
+            
             ui.add_space(20.0);
             
             // NIST tests
@@ -310,23 +894,139 @@ impl EntropyForgeApp {
                     ui.label("Test");
                     ui.label("P-Value");
                     ui.label("Result");
+                    ui.label("Time");
                     ui.end_row();
                     
-                    for (name, p_value) in &self.nist_results {
-                        ui.label(name);
-                        ui.label(format!("{:.4}", p_value));
+                    let total_time: Duration = self.nist_durations.iter().sum();
+                    
+                    for ((name, p_value), elapsed) in self.nist_results.iter().zip(&self.nist_durations) {
+                        let label = ui.label(name);
+                        if let Some(test) = NistTests::describe(name) {
+                            label.on_hover_text(format!(
+                                "{}\nFailing means: {}\nNeeds at least {} bytes.",
+                                test.summary, test.failure_indicates, test.min_bytes
+                            ));
+                        }
+                        match *p_value {
+                            Some(p_value) => {
+                                ui.label(format!("{:.4}", p_value));
+                                if p_value >= 0.01 {
+                                    ui.colored_label(self.palette.pass_color(), "✓ Pass");
+                                } else {
+                                    ui.colored_label(self.palette.fail_color(), "✗ Fail");
+                                }
+                            }
+                            None => {
+                                ui.label("—");
+                                ui.label("Skipped").on_hover_text("The sample is too small for this test");
+                            }
+                        }
                         
-                        if *p_value >= 0.01 {
-                            ui.colored_label(egui::Color32::GREEN, "✓ Pass");
+                        let ms = elapsed.as_secs_f64() * 1000.0;
+                        if elapsed.as_secs_f64() > Self::SLOW_TEST_SHARE * total_time.as_secs_f64() {
+                            ui.label(format!("⏱ {:.1} ms", ms))
+                                .on_hover_text("This test dominates runtime on this sample");
                         } else {
-                            ui.colored_label(egui::Color32::RED, "✗ Fail");
+                            ui.label(format!("{:.1} ms", ms));
                         }
                         ui.end_row();
                     }
                 });
+            
+            let failures = nist_ran - nist_passed;
+            if failures > 0 {
+                let chance = NistSummary::failure_tail_probability(failures, nist_ran, 0.01);
+                let plural = if failures == 1 { "failure" } else { "failures" };
+                if chance >= 0.01 {
+                    ui.label(format!("{} {} — within expected variance", failures, plural))
+                        .on_hover_text(format!(
+                            "An ideal source fails each test 1% of the time; at least this many failures happen by chance {:.1}% of the time.",
+                            chance * 100.0
+                        ));
+                } else {
+                    ui.colored_label(
+                        self.palette.fail_color(),
+                        format!("{} {} — more than chance explains (p = {:.2e})", failures, plural, chance),
+                    );
+                }
+            }
         } else {
             ui.label("Click 'Run All Tests' to analyze entropy quality.");
         }
+        
+        ui.add_space(20.0);
+        self.render_source_comparison(ui);
+    }
+    
+    /// Columns and cell size (px) for a keystream grid of `len` bytes
+    ///
+    /// 64 bytes keep the original 8×8 grid of 30 px cells; longer
+    /// keystreams get more columns and smaller cells.
+    fn keystream_grid_layout(len: usize) -> (usize, f32) {
+        let columns = ((len as f64).sqrt().ceil() as usize).max(8);
+        let cell = (240.0 / columns as f32).clamp(6.0, 30.0).floor();
+        (columns, cell)
+    }
+    
+    /// Sources offered in the Test tab's comparison table
+    fn comparison_candidates() -> [(&'static str, SourceBuilder); 4] {
+        [
+            ("System", || Box::new(SystemEntropy::new())),
+            ("ChaCha20 (seed 0)", || Box::new(ChaChaEntropy::from_seed_u64(0))),
+            ("xoshiro256++ (seed 0)", || Box::new(XoshiroEntropy::from_seed_u64(0))),
+            ("Biased (60% ones)", || Box::new(BiasedEntropy::new(0, 0.6))),
+        ]
+    }
+    
+    /// Run the quality suite on several sources and show them side by side
+    fn render_source_comparison(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing("⚖ Compare Sources", |ui| {
+            let candidates = Self::comparison_candidates();
+            ui.horizontal(|ui| {
+                for ((name, _), selected) in candidates.iter().zip(&mut self.compare_selected) {
+                    ui.checkbox(selected, *name);
+                }
+            });
+            
+            let chosen: Vec<_> = candidates.iter()
+                .zip(&self.compare_selected)
+                .filter(|(_, &selected)| selected)
+                .map(|(candidate, _)| *candidate)
+                .collect();
+            
+            let compare = ui.add_enabled(chosen.len() >= 2, egui::Button::new("Compare"))
+                .on_disabled_hover_text("Select at least two sources");
+            if compare.clicked() {
+                let mut sources: Vec<Box<dyn EntropySource>> = chosen.iter().map(|(_, build)| build()).collect();
+                let reports = compare_sources(&mut sources, self.quality_sample_size);
+                self.compare_results = chosen.iter().map(|(name, _)| *name).zip(reports).collect();
+            }
+            
+            if self.compare_results.is_empty() {
+                return;
+            }
+            
+            ui.add_space(5.0);
+            egui::Grid::new("source_comparison")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Source");
+                    ui.label("Shannon");
+                    ui.label("Min-Entropy");
+                    ui.label("Score");
+                    ui.label("NIST Passed");
+                    ui.end_row();
+                    
+                    for (name, report) in &self.compare_results {
+                        ui.label(*name);
+                        ui.label(format!("{:.4}", report.metrics.shannon_entropy));
+                        ui.label(format!("{:.4}", report.metrics.min_entropy));
+                        ui.label(format!("{:.1}/100", report.metrics.overall_score()));
+                        ui.label(format!("{}/{}", report.nist_passed(), report.nist_applicable()));
+                        ui.end_row();
+                    }
+                });
+        });
     }
     
     /// Render the "Benchmark" tab
@@ -345,20 +1045,23 @@ impl EntropyForgeApp {
         
         ui.add_space(10.0);
         
+        let estimate = Self::cached_estimate(&*self.entropy, &mut self.bench_estimate, self.bench_size);
+        
         // Run benchmark button
         ui.horizontal(|ui| {
-            if ui.button("⚡ Run Benchmark").clicked() && !self.is_benchmarking {
-                self.is_benchmarking = true;
+            if ui.button("⚡ Run Benchmark").clicked() {
+                // Reference point, measured once on the first run
+                if self.bench_baseline.is_none() {
+                    self.bench_baseline = Some(PerformanceBench::benchmark(
+                        &mut SystemEntropy::new(),
+                        Self::BENCH_BASELINE_BYTES,
+                    ));
+                }
                 self.bench_result = Some(
-                    PerformanceBench::benchmark(&mut *self.entropy, self.bench_size)
+                    PerformanceBench::benchmark_dyn(self.entropy.as_mut(), self.bench_size)
                 );
-                self.is_benchmarking = false;
-            }
-            
-            if self.is_benchmarking {
-                ui.spinner();
-                ui.label("Benchmarking...");
             }
+            Self::render_estimate(ui, estimate);
         });
         
         ui.add_space(20.0);
@@ -378,6 +1081,20 @@ impl EntropyForgeApp {
                 ui.heading(format!("{:.2} MB/s", result.throughput_mbps));
             });
             
+            if let Some(ref baseline) = self.bench_baseline {
+                ui.horizontal(|ui| {
+                    Self::render_explanation_tooltip(
+                        ui,
+                        "Relative:",
+                        "Throughput compared with the OS random number generator (SystemEntropy),\nmeasured once on the first benchmark run."
+                    );
+                    ui.label(format!("{:.0}% of SystemEntropy ({:.2} MB/s)",
+                        PerformanceBench::relative_to(result, baseline),
+                        baseline.throughput_mbps
+                    ));
+                });
+            }
+            
             ui.add_space(5.0);
             
             // Latency
@@ -405,11 +1122,17 @@ impl EntropyForgeApp {
     /// Render the "Learn" tab
     fn render_learn_tab(&mut self, ui: &mut egui::Ui) {
         // Sub-tabs for Learn Mode
+        let previous_mode = self.learn_mode;
         ui.horizontal(|ui| {
             ui.selectable_value(&mut self.learn_mode, LearnMode::XorCipher, "XOR Cipher");
             ui.selectable_value(&mut self.learn_mode, LearnMode::ShannonEntropy, "Shannon Entropy");
             ui.selectable_value(&mut self.learn_mode, LearnMode::NistFrequency, "NIST Frequency");
+            ui.selectable_value(&mut self.learn_mode, LearnMode::BlockFrequency, "Block Frequency");
+            ui.selectable_value(&mut self.learn_mode, LearnMode::SpotThePattern, "Spot the Pattern");
         });
+        if self.learn_mode != previous_mode {
+            self.learn_random_error = None;
+        }
         ui.separator();
         ui.add_space(10.0);
 
@@ -417,6 +1140,91 @@ impl EntropyForgeApp {
             LearnMode::XorCipher => self.render_xor_visualizer(ui),
             LearnMode::ShannonEntropy => self.render_entropy_visualizer(ui),
             LearnMode::NistFrequency => self.render_nist_visualizer(ui),
+            LearnMode::BlockFrequency => self.render_block_freq_visualizer(ui),
+            LearnMode::SpotThePattern => self.render_spot_the_pattern(ui),
+        }
+    }
+
+    fn render_spot_the_pattern(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Spot the Pattern");
+        ui.label("One grid is random, the other comes from a source with 55% ones. Can you tell which?");
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            if ui.button("🎲 New Round").clicked() {
+                self.challenge = Some(Challenge::generate(&mut SystemEntropy::new()));
+                self.challenge_guess = None;
+            }
+            let (correct, rounds) = self.challenge_score;
+            if rounds > 0 {
+                ui.label(format!("Score: {}/{}", correct, rounds));
+            }
+        });
+
+        let Some(challenge) = &self.challenge else {
+            ui.label("Click 'New Round' to start.");
+            return;
+        };
+
+        ui.add_space(10.0);
+        let mut picked = None;
+        ui.horizontal_top(|ui| {
+            for (i, sample) in challenge.samples.iter().enumerate() {
+                ui.vertical(|ui| {
+                    ui.label(if i == 0 { "Left" } else { "Right" });
+                    egui::Grid::new(("challenge_grid", i))
+                        .spacing([1.0, 1.0])
+                        .show(ui, |ui| {
+                            for (j, &byte) in sample.bytes.iter().enumerate() {
+                                if j % 32 == 0 && j > 0 {
+                                    ui.end_row();
+                                }
+                                let (rect, _) = ui.allocate_exact_size(egui::vec2(7.0, 7.0), egui::Sense::hover());
+                                ui.painter().rect_filled(rect, 0.0, egui::Color32::from_rgb(byte, byte, byte));
+                            }
+                        });
+
+                    ui.add_space(5.0);
+                    match self.challenge_guess {
+                        None => {
+                            if ui.button("This one is biased").clicked() {
+                                picked = Some(i);
+                            }
+                        }
+                        Some(_) => {
+                            let source = if sample.biased { "Biased (55% ones)" } else { "SystemEntropy" };
+                            ui.label(egui::RichText::new(source).strong());
+                            ui.colored_label(
+                                self.palette.result_color(sample.passed()),
+                                format!("NIST: {}/{} passed", sample.summary.passed, sample.summary.total()),
+                            );
+                            if let Some((name, p)) = sample.summary.weakest() {
+                                ui.label(format!("Lowest p-value: {} ({:.4})", name, p));
+                            }
+                        }
+                    }
+                });
+                ui.add_space(20.0);
+            }
+        });
+
+        if let Some(guess) = picked {
+            let correct = challenge.is_correct(guess);
+            self.challenge_guess = Some(guess);
+            self.challenge_score.1 += 1;
+            if correct {
+                self.challenge_score.0 += 1;
+            }
+        }
+
+        if let (Some(guess), Some(challenge)) = (self.challenge_guess, &self.challenge) {
+            ui.add_space(10.0);
+            if challenge.is_correct(guess) {
+                ui.colored_label(self.palette.pass_color(), "✓ Correct!");
+            } else {
+                ui.colored_label(self.palette.fail_color(), "✗ Not this time.");
+            }
+            ui.label("The bias is nearly invisible to the eye, but the statistical tests find it easily.");
         }
     }
 
@@ -609,7 +1417,18 @@ impl EntropyForgeApp {
             if ui.button("Calculate").clicked() {
                 self.entropy_process.start(&self.entropy_process.input.clone());
             }
+            if ui.button("Generate Random").clicked() {
+                let count = self.entropy_process.random_len;
+                self.learn_random_error = self.entropy_process.generate_random(&mut *self.entropy, count)
+                    .err()
+                    .map(|e| format!("Could not generate: {}", e));
+            }
+            ui.add(egui::DragValue::new(&mut self.entropy_process.random_len).range(1..=1024).suffix(" chars"));
         });
+        
+        if let Some(ref error) = self.learn_random_error {
+            ui.colored_label(self.palette.fail_color(), format!("⚠ {}", error));
+        }
 
         ui.add_space(20.0);
 
@@ -655,7 +1474,7 @@ impl EntropyForgeApp {
 
                             for &byte in bytes {
                                 let count = step.byte_counts[&byte];
-                                let char_repr = if byte >= 32 && byte <= 126 {
+                                let char_repr = if (32..=126).contains(&byte) {
                                     (byte as char).to_string()
                                 } else {
                                     format!("0x{:02X}", byte)
@@ -773,9 +1592,24 @@ impl EntropyForgeApp {
                 self.nist_process.start(&self.nist_process.input_text.clone());
             }
             if ui.button("Generate Random").clicked() {
-                self.nist_process.generate_random(16);
+                let count = self.nist_process.random_len;
+                self.learn_random_error = self.nist_process.generate_random(&mut *self.entropy, count)
+                    .err()
+                    .map(|e| format!("Could not generate: {}", e));
             }
+            if ui.button("Show Failing Example")
+                .on_hover_text("Generate text biased towards 1 bits, which always fails")
+                .clicked()
+            {
+                let count = self.nist_process.random_len;
+                self.nist_process.generate_failing(&mut *self.entropy, count);
+            }
+            ui.add(egui::DragValue::new(&mut self.nist_process.random_len).range(1..=1024).suffix(" chars"));
         });
+        
+        if let Some(ref error) = self.learn_random_error {
+            ui.colored_label(self.palette.fail_color(), format!("⚠ {}", error));
+        }
 
         ui.add_space(20.0);
 
@@ -872,9 +1706,12 @@ impl EntropyForgeApp {
                                  if step.step_type == NistStepType::Interpret {
                                      ui.add_space(10.0);
                                      if step.passed {
-                                         ui.colored_label(egui::Color32::GREEN, "✅ PASS: The sequence looks random.");
+                                         ui.colored_label(self.palette.pass_color(), "✅ PASS: The sequence looks random.");
                                      } else {
-                                         ui.colored_label(egui::Color32::RED, "❌ FAIL: The sequence has too many 1s or 0s.");
+                                         ui.colored_label(self.palette.fail_color(), "❌ FAIL: The sequence has too many 1s or 0s.");
+                                     }
+                                     if let Some(explanation) = step.explanation() {
+                                         ui.label(explanation);
                                      }
                                      ui.label("(Threshold: P-value ≥ 0.01)");
                                  }
@@ -911,4 +1748,276 @@ impl EntropyForgeApp {
             ui.label("Enter text or generate random bytes to start.");
         }
     }
+
+    fn render_block_freq_visualizer(&mut self, ui: &mut egui::Ui) {
+        use crate::learn::block_freq_visual::BlockFreqStepType;
+
+        // Update animation state if playing
+        let time = ui.input(|i| i.time);
+        self.block_freq_process.update(time);
+        if self.block_freq_process.is_playing {
+            ui.ctx().request_repaint();
+        }
+
+        ui.heading("Learn NIST Block Frequency Test");
+        ui.label("Checks that 1s and 0s are balanced inside every block, not just overall.");
+        ui.add_space(10.0);
+
+        // Input Section
+        ui.horizontal(|ui| {
+            ui.label("Input:");
+            ui.text_edit_singleline(&mut self.block_freq_process.input_text);
+            ui.label("Block size (bits):");
+            ui.add(egui::DragValue::new(&mut self.block_freq_process.block_size).range(2..=128));
+            if ui.button("Analyze").clicked() {
+                self.block_freq_process.start(&self.block_freq_process.input_text.clone());
+            }
+        });
+
+        ui.add_space(20.0);
+
+        if let Some(step) = self.block_freq_process.current_step() {
+            let total_steps = self.block_freq_process.steps.len();
+            let current_idx = self.block_freq_process.current_step_index + 1;
+
+            let step_title = match step.step_type {
+                BlockFreqStepType::ConvertToBits => "Convert to Bits",
+                BlockFreqStepType::SplitIntoBlocks => "Split into Blocks",
+                BlockFreqStepType::CalculateProportions => "Proportion of Ones per Block",
+                BlockFreqStepType::AccumulateChiSquare => "Accumulate χ²",
+                BlockFreqStepType::CalculatePValue => "Calculate P-Value",
+                BlockFreqStepType::Interpret => "Interpretation",
+            };
+
+            ui.heading(format!("Step {} of {}: {}", current_idx, total_steps, step_title));
+            ui.add_space(10.0);
+
+            egui::Frame::canvas(ui.style()).show(ui, |ui| {
+                ui.set_min_width(600.0);
+                ui.vertical(|ui| {
+                    ui.add_space(10.0);
+
+                    // Bit visualization, grouped by block once split
+                    let group = if step.step_type == BlockFreqStepType::ConvertToBits { 8 } else { step.block_size };
+                    let used_bits = step.bits.len() / step.block_size * step.block_size;
+                    ui.label(egui::RichText::new("Bit Sequence:").strong());
+                    ui.horizontal_wrapped(|ui| {
+                        for (i, &bit) in step.bits.iter().enumerate() {
+                            if i > 0 && i % group == 0 {
+                                ui.add_space(8.0);
+                            }
+                            let dropped = step.step_type != BlockFreqStepType::ConvertToBits && i >= used_bits;
+                            let color = if dropped {
+                                egui::Color32::DARK_GRAY
+                            } else if bit == 1 {
+                                egui::Color32::GREEN
+                            } else {
+                                egui::Color32::LIGHT_GRAY
+                            };
+                            ui.label(egui::RichText::new(format!("{}", bit)).color(color).monospace().strong());
+                        }
+                    });
+                    if step.step_type != BlockFreqStepType::ConvertToBits && used_bits < step.bits.len() {
+                        ui.label(format!("{} trailing bits don't fill a block and are ignored.", step.bits.len() - used_bits));
+                    }
+
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+
+                    // One cell per block: white at π = 0.5, shading towards red as it deviates
+                    if !step.proportions.is_empty() {
+                        ui.label(egui::RichText::new("Ones-proportion per block (ideal 0.5):").strong());
+                        ui.horizontal_wrapped(|ui| {
+                            for (i, &pi) in step.proportions.iter().enumerate() {
+                                let deviation = ((pi - 0.5).abs() * 2.0) as f32;
+                                let fade = (255.0 * (1.0 - deviation)) as u8;
+                                let fill = egui::Color32::from_rgb(255, fade, fade);
+                                let (rect, response) = ui.allocate_exact_size(egui::vec2(48.0, 32.0), egui::Sense::hover());
+                                ui.painter().rect_filled(rect, 2.0, fill);
+                                ui.painter().text(
+                                    rect.center(),
+                                    egui::Align2::CENTER_CENTER,
+                                    format!("{:.2}", pi),
+                                    egui::FontId::monospace(12.0),
+                                    egui::Color32::BLACK,
+                                );
+
+                                let mut hover = format!("Block {}: π = {:.3}", i + 1, pi);
+                                if let Some(running) = step.running_chi_square.get(i) {
+                                    hover.push_str(&format!("\nχ² so far: {:.3}", running));
+                                }
+                                response.on_hover_text(hover);
+                            }
+                        });
+                    }
+
+                    ui.add_space(20.0);
+
+                    // Stats and interpretation
+                    if matches!(step.step_type, BlockFreqStepType::AccumulateChiSquare | BlockFreqStepType::CalculatePValue | BlockFreqStepType::Interpret) {
+                        ui.group(|ui| {
+                            ui.vertical(|ui| {
+                                ui.label(format!("Blocks (N): {}   Block size (M): {}", step.proportions.len(), step.block_size));
+                                ui.label(format!("χ² = 4M Σ(π − ½)²: {:.4}", step.chi_square));
+
+                                if matches!(step.step_type, BlockFreqStepType::CalculatePValue | BlockFreqStepType::Interpret) {
+                                    ui.add_space(5.0);
+                                    ui.label(format!("P-Value (igamc(N/2, χ²/2)): {:.4}", step.p_value));
+                                }
+
+                                if step.step_type == BlockFreqStepType::Interpret {
+                                    ui.add_space(10.0);
+                                    if step.passed {
+                                        ui.colored_label(self.palette.pass_color(), "✅ PASS: Every block is reasonably balanced.");
+                                    } else {
+                                        ui.colored_label(self.palette.fail_color(), "❌ FAIL: Some blocks have too many 1s or 0s.");
+                                    }
+                                    ui.label("(Threshold: P-value ≥ 0.01)");
+                                }
+                            });
+                        });
+                    }
+
+                    ui.add_space(10.0);
+                });
+            });
+
+            ui.add_space(20.0);
+
+            // Controls
+            ui.horizontal(|ui| {
+                if ui.button("⬅ Previous").clicked() {
+                    self.block_freq_process.prev_step();
+                }
+
+                let play_label = if self.block_freq_process.is_playing { "⏸ Pause" } else { "▶ Play" };
+                if ui.button(play_label).clicked() {
+                    self.block_freq_process.toggle_play();
+                }
+
+                if ui.button("Next ➡").clicked() {
+                    self.block_freq_process.next_step();
+                }
+
+                ui.add_space(20.0);
+                ui.label("Speed:");
+                ui.add(egui::Slider::new(&mut self.block_freq_process.speed, 0.1..=5.0).text("steps/s"));
+            });
+        } else {
+            ui.label("Enter text and click Analyze to start.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entropy::{MockEntropy, RateLimitedEntropy, RecordedEntropy, SourceConfig};
+
+    #[test]
+    fn test_builder_sets_fields() {
+        let app = EntropyForgeApp::builder()
+            .source(Box::new(MockEntropy::new(3)))
+            .sample_size(12_345)
+            .start_tab(Tab::Test)
+            .build();
+
+        assert_eq!(app.entropy.name(), MockEntropy::new(3).name());
+        assert_eq!(app.quality_sample_size, 12_345);
+        assert_eq!(app.current_tab, Tab::Test);
+
+        // Unset options match the defaults
+        let plain = EntropyForgeApp::builder().build();
+        let default = EntropyForgeApp::default();
+        assert_eq!(plain.entropy.name(), default.entropy.name());
+        assert_eq!(plain.quality_sample_size, default.quality_sample_size);
+        assert_eq!(plain.current_tab, Tab::Use);
+    }
+
+    #[test]
+    fn test_builder_applies_config() {
+        let config = GuiConfig {
+            source: SourceConfig::Mock { seed: 5 },
+            sample_size: 2_000,
+            bench_size: 3_000,
+            dark_mode: false,
+            palette: Palette::ColorblindSafe,
+            start_tab: Tab::Benchmark,
+        };
+
+        let app = EntropyForgeApp::builder().config(config.clone()).build();
+        assert_eq!(app.entropy.source_config(), Some(config.source.clone()));
+        assert_eq!(app.current_config(), config);
+
+        // Explicit builder options win over the config
+        let app = EntropyForgeApp::builder()
+            .config(config.clone())
+            .sample_size(10)
+            .start_tab(Tab::Learn)
+            .build();
+        assert_eq!(app.quality_sample_size, 10);
+        assert_eq!(app.current_tab, Tab::Learn);
+        assert_eq!(app.bench_size, 3_000);
+    }
+
+    #[test]
+    fn test_keystream_grid_layout() {
+        assert_eq!(EntropyForgeApp::keystream_grid_layout(13), (8, 30.0));
+        assert_eq!(EntropyForgeApp::keystream_grid_layout(64), (8, 30.0));
+        assert_eq!(EntropyForgeApp::keystream_grid_layout(1_024), (32, 7.0));
+    }
+
+    #[test]
+    fn test_rerun_reproduces_results() {
+        let mut app = EntropyForgeApp::builder()
+            .source(Box::new(MockEntropy::new(5)))
+            .sample_size(4_000)
+            .build();
+
+        app.run_quality_tests();
+        let sample = app.quality_sample.clone();
+        let (metrics, results) = (app.quality_metrics.clone(), app.nist_results.clone());
+        assert_eq!(sample.len(), 4_000);
+        assert_eq!(app.rerun_matched, None);
+
+        app.rerun_quality_tests();
+        assert_eq!(app.rerun_matched, Some(true));
+        assert_eq!(app.quality_sample, sample);
+        assert_eq!(app.quality_metrics, metrics);
+        assert_eq!(app.nist_results, results);
+
+        // A fresh run draws new bytes and clears the comparison
+        app.run_quality_tests();
+        assert_ne!(app.quality_sample, sample);
+        assert_eq!(app.rerun_matched, None);
+    }
+
+    #[test]
+    fn test_estimate_leaves_live_source_alone() {
+        let mut live = MockEntropy::new(4);
+        let mut cache = None;
+        assert!(EntropyForgeApp::cached_estimate(&live, &mut cache, 100_000).is_some());
+        assert_eq!(live.next_u64(), MockEntropy::new(4).next_u64());
+
+        // Finite, throttled and unbuildable sources get no estimate
+        let recorded = RecordedEntropy::new(vec![1; 1_000]);
+        let throttled = RateLimitedEntropy::with_bytes_per_second(MockEntropy::new(4), 10.0);
+        let keyed = ChaChaEntropy::new([1; 32]);
+        for source in [&recorded as &dyn EntropySource, &throttled, &keyed] {
+            assert_eq!(EntropyForgeApp::cached_estimate(source, &mut None, 100_000), None, "{}", source.name());
+        }
+        assert_eq!(recorded.remaining_bytes(), Some(1_000));
+    }
+
+    #[test]
+    fn test_preset_configures_test_tab() {
+        let mut app = EntropyForgeApp::builder().source(Box::new(MockEntropy::new(2))).build();
+        app.apply_preset(Preset::QuickSmoke);
+        assert_eq!(app.quality_sample_size, Preset::QuickSmoke.sample_size());
+
+        app.run_quality_tests();
+        let names: Vec<&str> = app.nist_results.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, Preset::QuickSmoke.suite().names().collect::<Vec<_>>());
+    }
 }
@@ -0,0 +1,183 @@
+//! Persistent GUI defaults, stored as TOML
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use crate::entropy::SourceConfig;
+use super::{Palette, Tab};
+
+/// Errors from loading or saving a [`GuiConfig`]
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// The file could not be read or written
+    #[error("config file: {0}")]
+    Io(#[from] io::Error),
+
+    /// The file is not valid TOML or has the wrong shape
+    #[error("invalid config: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    /// The config could not be represented as TOML
+    #[error("cannot serialize config: {0}")]
+    Serialize(#[from] toml::ser::Error),
+}
+
+/// Settings the GUI starts with and saves on exit
+///
+/// Missing keys fall back to their defaults, so a file only needs the
+/// settings that differ:
+///
+/// ```toml
+/// sample_size = 50000
+/// start_tab = "test"
+///
+/// [source]
+/// kind = "mock"
+/// seed = 42
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use entropy_forge::viz::config::GuiConfig;
+/// use entropy_forge::viz::Tab;
+///
+/// let config = GuiConfig::from_toml("start_tab = \"benchmark\"").unwrap();
+/// assert_eq!(config.start_tab, Tab::Benchmark);
+/// assert_eq!(config.sample_size, GuiConfig::default().sample_size);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuiConfig {
+    /// Entropy source to build on startup
+    pub source: SourceConfig,
+
+    /// Test tab sample size in bytes
+    pub sample_size: usize,
+
+    /// Benchmark tab size in bytes
+    pub bench_size: usize,
+
+    /// Dark (true) or light theme
+    pub dark_mode: bool,
+
+    /// Pass/fail colors
+    pub palette: Palette,
+
+    /// Tab shown on launch
+    pub start_tab: Tab,
+}
+
+impl Default for GuiConfig {
+    fn default() -> Self {
+        Self {
+            source: SourceConfig::System,
+            sample_size: 100_000,
+            bench_size: 1_000_000,
+            dark_mode: true,
+            palette: Palette::Standard,
+            start_tab: Tab::Use,
+        }
+    }
+}
+
+impl GuiConfig {
+    /// File name inside the user's config directory
+    pub const FILE_NAME: &str = "entropy-forge.toml";
+
+    /// Default location, e.g. `~/.config/entropy-forge.toml` on Linux
+    ///
+    /// `None` if the platform has no home directory.
+    pub fn default_path() -> Option<PathBuf> {
+        directories::BaseDirs::new().map(|dirs| dirs.config_dir().join(Self::FILE_NAME))
+    }
+
+    /// Parse a config from TOML text
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(text)?)
+    }
+
+    /// Render the config as TOML text
+    pub fn to_toml(&self) -> Result<String, ConfigError> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Read a config file
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    /// Read a config file, falling back to the defaults
+    ///
+    /// A missing file is expected on first launch; any other error is
+    /// logged before the defaults are used.
+    pub fn load_or_default(path: &Path) -> Self {
+        match Self::load(path) {
+            Ok(config) => config,
+            Err(ConfigError::Io(e)) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                log::warn!("ignoring {}: {}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Write the config, creating the parent directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), ConfigError> {
+        let text = self.to_toml()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, text)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_round_trip() {
+        let configs = [
+            GuiConfig::default(),
+            GuiConfig {
+                source: SourceConfig::ChaCha { key: [9; 32], nonce: 4, weak_seed: false },
+                sample_size: 12_345,
+                bench_size: 64,
+                dark_mode: false,
+                palette: Palette::ColorblindSafe,
+                start_tab: Tab::Learn,
+            },
+            GuiConfig {
+                source: SourceConfig::Mock { seed: 42 },
+                ..GuiConfig::default()
+            },
+        ];
+
+        for config in configs {
+            let text = config.to_toml().unwrap();
+            assert_eq!(GuiConfig::from_toml(&text).unwrap(), config, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_partial_and_invalid_files() {
+        assert_eq!(GuiConfig::from_toml("").unwrap(), GuiConfig::default());
+
+        let config = GuiConfig::from_toml("palette = \"colorblind_safe\"\n[source]\nkind = \"mock\"\nseed = 7").unwrap();
+        assert_eq!(config.palette, Palette::ColorblindSafe);
+        assert_eq!(config.source, SourceConfig::Mock { seed: 7 });
+
+        assert!(matches!(GuiConfig::from_toml("start_tab = \"nope\""), Err(ConfigError::Parse(_))));
+        assert!(matches!(GuiConfig::from_toml("sample_size = ["), Err(ConfigError::Parse(_))));
+    }
+
+    #[test]
+    fn test_load_or_default_missing_file() {
+        let path = std::env::temp_dir().join("entropy-forge-missing-dir").join(GuiConfig::FILE_NAME);
+        assert_eq!(GuiConfig::load_or_default(&path), GuiConfig::default());
+    }
+}
@@ -1,5 +1,8 @@
 //! GUI visualization using egui
 
 mod app;
+mod theme;
+pub mod config;
 
-pub use app::EntropyForgeApp;
+pub use app::{EntropyForgeApp, EntropyForgeAppBuilder, Tab};
+pub use theme::Palette;
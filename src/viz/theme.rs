@@ -0,0 +1,64 @@
+//! Color palettes for test results
+
+use eframe::egui::Color32;
+use serde::{Deserialize, Serialize};
+
+/// Palette used to color pass/fail results
+///
+/// The colorblind-safe palette uses blue/orange from the Okabe-Ito set,
+/// which stay distinguishable for the common forms of color blindness.
+/// Results always carry a ✓/✗ symbol as well, so color is never the only
+/// signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Palette {
+    /// Green for pass, red for fail
+    #[default]
+    Standard,
+    /// Blue for pass, orange for fail
+    ColorblindSafe,
+}
+
+impl Palette {
+    /// Color for a passing result
+    pub fn pass_color(self) -> Color32 {
+        match self {
+            Palette::Standard => Color32::GREEN,
+            Palette::ColorblindSafe => Color32::from_rgb(0, 114, 178),
+        }
+    }
+
+    /// Color for a failing result
+    pub fn fail_color(self) -> Color32 {
+        match self {
+            Palette::Standard => Color32::RED,
+            Palette::ColorblindSafe => Color32::from_rgb(230, 159, 0),
+        }
+    }
+
+    /// Color for a result given whether it passed
+    pub fn result_color(self, passed: bool) -> Color32 {
+        if passed {
+            self.pass_color()
+        } else {
+            self.fail_color()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palettes_are_distinct() {
+        for palette in [Palette::Standard, Palette::ColorblindSafe] {
+            assert_ne!(palette.pass_color(), palette.fail_color());
+            assert_eq!(palette.result_color(true), palette.pass_color());
+            assert_eq!(palette.result_color(false), palette.fail_color());
+        }
+
+        assert_ne!(Palette::Standard.pass_color(), Palette::ColorblindSafe.pass_color());
+        assert_ne!(Palette::Standard.fail_color(), Palette::ColorblindSafe.fail_color());
+    }
+}
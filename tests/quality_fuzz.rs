@@ -0,0 +1,173 @@
+//! Property tests: quality functions never panic and stay in range on
+//! arbitrary input
+
+use entropy_forge::quality::{BitOrder, NistTests, QualityMetrics};
+use proptest::prelude::*;
+
+/// Byte slices from empty up to a few blocks of the larger NIST tests
+fn bytes() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..2_048)
+}
+
+/// Byte slices dominated by one value, which drive runs and χ² to extremes
+fn skewed_bytes() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(prop_oneof![8 => Just(0u8), 1 => any::<u8>()], 0..2_048)
+}
+
+fn assert_p_value(name: &str, p: f64) {
+    assert!((0.0..=1.0).contains(&p), "{} returned p = {}", name, p);
+}
+
+/// `None` (not applicable) is fine; a p-value must be in range
+fn assert_optional_p_value(name: &str, p: Option<f64>) {
+    if let Some(p) = p {
+        assert_p_value(name, p);
+    }
+}
+
+fn check_metrics(data: &[u8]) {
+    let in_range = |name: &str, x: f64, max: f64| {
+        assert!(x.is_finite() && (0.0..=max).contains(&x), "{} = {} out of [0, {}]", name, x, max);
+    };
+
+    // Byte-level metrics are `None` exactly when there is no data
+    let present = |name: &str, x: Option<f64>, max: f64| {
+        assert_eq!(x.is_none(), data.is_empty(), "{} = {:?} for {} bytes", name, x, data.len());
+        if let Some(x) = x {
+            in_range(name, x, max);
+        }
+    };
+
+    present("shannon_entropy", QualityMetrics::shannon_entropy(data), 8.0);
+    present("min_entropy", QualityMetrics::min_entropy(data), 8.0);
+    present("mean", QualityMetrics::mean(data), 255.0);
+    present("byte_fraction", QualityMetrics::byte_fraction(data, 0), 256.0);
+    present("chi_square", QualityMetrics::chi_square(data), f64::MAX);
+    if let Some(chi_square) = QualityMetrics::chi_square(data) {
+        assert_p_value("chi_square_p_value", QualityMetrics::chi_square_p_value(chi_square));
+    }
+
+    match QualityMetrics::median_byte(data) {
+        Some(median) => assert!(data.contains(&median)),
+        None => assert!(data.is_empty()),
+    }
+
+    let histogram = QualityMetrics::run_length_histogram(data);
+    let bits: usize = histogram.iter().enumerate().map(|(len, count)| len * count).sum();
+    assert_eq!(bits, data.len() * 8);
+    assert!(QualityMetrics::longest_run(data) <= data.len() * 8);
+
+    let profile = QualityMetrics::autocorrelation_profile(data, 20);
+    assert_eq!(profile.len(), 20);
+    for r in profile {
+        assert!(r.is_finite() && (-1.0 - 1e-9..=1.0 + 1e-9).contains(&r), "autocorrelation = {}", r);
+    }
+
+    assert_optional_p_value("digraph", QualityMetrics::digraph_chi_square(data));
+
+    let metrics = QualityMetrics::from_bytes(data);
+    assert_eq!(metrics.total_bytes, data.len());
+    assert_p_value("metrics.chi_square_p_value", metrics.chi_square_p_value);
+    in_range("overall_score", metrics.overall_score(), 100.0);
+    metrics.classify(data.len() % 7, 6);
+
+    let normalized = metrics.normalized();
+    for x in [normalized.shannon_entropy, normalized.min_entropy, normalized.chi_square, normalized.mean] {
+        in_range("normalized", x, 1.0);
+    }
+}
+
+fn check_nist(data: &[u8]) {
+    for (name, p) in NistTests::run_all_tests(data) {
+        assert_optional_p_value(name, p);
+    }
+    assert_optional_p_value("block runs", NistTests::block_runs_test(data, 128));
+
+    for test in NistTests::registry() {
+        let p = (test.run)(data);
+        assert_eq!(p.is_some(), data.len() >= test.min_bytes, "{} = {:?} for {} bytes", test.name, p, data.len());
+    }
+
+    for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+        assert_optional_p_value("frequency", NistTests::frequency_test_with_order(data, order));
+        assert_optional_p_value("runs", NistTests::runs_test_with_order(data, order));
+        assert_optional_p_value("longest run", NistTests::longest_run_test_with_order(data, order));
+        assert_optional_p_value("serial", NistTests::serial_test_with_order(data, order));
+        assert_eq!(order.extract(data).len(), data.len() * 8);
+    }
+}
+
+proptest! {
+    #[test]
+    fn metrics_never_panic(data in bytes()) {
+        check_metrics(&data);
+    }
+
+    #[test]
+    fn metrics_never_panic_on_skewed_data(data in skewed_bytes()) {
+        check_metrics(&data);
+    }
+
+    #[test]
+    fn nist_never_panics(data in bytes()) {
+        check_nist(&data);
+    }
+
+    #[test]
+    fn nist_never_panics_on_skewed_data(data in skewed_bytes()) {
+        check_nist(&data);
+    }
+
+    #[test]
+    fn bit_functions_accept_any_elements(bits in prop::collection::vec(any::<u8>(), 0..1_024), block_size in 0usize..2_048) {
+        assert_optional_p_value("frequency_bits", NistTests::frequency_test_bits(&bits));
+        assert_optional_p_value("block_frequency_bits", NistTests::block_frequency_test_bits(&bits, block_size));
+        assert_optional_p_value("runs_bits", NistTests::runs_test_bits(&bits));
+        assert_optional_p_value("serial_bits", NistTests::serial_test_bits(&bits));
+    }
+
+    #[test]
+    fn parameterized_metrics_never_panic(data in bytes(), symbol_bits in 0usize..40, window in 0usize..512, step in 0usize..512) {
+        assert_optional_p_value("block runs", NistTests::block_runs_test(&data, window));
+
+        if let Some(h) = QualityMetrics::shannon_entropy_symbols(&data, symbol_bits) {
+            prop_assert!(h.is_finite() && h >= 0.0 && h <= symbol_bits as f64);
+        }
+
+        for h in QualityMetrics::windowed_entropy(&data, window, step) {
+            prop_assert!(h.is_finite() && (0.0..=8.0).contains(&h));
+        }
+
+        for order in 0..4 {
+            let h = QualityMetrics::conditional_entropy(&data, order);
+            prop_assert_eq!(h.is_some(), order <= 2 && data.len() > order);
+            if let Some(h) = h {
+                prop_assert!(h.is_finite() && (0.0..=8.0 + 1e-9).contains(&h), "order {} = {}", order, h);
+            }
+        }
+    }
+
+    #[test]
+    fn p_value_helpers_accept_any_float(x in any::<f64>()) {
+        assert_p_value("chi_square_p_value", QualityMetrics::chi_square_p_value(x));
+
+        let erfc = NistTests::erfc(x);
+        prop_assert!(x.is_nan() || (0.0..=2.0).contains(&erfc), "erfc({}) = {}", x, erfc);
+    }
+}
+
+#[test]
+fn huge_input_stays_in_range() {
+    // Large enough to hit the 10,000-bit block size of the longest run test
+    let mut data = vec![0u8; 1 << 20];
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte = (i.wrapping_mul(2_654_435_761) >> 13) as u8;
+    }
+
+    check_metrics(&data);
+    check_nist(&data);
+
+    let zeros = vec![0u8; 1 << 20];
+    check_metrics(&zeros);
+    check_nist(&zeros);
+}
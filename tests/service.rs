@@ -0,0 +1,85 @@
+//! Service mode: JSON requests through the handler produce well-formed responses
+
+use entropy_forge::quality::NistTests;
+use entropy_forge::service::{handle_line, serve, MAX_REQUEST_BYTES};
+use serde_json::Value;
+
+fn respond(line: &str) -> Value {
+    serde_json::from_str(&handle_line(line)).unwrap()
+}
+
+#[test]
+fn test_analyze_response_shape() {
+    let response = respond(r#"{"op":"analyze","source":{"kind":"mock","seed":42},"size":10000}"#);
+
+    assert_eq!(response["op"], "analyze");
+    let report = &response["report"];
+    assert_eq!(report["source"]["kind"], "mock");
+    assert_eq!(report["metrics"]["total_bytes"], 10_000);
+    assert_eq!(report["nist_results"].as_array().unwrap().len(), NistTests::registry().len());
+
+    // Deterministic sources answer identically every time
+    assert_eq!(respond(r#"{"op":"analyze","source":{"kind":"mock","seed":42},"size":10000}"#), response);
+}
+
+#[test]
+fn test_analyze_does_not_echo_keys() {
+    let key = "07".repeat(32);
+    let request = format!(r#"{{"op":"analyze","source":{{"kind":"chacha","key":"{}"}},"size":1000}}"#, key);
+    let line = handle_line(&request);
+    assert!(!line.contains(&key), "{}", line);
+
+    let response: Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(response["report"]["source"]["kind"], "chacha-fingerprint");
+
+    // A fingerprint can't rebuild the source
+    let fingerprinted = format!(r#"{{"op":"generate","source":{},"size":4}}"#, response["report"]["source"]);
+    assert_eq!(respond(&fingerprinted)["op"], "error");
+}
+
+#[test]
+fn test_nist_benchmark_generate() {
+    let nist = respond(r#"{"op":"nist","source":{"kind":"system"},"size":10000}"#);
+    assert_eq!(nist["op"], "nist");
+    let results = nist["results"].as_array().unwrap();
+    assert_eq!(results.len(), NistTests::registry().len());
+    assert!(results[0][0].is_string() && results[0][1].is_f64());
+    assert!(nist["passed"].as_u64().unwrap() <= results.len() as u64);
+
+    let bench = respond(r#"{"op":"benchmark","source":{"kind":"system"},"size":100000}"#);
+    assert_eq!(bench["op"], "benchmark");
+    assert_eq!(bench["bytes_generated"], 100_000);
+    assert!(bench["throughput_mbps"].as_f64().unwrap() > 0.0);
+
+    let generated = respond(r#"{"op":"generate","source":{"kind":"mock","seed":1},"size":16}"#);
+    assert_eq!(generated["op"], "generate");
+    assert_eq!(generated["hex"].as_str().unwrap().len(), 32);
+}
+
+#[test]
+fn test_errors() {
+    for line in ["not json", r#"{"op":"explode"}"#, r#"{"op":"generate","size":4}"#] {
+        let response = respond(line);
+        assert_eq!(response["op"], "error", "{}", line);
+        assert!(response["message"].is_string());
+    }
+
+    let too_big = format!(r#"{{"op":"generate","source":{{"kind":"system"}},"size":{}}}"#, MAX_REQUEST_BYTES + 1);
+    assert_eq!(respond(&too_big)["op"], "error");
+}
+
+#[test]
+fn test_serve_answers_each_line() {
+    let input = "{\"op\":\"generate\",\"source\":{\"kind\":\"mock\",\"seed\":1},\"size\":4}\n\nbad\n";
+    let mut output = Vec::new();
+    serve(input.as_bytes(), &mut output).unwrap();
+
+    let lines: Vec<Value> = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["op"], "generate");
+    assert_eq!(lines[1]["op"], "error");
+}
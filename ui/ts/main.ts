@@ -231,7 +231,7 @@ if (useHelpLink) {
                 document.getElementById('test-min-bar')!.style.width = `${(result.min_entropy / 8.0) * 100}%`;
 
                 document.getElementById('test-mean-val')!.textContent = result.mean.toFixed(3);
-                document.getElementById('test-chi-val')!.textContent = result.chi_square.toFixed(2);
+                document.getElementById('test-chi-val')!.textContent = `${result.chi_square.toFixed(1)} (p = ${result.chi_square_p_value.toFixed(2)})`;
                 document.getElementById('test-longest-run-val')!.textContent = result.longest_run.toString();
                 document.getElementById('test-score-val')!.textContent = result.overall_score.toFixed(1);
 